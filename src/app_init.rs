@@ -2,15 +2,19 @@ use std::sync::mpsc::Sender;
 
 use compact_str::ToCompactString;
 use ratatui::{Terminal, backend::CrosstermBackend};
+use tracing::warn;
 use tracing_appender::non_blocking::WorkerGuard;
 
 use crate::{
-    client::{ClientConfig, GithubApi, GithubPoller, GithubService},
+    client::{
+        ClientConfig, FixtureSource, GithubApi, GithubPoller, GithubService, WebhookReceiver,
+    },
     dispatcher::Dispatcher,
     effect_registry::EffectRegistry,
     event::{EventHandler, GlomEvent},
     glom_app::{GlomApp, GlomConfig},
     logging::{LoggingConfig, LoggingReloadHandle, init_logging},
+    prometheus::PrometheusExporter,
     result::{GlomError, Result},
     tui::Tui,
     ui::StatefulWidgets,
@@ -22,27 +26,39 @@ pub struct AppComponents {
     pub widget_states: StatefulWidgets,
     pub effects: EffectRegistry,
     pub poller: GithubPoller,
+    pub webhook: Option<WebhookReceiver>,
+    pub exporter: Option<PrometheusExporter>,
     pub _log_guard: Option<WorkerGuard>,
 }
 
+/// GitHub client components sharing one `GithubApi`
+pub struct GithubComponents {
+    pub service: GithubService,
+    pub poller: GithubPoller,
+    pub webhook: Option<WebhookReceiver>,
+    pub exporter: Option<PrometheusExporter>,
+}
+
 pub async fn initialize_app(
     config_path: std::path::PathBuf,
     config: GlomConfig,
     debug: bool,
+    fixtures: Option<FixtureSource>,
 ) -> Result<AppComponents> {
     color_eyre::install().expect("failed to install color_eyre");
 
     let event_handler = EventHandler::new(std::time::Duration::from_millis(33));
     let sender = event_handler.sender();
 
-    let (log_guard, log_reload_handle) = initialize_logging(sender.clone(), &config)?;
+    let (log_guard, log_reload_handle) = initialize_logging(Some(sender.clone()), &config)?;
     tracing::info!(version = env!("CARGO_PKG_VERSION"), "Glom TUI starting up");
 
     let tui = initialize_terminal(event_handler)?;
     let widget_states = StatefulWidgets::new(sender.clone());
 
-    let (service, poller) =
-        create_github_service_and_poller(sender.clone(), config.clone(), debug).await?;
+    let uses_fixtures = fixtures.is_some();
+    let GithubComponents { service, poller, webhook, exporter } =
+        create_github_components(sender.clone(), config.clone(), debug, fixtures).await?;
 
     // We need to move the log_reload_handle into the app, so we can't use it in AppComponents
     // Instead, we'll create a separate handle for the app and keep one for external use
@@ -51,10 +67,11 @@ pub async fn initialize_app(
         config_path,
         service,
         log_reload_handle,
+        exporter.as_ref().map(PrometheusExporter::metrics),
         &config,
     );
     app.dispatch(GlomEvent::ProjectsFetch);
-    if config == GlomConfig::default() {
+    if config == GlomConfig::default() && !uses_fixtures {
         app.dispatch(GlomEvent::ConfigOpen);
     }
 
@@ -68,12 +85,15 @@ pub async fn initialize_app(
         widget_states,
         effects,
         poller,
+        webhook,
+        exporter,
         _log_guard: log_guard,
     })
 }
 
-fn initialize_logging(
-    sender: Sender<GlomEvent>,
+/// File logging as configured; with a sender, info logs also reach the internal logs view
+pub fn initialize_logging(
+    sender: Option<Sender<GlomEvent>>,
     glom_config: &GlomConfig,
 ) -> Result<(Option<WorkerGuard>, LoggingReloadHandle)> {
     let mut logging_config = LoggingConfig::from_env();
@@ -90,10 +110,9 @@ fn initialize_logging(
         }
     }
 
-    let (log_guard, log_reload_handle) =
-        init_logging(logging_config, Some(sender)).map_err(|e| {
-            GlomError::GeneralError(format!("Failed to initialize logging: {e}").into())
-        })?;
+    let (log_guard, log_reload_handle) = init_logging(logging_config, sender).map_err(|e| {
+        GlomError::GeneralError(format!("Failed to initialize logging: {e}").into())
+    })?;
     Ok((log_guard, log_reload_handle))
 }
 
@@ -107,12 +126,28 @@ fn initialize_terminal(event_handler: EventHandler) -> Result<Tui> {
     Ok(tui)
 }
 
-async fn create_github_service_and_poller(
+pub async fn create_github_components(
     sender: Sender<GlomEvent>,
     config: GlomConfig,
     debug: bool,
-) -> Result<(GithubService, GithubPoller)> {
-    let client_config = ClientConfig::from(config).with_debug_logging(debug);
+    fixtures: Option<FixtureSource>,
+) -> Result<GithubComponents> {
+    let blank_secret = config
+        .webhook_secret
+        .as_deref()
+        .is_none_or(|secret| secret.trim().is_empty());
+    if config.webhook_listen.is_some() && blank_secret {
+        warn!("Webhook listener requires a non-empty webhook_secret; not starting it");
+        sender.dispatch(GlomEvent::AppError(GlomError::config_validation_error(
+            "webhook_secret",
+            "required and non-empty when webhook_listen is set",
+        )));
+    }
+
+    let metrics_listen = config.metrics_listen.clone();
+    let client_config = ClientConfig::from(config)
+        .with_debug_logging(debug)
+        .with_fixtures(fixtures);
 
     // Create a shared GithubApi instance
     let api = std::sync::Arc::new(GithubApi::force_new(client_config.clone())?);
@@ -120,8 +155,19 @@ async fn create_github_service_and_poller(
     // Create service using shared API
     let service = GithubService::from_api(api.clone(), sender.clone())?;
 
-    // Create poller using shared API
-    let poller = GithubPoller::new(api, sender, client_config.polling.clone());
+    // Create the optional webhook receiver using shared API
+    let webhook = client_config
+        .webhook
+        .clone()
+        .map(|webhook| WebhookReceiver::new(api.clone(), sender.clone(), webhook));
+
+    // Create poller sharing the service's request scheduler, backing off while
+    // webhooks are flowing
+    let poller = GithubPoller::new(service.clone(), sender, client_config.polling.clone())
+        .with_webhook_activity(webhook.as_ref().map(WebhookReceiver::activity));
+
+    // Create the optional Prometheus exporter, reporting the requests of the shared API
+    let exporter = metrics_listen.map(|listen| PrometheusExporter::new(listen, api.stats()));
 
-    Ok((service, poller))
+    Ok(GithubComponents { service, poller, webhook, exporter })
 }
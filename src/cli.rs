@@ -0,0 +1,727 @@
+//! Subcommands run without the TUI
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use clap::{Args, Subcommand, ValueEnum};
+use compact_str::CompactString;
+use futures::{StreamExt, TryStreamExt, stream};
+use serde::Serialize;
+
+use crate::{
+    client::{ClientConfig, ClientError, FixtureSource, GithubApi, config::RunFilter},
+    domain::{
+        Job, JobDto, Pipeline, PipelineDto, PipelineSource, PipelineStatus, Project, ProjectDto,
+    },
+    export::{DASHBOARD_BRANCHES, DashboardExport, ExportFormat},
+    git,
+    glom_app::GlomConfig,
+    history::{RunHistory, default_history_path, local_day_start, month_start},
+    id::{JobId, PipelineId, ProjectId},
+    metrics::{MetricsReport, format_percent},
+    result::{GlomError, Result},
+    snapshot::{default_snapshot_path, load_snapshot, read_snapshot},
+    ui::format_duration,
+    wait::{WaitOptions, WaitOutcome, wait_for_runs},
+};
+
+/// Exit code of failures not covered by the codes below
+const EXIT_FAILURE: i32 = 1;
+/// Exit code when the GitHub token is missing, invalid or expired
+const EXIT_AUTHENTICATION: i32 = 3;
+/// Exit code when the repository, run or job does not exist
+const EXIT_NOT_FOUND: i32 = 4;
+/// Exit code when the GitHub API rate limit is exceeded
+const EXIT_RATE_LIMITED: i32 = 5;
+/// Exit code when `glom wait` gives up, as used by `timeout(1)`
+const EXIT_TIMEOUT: i32 = 124;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Query the local history of completed runs and jobs.
+    #[command(subcommand)]
+    History(HistoryCommand),
+    /// Export the dashboard, fetching the repositories and their runs.
+    Export(ExportArgs),
+    /// List the repositories matching the configured search filter.
+    Projects(ProjectsArgs),
+    /// List the recent workflow runs of a repository.
+    Runs(RunsArgs),
+    /// List the jobs of a workflow run.
+    Jobs(JobsArgs),
+    /// Print the log of a job.
+    Log(LogArgs),
+    /// Wait for the workflow runs of a commit to complete; exits 0 only if all succeeded.
+    Wait(WaitArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HistoryCommand {
+    /// List workflows whose latest runs on a branch failed, and since when.
+    Red(RedArgs),
+    /// List jobs that failed, with their failure rate.
+    Failures(FailuresArgs),
+    /// Show the delivery metrics of a repository's default branch over 7 and 30 days.
+    Metrics(MetricsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct RedArgs {
+    /// Only include this repository, e.g. `owner/repo`.
+    #[arg(short, long)]
+    project: Option<String>,
+    /// Only include this branch.
+    #[arg(short, long)]
+    branch: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct FailuresArgs {
+    /// Only include this repository, e.g. `owner/repo`.
+    #[arg(short, long)]
+    project: Option<String>,
+    /// Only include jobs completed on or after this date; defaults to the start of the month.
+    #[arg(short, long, value_name = "YYYY-MM-DD")]
+    since: Option<NaiveDate>,
+}
+
+#[derive(Args, Debug)]
+pub struct ExportArgs {
+    /// Output format; defaults to `export_format` of the configuration.
+    #[arg(short, long, value_enum)]
+    format: Option<ExportFormat>,
+    /// Only include repositories whose name or description contains this text.
+    #[arg(long)]
+    filter: Option<String>,
+    /// Write the export to this file instead of stdout.
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// Export the projects saved by the last session of the TUI instead of fetching them.
+    #[arg(long)]
+    from_snapshot: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct MetricsArgs {
+    /// Repository, e.g. `owner/repo`.
+    #[arg(short, long)]
+    project: String,
+    /// Branch to compute the metrics of; defaults to the repository's default branch.
+    #[arg(short, long)]
+    branch: Option<String>,
+    /// Print the metrics as CSV instead of a table.
+    #[arg(long, conflicts_with = "json")]
+    csv: bool,
+    /// Print the metrics as JSON instead of a table.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Output of the subcommands querying GitHub
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum OutputFormat {
+    /// Tab-separated columns, one row per line.
+    #[default]
+    Table,
+    /// A single JSON array.
+    Json,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Output format.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Args, Debug)]
+pub struct ProjectsArgs {
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct RunsArgs {
+    /// Repository, e.g. `owner/repo`.
+    project: String,
+    /// Only include runs on this branch.
+    #[arg(short, long)]
+    branch: Option<String>,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct JobsArgs {
+    /// Id of the workflow run.
+    run_id: u64,
+    /// Repository of the run, e.g. `owner/repo`.
+    #[arg(short, long)]
+    project: String,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct LogArgs {
+    /// Id of the job.
+    job_id: u64,
+    /// Repository of the job, e.g. `owner/repo`.
+    #[arg(short, long)]
+    project: String,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct WaitArgs {
+    /// Commit to wait for; defaults to the HEAD of the git checkout in the current directory.
+    #[arg(long)]
+    sha: Option<String>,
+    /// Repository, e.g. `owner/repo`; defaults to the remote of the git checkout.
+    #[arg(short, long)]
+    project: Option<String>,
+    /// Only wait for the workflow with this name; can be repeated.
+    #[arg(short, long = "workflow", value_name = "NAME")]
+    workflows: Vec<String>,
+    /// Give up after this many seconds.
+    #[arg(short, long, value_name = "SECS", default_value_t = 3600)]
+    timeout: u64,
+    /// Seconds between polls of the runs.
+    #[arg(short, long, value_name = "SECS", default_value_t = 10)]
+    interval: u64,
+}
+
+#[derive(Serialize)]
+struct ProjectRow {
+    project: CompactString,
+    description: Option<CompactString>,
+    default_branch: CompactString,
+    url: CompactString,
+    updated_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct RunRow {
+    id: PipelineId,
+    workflow: CompactString,
+    branch: Option<CompactString>,
+    status: PipelineStatus,
+    event: PipelineSource,
+    actor: Option<CompactString>,
+    head_sha: CompactString,
+    created_at: DateTime<Utc>,
+    url: CompactString,
+}
+
+#[derive(Serialize)]
+struct JobRow {
+    id: JobId,
+    run_id: PipelineId,
+    name: CompactString,
+    status: PipelineStatus,
+    started_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    runner: Option<CompactString>,
+    url: CompactString,
+}
+
+#[derive(Serialize)]
+struct LogLine<'a> {
+    job_id: JobId,
+    line: usize,
+    text: &'a str,
+}
+
+/// Failed subcommand, with the exit code it maps to
+struct Failure {
+    code: i32,
+    message: String,
+}
+
+impl From<GlomError> for Failure {
+    fn from(e: GlomError) -> Self {
+        let code = match e {
+            GlomError::InvalidGithubToken | GlomError::ExpiredGithubToken => EXIT_AUTHENTICATION,
+            _ => EXIT_FAILURE,
+        };
+        Self { code, message: e.to_string() }
+    }
+}
+
+impl From<ClientError> for Failure {
+    fn from(e: ClientError) -> Self {
+        let code = match e {
+            ClientError::Authentication | ClientError::InvalidToken | ClientError::ExpiredToken => {
+                EXIT_AUTHENTICATION
+            },
+            ClientError::NotFound { .. } => EXIT_NOT_FOUND,
+            ClientError::RateLimit { .. } => EXIT_RATE_LIMITED,
+            _ => EXIT_FAILURE,
+        };
+        Self { code, message: e.to_string() }
+    }
+}
+
+impl From<serde_json::Error> for Failure {
+    fn from(e: serde_json::Error) -> Self {
+        Self { code: EXIT_FAILURE, message: e.to_string() }
+    }
+}
+
+/// Runs the subcommand, returning the process exit code; with fixtures, the API is
+/// answered from them and the local state is that of the fixtures
+pub fn run(command: Command, config: &GlomConfig, fixtures: Option<FixtureSource>) -> i32 {
+    let state_dir = fixtures.as_ref().map(FixtureSource::state_dir);
+    let state_dir = state_dir.as_deref();
+    let result = match command {
+        Command::History(command) => run_history(command, config, state_dir).map_err(Failure::from),
+        Command::Export(args) if args.from_snapshot => {
+            run_snapshot_export(args, config, state_dir).map_err(Failure::from)
+        },
+        command => run_remote(command, config, fixtures),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(failure) => {
+            eprintln!("glom: {}", failure.message);
+            failure.code
+        },
+    }
+}
+
+/// Runs a subcommand querying the GitHub API, without creating the TUI
+fn run_remote(
+    command: Command,
+    config: &GlomConfig,
+    fixtures: Option<FixtureSource>,
+) -> std::result::Result<(), Failure> {
+    let client_config = ClientConfig::from(config.clone()).with_fixtures(fixtures);
+    let api = GithubApi::force_new(client_config.clone())?;
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| GlomError::GeneralError(format!("Failed to create runtime: {e}").into()))?;
+
+    match command {
+        Command::History(_) => unreachable!("answered from the local history"),
+        Command::Export(args) => {
+            let projects = rt.block_on(fetch_dashboard(&api, &client_config))?;
+            write_export(args, config, projects, None).map_err(Failure::from)
+        },
+        Command::Projects(args) => {
+            let projects = rt.block_on(api.get_projects(&client_config.default_project_query()))?;
+            let rows: Vec<ProjectRow> = projects
+                .into_iter()
+                .map(ProjectRow::from)
+                .collect();
+            print_rows(&rows, args.output.format, |p| {
+                format!(
+                    "{}\t{}\t{}\t{}",
+                    p.project,
+                    p.default_branch,
+                    p.updated_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    p.url,
+                )
+            })
+        },
+        Command::Runs(args) => {
+            let project_id = ProjectId::new(args.project.as_str());
+            let filter = RunFilter {
+                branch: args.branch.map(Into::into),
+                ..client_config.run_filter(&project_id)
+            };
+            let query = client_config
+                .default_pipeline_query()
+                .with_filter(filter);
+
+            let runs = rt.block_on(api.get_pipelines(project_id, &query))?;
+            let rows: Vec<RunRow> = runs.into_iter().map(RunRow::from).collect();
+            print_rows(&rows, args.output.format, |r| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    r.id,
+                    r.workflow,
+                    r.branch.as_deref().unwrap_or("-"),
+                    r.status.as_api_str(),
+                    r.event.to_string(),
+                    r.created_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    r.url,
+                )
+            })
+        },
+        Command::Jobs(args) => {
+            let project_id = ProjectId::new(args.project.as_str());
+            let jobs = rt.block_on(api.get_jobs(project_id, PipelineId::new(args.run_id)))?;
+            let rows: Vec<JobRow> = jobs.into_iter().map(JobRow::from).collect();
+            print_rows(&rows, args.output.format, |j| {
+                let duration = j
+                    .started_at
+                    .zip(j.completed_at)
+                    .map_or_else(|| "-".into(), |(start, end)| format_duration(end - start));
+                format!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    j.id,
+                    j.name,
+                    j.status.as_api_str(),
+                    duration,
+                    j.url,
+                )
+            })
+        },
+        Command::Log(args) => {
+            let project_id = ProjectId::new(args.project.as_str());
+            let job_id = JobId::new(args.job_id);
+            let log = rt.block_on(api.get_job_trace(project_id, job_id))?;
+
+            match args.output.format {
+                OutputFormat::Table => print!("{log}"),
+                OutputFormat::Json => {
+                    let json = serde_json::json!({ "job_id": job_id, "log": log });
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                },
+                OutputFormat::Ndjson => {
+                    for (idx, text) in log.lines().enumerate() {
+                        let line = LogLine { job_id, line: idx + 1, text };
+                        println!("{}", serde_json::to_string(&line)?);
+                    }
+                },
+            }
+            Ok(())
+        },
+        Command::Wait(args) => {
+            let head_sha = match args.sha {
+                Some(sha) => sha.into(),
+                None => git::head_sha()?,
+            };
+            let project_id = match args.project {
+                Some(project) => ProjectId::new(project),
+                None => git::remote_project()?,
+            };
+
+            let options = WaitOptions {
+                project_id,
+                head_sha,
+                workflows: args.workflows,
+                timeout: Duration::from_secs(args.timeout),
+                interval: Duration::from_secs(args.interval.max(1)),
+            };
+
+            match wait_for_runs(&rt, &api, &client_config, &options)? {
+                WaitOutcome::Succeeded => Ok(()),
+                WaitOutcome::Failed => Err(Failure {
+                    code: EXIT_FAILURE,
+                    message: format!("runs of {} failed", options.head_sha),
+                }),
+                WaitOutcome::TimedOut => Err(Failure {
+                    code: EXIT_TIMEOUT,
+                    message: format!(
+                        "timed out after {}s waiting for the runs of {}",
+                        args.timeout, options.head_sha
+                    ),
+                }),
+            }
+        },
+    }
+}
+
+fn print_rows<T: Serialize>(
+    rows: &[T],
+    format: OutputFormat,
+    table_row: impl Fn(&T) -> String,
+) -> std::result::Result<(), Failure> {
+    match format {
+        OutputFormat::Table => rows
+            .iter()
+            .for_each(|row| println!("{}", table_row(row))),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(rows)?),
+        OutputFormat::Ndjson => {
+            for row in rows {
+                println!("{}", serde_json::to_string(row)?);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+impl From<ProjectDto> for ProjectRow {
+    fn from(p: ProjectDto) -> Self {
+        Self {
+            project: p.full_name,
+            description: p.description,
+            default_branch: p.default_branch,
+            url: p.html_url,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+impl From<PipelineDto> for RunRow {
+    fn from(p: PipelineDto) -> Self {
+        Self {
+            id: p.id,
+            workflow: p.name,
+            branch: p.head_branch,
+            status: PipelineStatus::resolve(p.status, p.conclusion),
+            event: p.event,
+            actor: p.actor.map(|a| a.login),
+            head_sha: p.head_sha,
+            created_at: p.created_at,
+            url: p.html_url,
+        }
+    }
+}
+
+impl From<JobDto> for JobRow {
+    fn from(j: JobDto) -> Self {
+        Self {
+            id: j.id,
+            run_id: j.run_id,
+            name: j.name,
+            status: PipelineStatus::resolve(j.status, j.conclusion),
+            started_at: j.started_at,
+            completed_at: j.completed_at,
+            runner: j.runner_name,
+            url: j.html_url,
+        }
+    }
+}
+
+fn run_history(
+    command: HistoryCommand,
+    config: &GlomConfig,
+    state_dir: Option<&Path>,
+) -> Result<()> {
+    let path = state_path(state_dir, "history.db", default_history_path);
+    if !path.exists() {
+        return Err(GlomError::HistoryError(
+            format!("no runs recorded yet in {}", path.display()).into(),
+        ));
+    }
+
+    let history = RunHistory::open(&path)?;
+    match command {
+        HistoryCommand::Red(args) => {
+            let streaks = history.red_streaks(args.project.as_deref(), args.branch.as_deref())?;
+            if streaks.is_empty() {
+                println!("all branches are green");
+            }
+
+            let now = Utc::now();
+            for streak in streaks {
+                let red_for = now - streak.since;
+                println!(
+                    "{}\t{}\t{}\tred for {}d {}h since {}\t{} failed runs\t{}",
+                    streak.project,
+                    streak.branch,
+                    streak.workflow,
+                    red_for.num_days(),
+                    red_for.num_hours() % 24,
+                    streak
+                        .since
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    streak.failed_runs,
+                    streak.url,
+                );
+            }
+        },
+        HistoryCommand::Failures(args) => {
+            let since = args
+                .since
+                .map_or_else(month_start, local_day_start);
+
+            let failures = history.job_failure_rates(args.project.as_deref(), since)?;
+            if failures.is_empty() {
+                println!(
+                    "no failed jobs since {}",
+                    since.with_timezone(&Local).format("%Y-%m-%d")
+                );
+            }
+
+            for job in failures {
+                println!(
+                    "{}\t{}\t{}/{} failed\t{:.0}%",
+                    job.project,
+                    job.name,
+                    job.failures,
+                    job.runs,
+                    job.rate(),
+                );
+            }
+        },
+        HistoryCommand::Metrics(args) => {
+            let project_id = ProjectId::new(args.project.as_str());
+            let branch = match args.branch {
+                Some(branch) => branch.into(),
+                None => default_branch(&project_id, config, state_dir)?,
+            };
+
+            let runs =
+                history.branch_runs(&project_id, &branch, MetricsReport::oldest_window_start())?;
+            let report = MetricsReport::new(project_id, branch, &runs);
+
+            if args.json {
+                let json = serde_json::to_string_pretty(&report)
+                    .map_err(|e| GlomError::GeneralError(e.to_string().into()))?;
+                println!("{json}");
+            } else if args.csv {
+                print!("{}", report.to_csv());
+            } else {
+                print_metrics(&report);
+            }
+        },
+    }
+
+    Ok(())
+}
+
+/// Fetches the projects along with their recent runs, and the jobs of the runs shown on
+/// the dashboard
+async fn fetch_dashboard(
+    api: &GithubApi,
+    client_config: &ClientConfig,
+) -> std::result::Result<Vec<Project>, ClientError> {
+    let projects = api
+        .get_projects(&client_config.default_project_query())
+        .await?;
+
+    stream::iter(projects)
+        .map(|dto| async move {
+            let mut project = Project::from(dto);
+            let query = client_config
+                .default_pipeline_query()
+                .with_filter(client_config.run_filter(&project.id));
+            let runs = api
+                .get_pipelines(project.id.clone(), &query)
+                .await?;
+            project.update_pipelines(runs.into_iter().map(Pipeline::from).collect());
+
+            let shown: Vec<PipelineId> = project
+                .first_pipeline_per_branch(DASHBOARD_BRANCHES, |p| p.status.is_active())
+                .iter()
+                .map(|p| p.id)
+                .collect();
+            for run_id in shown {
+                let jobs = api.get_jobs(project.id.clone(), run_id).await?;
+                project.update_jobs(run_id, jobs.into_iter().map(Job::from).collect());
+            }
+
+            Ok(project)
+        })
+        .buffer_unordered(
+            client_config
+                .request
+                .max_concurrent_requests
+                .max(1),
+        )
+        .try_collect()
+        .await
+}
+
+/// Exports the projects saved by the last session of the TUI, as of when they were saved
+fn run_snapshot_export(
+    args: ExportArgs,
+    config: &GlomConfig,
+    state_dir: Option<&Path>,
+) -> Result<()> {
+    let path = state_path(state_dir, "projects.json", default_snapshot_path);
+    let (saved_at, projects) = read_snapshot(&path, &config.github_url).ok_or_else(|| {
+        GlomError::GeneralError(
+            format!(
+                "no projects saved yet in {}; run glom first",
+                path.display()
+            )
+            .into(),
+        )
+    })?;
+
+    write_export(args, config, projects, Some(saved_at))
+}
+
+/// Writes the export of the projects, filtered and sorted as on the dashboard; exports of
+/// a snapshot are labelled with the time it was saved
+fn write_export(
+    args: ExportArgs,
+    config: &GlomConfig,
+    mut projects: Vec<Project>,
+    snapshot_saved_at: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let filter = args
+        .filter
+        .map(CompactString::from)
+        .or_else(|| config.search_filter.clone())
+        .filter(|f| !f.trim().is_empty());
+    if let Some(filter) = filter {
+        let filter_lower = filter.to_lowercase();
+        projects.retain(|p| p.matches_filter(&filter_lower));
+    }
+    projects.sort_by_key(|p: &Project| std::cmp::Reverse(p.last_activity()));
+
+    let export = DashboardExport::new(&projects).with_snapshot_saved_at(snapshot_saved_at);
+    let content = export.render(args.format.unwrap_or(config.export_format));
+    match args.output {
+        Some(path) => std::fs::write(&path, content).map_err(|e| {
+            GlomError::GeneralError(format!("failed to write {}: {e}", path.display()).into())
+        }),
+        None => {
+            print!("{content}");
+            Ok(())
+        },
+    }
+}
+
+/// Default branch of the project, as of the last session of the TUI
+fn default_branch(
+    project_id: &ProjectId,
+    config: &GlomConfig,
+    state_dir: Option<&Path>,
+) -> Result<CompactString> {
+    let path = state_path(state_dir, "projects.json", default_snapshot_path);
+    load_snapshot(&path, &config.github_url)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|p| &p.id == project_id)
+        .map(|p| p.default_branch)
+        .ok_or_else(|| {
+            GlomError::GeneralError(
+                format!("default branch of {project_id} is unknown; pass --branch").into(),
+            )
+        })
+}
+
+/// Path of a state file, kept apart in the state directory of fixtures if given
+fn state_path(state_dir: Option<&Path>, name: &str, default: fn() -> PathBuf) -> PathBuf {
+    match state_dir {
+        Some(dir) => dir.join(name),
+        None => default(),
+    }
+}
+
+fn print_metrics(report: &MetricsReport) {
+    let duration = |d: Option<chrono::Duration>| d.map_or_else(|| "-".into(), format_duration);
+
+    println!("{} ({})", report.project, report.branch);
+    for m in &report.windows {
+        println!(
+            "{} days\t{} runs\tsuccess rate {}\tMTTR {}\tchange failure rate {}\tmedian run time {}",
+            m.window_days,
+            m.runs,
+            format_percent(m.success_rate),
+            duration(m.mttr()),
+            format_percent(m.change_failure_rate),
+            duration(m.median_duration()),
+        );
+    }
+}
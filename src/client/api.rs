@@ -1,31 +1,70 @@
 //! Core HTTP client for GitHub API
 
-use std::sync::RwLock;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 
 use chrono::Local;
 use compact_str::{CompactString, format_compact};
-use reqwest::{Client, RequestBuilder, Response};
+use futures::{StreamExt, stream};
+use itertools::Itertools;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode, header::LINK};
 use serde::Deserialize;
 use tracing::{debug, instrument, warn};
 
 use super::{
     config::{ClientConfig, PipelineQuery, ProjectQuery},
     error::{ClientError, Result},
+    fixtures::FixtureBackend,
+    stats::ApiStats,
 };
 use crate::{
     domain::{
-        ContributorDto, GitHubArtifactsResponse, GitHubJobsResponse, GitHubSearchResponse,
-        GitHubWorkflowRunsResponse, JobDto, PipelineDto, ProjectDto, RepositoryDetailsDto,
-        StatisticsDto,
+        CacheDto, CacheUsageDto, CombinedStatusDto, CommitChecks, DeploymentDto,
+        DeploymentStatusDto, EnvironmentDeployment, GitHubArtifactsResponse, GitHubCachesResponse,
+        GitHubCheckRunsResponse, GitHubEnvironmentsResponse, GitHubJobsResponse,
+        GitHubRunnersResponse, GitHubSearchResponse, GitHubWorkflowRunsResponse, JobDto,
+        PipelineDto, ProjectDto, PullRequest, PullRequestDetailsDto, PullRequestDto,
+        PullRequestReviewDto, RepositoryDetailsDto, RepositoryInfo, RunTimingDto, RunnerDto,
+        RunnerScope, StatisticsDto,
     },
     id::{JobId, PipelineId, ProjectId},
 };
 
-/// Pure HTTP client for GitHub API
+/// Upper bound of cache pages fetched per repository
+const MAX_CACHE_PAGES: u32 = 10;
+
+/// Upper bound of open pull requests fetched per repository; each costs four requests
+const MAX_PULL_REQUESTS: u32 = 30;
+
+/// Lookups made at once for each pull request: details, reviews and checks
+const PULL_REQUEST_LOOKUPS: usize = 3;
+
+/// Client for the GitHub API, answered over HTTP or from fixtures
 #[derive(Debug)]
 pub struct GithubApi {
-    client: RwLock<Client>,
+    backend: RwLock<Backend>,
     config: RwLock<ClientConfig>,
+    stats: Arc<ApiStats>,
+}
+
+/// Answers the requests of a `GithubApi`
+#[derive(Debug)]
+enum Backend {
+    Http(Client),
+    Fixtures(FixtureBackend),
+}
+
+/// Response of either backend, read in full
+#[derive(Debug)]
+struct ApiResponse {
+    /// Path of the request, without its query
+    path: String,
+    status: StatusCode,
+    /// Pagination of list endpoints
+    link: Option<String>,
+    body: String,
 }
 
 /// GitHub API error response formats
@@ -42,14 +81,12 @@ struct GithubApiError2 {
 
 impl GithubApi {
     pub fn force_new(config: ClientConfig) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(config.request.timeout)
-            .build()
-            .map_err(ClientError::Http)?;
+        let backend = Backend::new(&config)?;
 
         Ok(Self {
-            client: RwLock::new(client),
+            backend: RwLock::new(backend),
             config: RwLock::new(config),
+            stats: Arc::new(ApiStats::default()),
         })
     }
 
@@ -88,21 +125,45 @@ impl GithubApi {
         Ok(response.workflow_runs)
     }
 
-    /// Get jobs for a workflow run
+    /// Get jobs of the latest attempt of a workflow run
     #[instrument(skip(self), fields(project_id = %project_id, pipeline_id = %pipeline_id))]
     pub async fn get_jobs(
         &self,
         project_id: ProjectId,
         pipeline_id: PipelineId,
+    ) -> Result<Vec<JobDto>> {
+        self.get_run_jobs(project_id, pipeline_id, "latest")
+            .await
+    }
+
+    /// Get jobs of every attempt of a workflow run, including those re-run since
+    #[instrument(skip(self), fields(project_id = %project_id, pipeline_id = %pipeline_id))]
+    pub async fn get_jobs_of_all_attempts(
+        &self,
+        project_id: ProjectId,
+        pipeline_id: PipelineId,
+    ) -> Result<Vec<JobDto>> {
+        self.get_run_jobs(project_id, pipeline_id, "all")
+            .await
+    }
+
+    /// Get jobs of a workflow run, `filter`ed to its `latest` attempt or `all` attempts
+    async fn get_run_jobs(
+        &self,
+        project_id: ProjectId,
+        pipeline_id: PipelineId,
+        filter: &str,
     ) -> Result<Vec<JobDto>> {
         let url = {
             let config = self.config.read().unwrap();
             // For GitHub, project_id should represent repo path "owner/repo"
             format_compact!(
-                "{}/repos/{}/actions/runs/{}/jobs",
+                "{}/repos/{}/actions/runs/{}/jobs?filter={}&per_page={}",
                 config.base_url,
                 project_id,
-                pipeline_id
+                pipeline_id,
+                filter,
+                config.request.per_page
             )
         };
 
@@ -113,6 +174,304 @@ impl GithubApi {
         Ok(jobs)
     }
 
+    /// Get the billable time of a workflow run per runner OS
+    #[instrument(skip(self), fields(project_id = %project_id, pipeline_id = %pipeline_id))]
+    pub async fn get_run_timing(
+        &self,
+        project_id: ProjectId,
+        pipeline_id: PipelineId,
+    ) -> Result<RunTimingDto> {
+        let url = {
+            let config = self.config.read().unwrap();
+            format_compact!(
+                "{}/repos/{}/actions/runs/{}/timing",
+                config.base_url,
+                project_id,
+                pipeline_id
+            )
+        };
+
+        self.get_json(&url).await
+    }
+
+    /// Get the commit statuses and check runs of a commit, optionally including those
+    /// of GitHub Actions
+    #[instrument(skip(self), fields(project_id = %project_id, sha = %sha))]
+    pub async fn get_commit_checks(
+        &self,
+        project_id: ProjectId,
+        sha: &str,
+        include_actions: bool,
+    ) -> Result<CommitChecks> {
+        let base_url = {
+            let config = self.config.read().unwrap();
+            format_compact!("{}/repos/{}/commits/{}", config.base_url, project_id, sha)
+        };
+
+        let status_url = format_compact!("{base_url}/status?per_page=100");
+        let combined: CombinedStatusDto = self.get_json(&status_url).await?;
+
+        let check_runs_url = format_compact!("{base_url}/check-runs?per_page=100");
+        let check_runs: GitHubCheckRunsResponse = self.get_json(&check_runs_url).await?;
+
+        let checks = CommitChecks::new(combined, check_runs.check_runs, include_actions);
+        debug!(
+            check_count = checks.checks.len(),
+            "Successfully fetched commit checks"
+        );
+        Ok(checks)
+    }
+
+    /// Get the open pull requests of a repository along with their review decision,
+    /// mergeable state and checks
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn get_pull_requests(&self, project_id: ProjectId) -> Result<Vec<PullRequest>> {
+        let base_url = {
+            let config = self.config.read().unwrap();
+            format_compact!("{}/repos/{}/pulls", config.base_url, project_id)
+        };
+
+        let url = format_compact!(
+            "{base_url}?state=open&sort=updated&direction=desc&per_page={MAX_PULL_REQUESTS}"
+        );
+        let pull_requests: Vec<PullRequestDto> = self.get_json(&url).await?;
+
+        let result: Vec<PullRequest> = stream::iter(pull_requests)
+            .map(|pr| self.complete_pull_request(&project_id, &base_url, pr))
+            .buffered((self.max_concurrent_requests() / PULL_REQUEST_LOOKUPS).max(1))
+            .collect()
+            .await;
+
+        debug!(
+            pull_request_count = result.len(),
+            "Successfully fetched pull requests"
+        );
+        Ok(result)
+    }
+
+    /// Completes a listed pull request with its details, reviews and checks; a failed
+    /// lookup, e.g. for a token without the checks scope, only leaves its part unknown
+    async fn complete_pull_request(
+        &self,
+        project_id: &ProjectId,
+        base_url: &str,
+        pr: PullRequestDto,
+    ) -> PullRequest {
+        let details_url = format_compact!("{base_url}/{}", pr.number);
+        let reviews_url = format_compact!("{base_url}/{}/reviews?per_page=100", pr.number);
+
+        let (details, reviews, checks) = tokio::join!(
+            self.get_json::<PullRequestDetailsDto>(&details_url),
+            self.get_json::<Vec<PullRequestReviewDto>>(&reviews_url),
+            self.get_commit_checks(project_id.clone(), &pr.head.sha, true),
+        );
+
+        let number = pr.number;
+        let details = details
+            .inspect_err(|e| warn!(number, error = %e, "Failed to fetch pull request details"))
+            .ok();
+        let reviews = reviews
+            .inspect_err(|e| warn!(number, error = %e, "Failed to fetch pull request reviews"))
+            .ok();
+        let checks = checks
+            .inspect_err(|e| warn!(number, error = %e, "Failed to fetch pull request checks"))
+            .unwrap_or_default();
+
+        PullRequest::new(pr, details, reviews.as_deref(), checks)
+    }
+
+    /// Requests a single fetch fanning out may make at once, as many as the scheduler
+    /// allows overall
+    fn max_concurrent_requests(&self) -> usize {
+        self.config
+            .read()
+            .unwrap()
+            .request
+            .max_concurrent_requests
+            .max(1)
+    }
+
+    /// Get the self-hosted runners available to a repository, including those
+    /// registered with its organization
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn get_runners(&self, project_id: ProjectId) -> Result<Vec<RunnerDto>> {
+        let (repo_url, org_url) = {
+            let config = self.config.read().unwrap();
+            (
+                format_compact!(
+                    "{}/repos/{}/actions/runners?per_page=100",
+                    config.base_url,
+                    project_id
+                ),
+                format_compact!(
+                    "{}/orgs/{}/actions/runners?per_page=100",
+                    config.base_url,
+                    project_id.owner()
+                ),
+            )
+        };
+
+        let response: GitHubRunnersResponse = self.get_json(&repo_url).await?;
+        let mut runners = response.runners;
+
+        // personal accounts have no organization runners, and listing them requires org admin
+        match self
+            .get_json::<GitHubRunnersResponse>(&org_url)
+            .await
+        {
+            Ok(response) => runners.extend(response.runners.into_iter().map(|mut r| {
+                r.scope = RunnerScope::Organization;
+                r
+            })),
+            Err(e) => debug!(owner = project_id.owner(), error = %e, "No organization runners"),
+        }
+
+        debug!(runner_count = runners.len(), "Successfully fetched runners");
+        Ok(runners)
+    }
+
+    /// Get the environments of a repository along with their latest deployment
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn get_environment_deployments(
+        &self,
+        project_id: ProjectId,
+    ) -> Result<Vec<EnvironmentDeployment>> {
+        let base_url = {
+            let config = self.config.read().unwrap();
+            format_compact!("{}/repos/{}", config.base_url, project_id)
+        };
+
+        let url = format_compact!("{base_url}/environments?per_page=100");
+        let response: GitHubEnvironmentsResponse = self.get_json(&url).await?;
+
+        let environments: Vec<EnvironmentDeployment> = stream::iter(response.environments)
+            .map(|environment| self.latest_deployment(&base_url, environment.name))
+            .buffered(self.max_concurrent_requests())
+            .collect()
+            .await;
+
+        debug!(
+            environment_count = environments.len(),
+            "Successfully fetched deployments"
+        );
+        Ok(environments)
+    }
+
+    /// Latest deployment to an environment along with its latest status; a failed
+    /// lookup, e.g. of a protected environment, leaves them unknown for this one only
+    async fn latest_deployment(
+        &self,
+        base_url: &str,
+        environment: CompactString,
+    ) -> EnvironmentDeployment {
+        let name: String = url::form_urlencoded::byte_serialize(environment.as_bytes()).collect();
+        let url = format_compact!("{base_url}/deployments?environment={name}&per_page=1");
+        let deployment = match self.get_json::<Vec<DeploymentDto>>(&url).await {
+            Ok(deployments) => deployments.into_iter().next(),
+            Err(e) => {
+                warn!(environment = %environment, error = %e, "Failed to fetch deployments");
+                None
+            },
+        };
+
+        // statuses are listed newest first
+        let status = match &deployment {
+            Some(deployment) => {
+                let url = format_compact!(
+                    "{base_url}/deployments/{}/statuses?per_page=1",
+                    deployment.id
+                );
+                match self
+                    .get_json::<Vec<DeploymentStatusDto>>(&url)
+                    .await
+                {
+                    Ok(statuses) => statuses.into_iter().next(),
+                    Err(e) => {
+                        warn!(environment = %environment, error = %e, "Failed to fetch deployment status");
+                        None
+                    },
+                }
+            },
+            None => None,
+        };
+
+        EnvironmentDeployment { environment, deployment, status }
+    }
+
+    /// Get the Actions caches of a repository, largest first
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn get_caches(
+        &self,
+        project_id: ProjectId,
+        key_prefix: Option<&str>,
+    ) -> Result<Vec<CacheDto>> {
+        let base_url = {
+            let config = self.config.read().unwrap();
+            let mut url = format_compact!(
+                "{}/repos/{}/actions/caches?per_page=100&sort=size_in_bytes&direction=desc",
+                config.base_url,
+                project_id
+            );
+            if let Some(prefix) = key_prefix {
+                // the key parameter matches keys by prefix
+                url.push_str("&key=");
+                url.extend(url::form_urlencoded::byte_serialize(prefix.as_bytes()));
+            }
+            url
+        };
+
+        let mut caches = Vec::new();
+        for page in 1..=MAX_CACHE_PAGES {
+            let url = format_compact!("{base_url}&page={page}");
+            let response: GitHubCachesResponse = self.get_json(&url).await?;
+            let page_len = response.actions_caches.len();
+            caches.extend(response.actions_caches);
+
+            if page_len == 0 || caches.len() >= response.total_count as usize {
+                break;
+            }
+        }
+
+        debug!(cache_count = caches.len(), "Successfully fetched caches");
+        Ok(caches)
+    }
+
+    /// Get the size and count of the active Actions caches of a repository
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn get_cache_usage(&self, project_id: ProjectId) -> Result<CacheUsageDto> {
+        let url = {
+            let config = self.config.read().unwrap();
+            format_compact!(
+                "{}/repos/{}/actions/cache/usage",
+                config.base_url,
+                project_id
+            )
+        };
+
+        self.get_json(&url).await
+    }
+
+    /// Delete an Actions cache by id
+    #[instrument(skip(self), fields(project_id = %project_id, cache_id = cache_id))]
+    pub async fn delete_cache(&self, project_id: ProjectId, cache_id: u64) -> Result<()> {
+        let url = {
+            let config = self.config.read().unwrap();
+            format_compact!(
+                "{}/repos/{}/actions/caches/{}",
+                config.base_url,
+                project_id,
+                cache_id
+            )
+        };
+
+        let response = self.send(Method::DELETE, &url).await?;
+        if response.status.is_success() {
+            Ok(())
+        } else {
+            self.handle_error_response(response.status.as_u16(), &response.body)
+        }
+    }
+
     /// Get job logs
     #[instrument(skip(self), fields(project_id = %project_id, job_id = %job_id))]
     pub async fn get_job_trace(
@@ -130,9 +489,8 @@ impl GithubApi {
             )
         };
 
-        let response = self.authenticated_request(&url).send().await?;
-        let body = response.text().await?;
-        Ok(body.into())
+        let response = self.send(Method::GET, &url).await?;
+        Ok(response.body.into())
     }
 
     /// Get repository statistics (size, commit count, etc.)
@@ -146,19 +504,50 @@ impl GithubApi {
         let repo_details: RepositoryDetailsDto = self.get_json(&repo_url).await?;
 
         let commit_count = self
-            .get_commit_count(project_id.clone())
+            .get_commit_count(project_id.clone(), &repo_details.default_branch)
             .await
-            .unwrap_or(0);
+            .unwrap_or_else(|e| {
+                debug!(project_id = %project_id, error = %e, "Failed to fetch commit count");
+                0
+            });
 
         let artifacts_size = self
             .get_total_artifacts_size(project_id.clone())
             .await
             .unwrap_or(0);
 
+        let cache_size = self
+            .get_cache_usage(project_id.clone())
+            .await
+            .map(|usage| usage.active_caches_size_in_bytes)
+            .unwrap_or_else(|e| {
+                debug!(project_id = %project_id, error = %e, "Failed to fetch cache usage");
+                0
+            });
+
+        let languages = self
+            .get_languages(project_id.clone())
+            .await
+            .unwrap_or_else(|e| {
+                debug!(project_id = %project_id, error = %e, "Failed to fetch languages");
+                Vec::new()
+            });
+
+        let open_pull_requests = self
+            .get_open_pull_request_count(project_id.clone())
+            .await
+            .unwrap_or_else(|e| {
+                debug!(project_id = %project_id, error = %e, "Failed to fetch open pull requests");
+                0
+            });
+
         Ok(StatisticsDto {
             commit_count,
+            commit_branch: repo_details.default_branch.clone(),
             repository_size: repo_details.size * 1024, // GitHub returns size in KB
             job_artifacts_size: artifacts_size,
+            cache_size,
+            repository: RepositoryInfo::new(repo_details, languages, open_pull_requests),
         })
     }
 
@@ -202,92 +591,88 @@ impl GithubApi {
         }
     }
 
-    /// Get commit count using GitHub's contributors API
-    async fn get_commit_count(&self, project_id: ProjectId) -> Result<u32> {
+    /// Count the commits on a branch
+    async fn get_commit_count(&self, project_id: ProjectId, branch: &str) -> Result<u32> {
         let url = {
             let config = self.config.read().unwrap();
             format_compact!(
-                "{}/repos/{}/contributors?per_page=100",
+                "{}/repos/{}/commits?sha={}",
                 config.base_url,
-                project_id
+                project_id,
+                url::form_urlencoded::byte_serialize(branch.as_bytes()).collect::<String>()
             )
         };
 
-        match self.get_json::<Vec<ContributorDto>>(&url).await {
-            Ok(contributors) => {
-                let total_commits: u32 = contributors.iter().map(|c| c.contributions).sum();
-                debug!(
-                    project_id = %project_id,
-                    contributor_count = contributors.len(),
-                    total_commits = total_commits,
-                    "Successfully fetched commit count from contributors API"
-                );
-                Ok(total_commits)
-            },
-            Err(e) => {
-                debug!(
-                    project_id = %project_id,
-                    error = %e,
-                    "Failed to fetch contributors, trying fallback method"
-                );
-                self.get_commit_count_fallback(project_id).await
-            },
-        }
+        let commit_count = self.count_items(&url).await?;
+        debug!(
+            project_id = %project_id,
+            branch = %branch,
+            commit_count = commit_count,
+            "Successfully fetched commit count"
+        );
+        Ok(commit_count)
     }
 
-    /// Fallback method to estimate commit count from recent commits
-    async fn get_commit_count_fallback(&self, project_id: ProjectId) -> Result<u32> {
+    /// Count the open pull requests
+    async fn get_open_pull_request_count(&self, project_id: ProjectId) -> Result<u32> {
         let url = {
             let config = self.config.read().unwrap();
-            format_compact!(
-                "{}/repos/{}/commits?per_page=100",
-                config.base_url,
-                project_id
-            )
+            format_compact!("{}/repos/{}/pulls?state=open", config.base_url, project_id)
         };
 
-        match self
-            .get_json::<Vec<serde_json::Value>>(&url)
-            .await
-        {
-            Ok(commits) => {
-                // This gives us at least the count of recent commits
-                // For repositories with more than 100 commits, this will be an underestimate
-                // but it's better than 0
-                let commit_count = commits.len() as u32;
-                debug!(
-                    project_id = %project_id,
-                    commit_count = commit_count,
-                    "Fallback: fetched recent commits count"
-                );
-                Ok(commit_count)
-            },
-            Err(e) => {
-                debug!(
-                    project_id = %project_id,
-                    error = %e,
-                    "Fallback method failed, returning 0"
-                );
-                Ok(0)
-            },
+        self.count_items(&url).await
+    }
+
+    /// Get the bytes of code per language, largest first
+    async fn get_languages(&self, project_id: ProjectId) -> Result<Vec<(CompactString, u64)>> {
+        let url = {
+            let config = self.config.read().unwrap();
+            format_compact!("{}/repos/{}/languages", config.base_url, project_id)
+        };
+
+        let languages: HashMap<CompactString, u64> = self.get_json(&url).await?;
+        Ok(languages
+            .into_iter()
+            .sorted_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)))
+            .collect())
+    }
+
+    /// Count the items of a list endpoint from the `last` page of a single-item listing
+    async fn count_items(&self, url: &str) -> Result<u32> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let url = format_compact!("{url}{separator}per_page=1");
+        let response = self.send(Method::GET, &url).await?;
+
+        // GitHub responds with 409 Conflict when listing commits of an empty repository
+        if response.status == StatusCode::CONFLICT {
+            return Ok(0);
         }
+
+        let last_page = response.link.as_deref().and_then(last_page);
+
+        // without pagination, all items fit on the single page
+        let items: Vec<serde_json::Value> = self.handle_response(response)?;
+        Ok(last_page.unwrap_or(items.len() as u32))
     }
 
     /// Update configuration
     pub fn update_config(&self, config: ClientConfig) -> Result<()> {
         config.validate()?;
 
-        let client = Client::builder()
-            .timeout(config.request.timeout)
-            .build()
-            .map_err(ClientError::Http)?;
-
+        // fixtures keep their clock, so the runs of the demo carry on
+        if config.fixtures.is_none() || config.fixtures != self.config().fixtures {
+            *self.backend.write().unwrap() = Backend::new(&config)?;
+        }
         *self.config.write().unwrap() = config;
-        *self.client.write().unwrap() = client;
 
         Ok(())
     }
 
+    /// Counters of the requests sent so far
+    pub fn stats(&self) -> Arc<ApiStats> {
+        self.stats.clone()
+    }
+
     /// Get current configuration
     pub fn config(&self) -> ClientConfig {
         self.config.read().unwrap().clone()
@@ -305,34 +690,46 @@ impl GithubApi {
     where
         T: for<'de> Deserialize<'de>,
     {
-        let response = self.authenticated_request(url).send().await?;
-        self.handle_response(response).await
+        let response = self.send(Method::GET, url).await?;
+        self.handle_response(response)
+    }
+
+    /// Send the request to the backend, recording those sent to GitHub in the stats
+    async fn send(&self, method: Method, url: &str) -> Result<ApiResponse> {
+        let request = match &*self.backend.read().unwrap() {
+            Backend::Http(client) => self.authenticated(client, method, url),
+            Backend::Fixtures(fixtures) => {
+                let (status, body) = fixtures.respond(&method, url);
+                return Ok(ApiResponse::from_fixture(url, status, body));
+            },
+        };
+
+        let response = request.send().await;
+        self.stats.record(&response);
+        ApiResponse::read(response?).await
     }
 
     /// Create authenticated request builder
-    fn authenticated_request(&self, url: &str) -> RequestBuilder {
-        let client = self.client.read().unwrap();
+    fn authenticated(&self, client: &Client, method: Method, url: &str) -> RequestBuilder {
         let private_token = self.config.read().unwrap().private_token.clone();
         client
-            .get(url)
+            .request(method, url)
             .header("Authorization", format!("token {}", private_token))
             .header("Accept", "application/vnd.github.v3+json")
             .header("User-Agent", "glom-github-client")
     }
 
     /// Handle HTTP response and deserialize JSON
-    async fn handle_response<T>(&self, response: Response) -> Result<T>
+    fn handle_response<T>(&self, response: ApiResponse) -> Result<T>
     where
         T: for<'de> Deserialize<'de>,
     {
-        let url_path = response.url().path().to_string();
-        let status = response.status();
-        let body = response.text().await?;
+        let ApiResponse { path: url_path, status, body, .. } = response;
 
-        // Log response if debug is enabled
+        // Log response if debug is enabled, unless it came from the fixtures
         {
             let config = self.config.read().unwrap();
-            if config.debug.log_responses {
+            if config.debug.log_responses && config.fixtures.is_none() {
                 self.log_response_to_file(&url_path, &body, &config);
             }
         }
@@ -450,21 +847,16 @@ impl GithubApi {
     /// Build URL for workflow runs endpoint
     fn build_pipelines_url(&self, project_id: ProjectId, query: &PipelineQuery) -> CompactString {
         let config = self.config.read().unwrap();
-        let mut url = format_compact!(
-            "{}/repos/{}/actions/runs?per_page={}",
+        let params = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query.query_pairs())
+            .finish();
+
+        format_compact!(
+            "{}/repos/{}/actions/runs?{}",
             config.base_url,
             project_id,
-            query.per_page
-        );
-
-        if let Some(updated_after) = query.updated_after {
-            url.push_str(&format_compact!(
-                "&created=>={}",
-                updated_after.format("%Y-%m-%dT%H:%M:%SZ")
-            ));
-        }
-
-        url
+            params
+        )
     }
 
     /// Log HTTP response to file for debugging
@@ -493,3 +885,58 @@ impl GithubApi {
         }
     }
 }
+
+impl Backend {
+    fn new(config: &ClientConfig) -> Result<Self> {
+        match &config.fixtures {
+            Some(source) => FixtureBackend::open(source, &config.base_url).map(Backend::Fixtures),
+            None => Client::builder()
+                .timeout(config.request.timeout)
+                .build()
+                .map(Backend::Http)
+                .map_err(ClientError::Http),
+        }
+    }
+}
+
+impl ApiResponse {
+    async fn read(response: Response) -> Result<Self> {
+        let path = response.url().path().to_string();
+        let status = response.status();
+        let link = response
+            .headers()
+            .get(LINK)
+            .and_then(|link| link.to_str().ok())
+            .map(String::from);
+        let body = response.text().await?;
+
+        Ok(Self { path, status, link, body })
+    }
+
+    fn from_fixture(url: &str, status: StatusCode, body: String) -> Self {
+        let path = url::Url::parse(url)
+            .map(|url| url.path().to_string())
+            .unwrap_or_default();
+
+        Self { path, status, link: None, body }
+    }
+}
+
+/// Extract the page number of the `rel="last"` entry of a Link header
+fn last_page(link: &str) -> Option<u32> {
+    link.split(',')
+        .filter_map(|entry| entry.split_once(';'))
+        .find(|(_, rel)| rel.trim() == r#"rel="last""#)
+        .and_then(|(target, _)| {
+            let target = target
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>');
+            url::Url::parse(target).ok()
+        })
+        .and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == "page")
+                .and_then(|(_, page)| page.parse().ok())
+        })
+}
@@ -1,12 +1,23 @@
 //! Configuration management for GitHub client
 
-use std::{path::PathBuf, time::Duration};
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
 
-use chrono::{DateTime, Utc};
-use compact_str::CompactString;
+use chrono::{DateTime, NaiveDate, Utc};
+use compact_str::{CompactString, ToCompactString, format_compact};
+use serde::{Deserialize, Serialize};
 
-use super::error::{ClientError, Result};
-use crate::glom_app::GlomConfig;
+use super::{
+    error::{ClientError, Result},
+    fixtures::FixtureSource,
+};
+use crate::{
+    domain::{PipelineDto, PipelineSource},
+    glom_app::GlomConfig,
+    id::ProjectId,
+};
+
+/// Default upper bound of concurrent GitHub API requests
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 8;
 
 /// Main configuration for GitHub client
 #[derive(Debug, Clone)]
@@ -23,6 +34,12 @@ pub struct ClientConfig {
     pub request: RequestConfig,
     /// Debug configuration
     pub debug: DebugConfig,
+    /// Webhook receiver configuration, if enabled
+    pub webhook: Option<WebhookConfig>,
+    /// Workflow run filters per project, keyed by `owner/repo`
+    pub run_filters: BTreeMap<CompactString, RunFilter>,
+    /// Answer requests from JSON fixtures instead of GitHub, if set
+    pub fixtures: Option<FixtureSource>,
 }
 
 /// Polling intervals configuration
@@ -33,6 +50,10 @@ pub struct PollingConfig {
     pub projects_interval: Duration,
     /// Interval for fetching active jobs
     pub jobs_interval: Duration,
+    /// Interval for fetching the runs of watched runs and branches
+    pub watch_interval: Duration,
+    /// Factor applied to the polling intervals while webhook deliveries are flowing
+    pub webhook_backoff: u32,
 }
 
 /// HTTP request configuration
@@ -45,6 +66,8 @@ pub struct RequestConfig {
     pub timeout: Duration,
     /// Maximum number of retries for failed requests
     pub max_retries: u32,
+    /// Maximum number of requests in flight at once
+    pub max_concurrent_requests: usize,
 }
 
 /// Debug and logging configuration
@@ -56,6 +79,15 @@ pub struct DebugConfig {
     pub log_directory: Option<PathBuf>,
 }
 
+/// Embedded webhook receiver configuration
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    /// Socket address the listener binds to
+    pub listen: CompactString,
+    /// Shared secret used to validate `X-Hub-Signature-256`
+    pub secret: CompactString,
+}
+
 /// Query parameters for fetching projects
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
@@ -80,42 +112,60 @@ pub struct ProjectQuery {
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
 pub struct PipelineQuery {
-    /// Only fetch pipelines updated after this time
+    /// Only fetch pipelines created after this time, unless `filter.created` is set
     pub updated_after: Option<DateTime<Utc>>,
     /// Number of results per page
     pub per_page: u32,
-    /// Pipeline scope (running, pending, finished, etc.)
-    pub scope: Option<PipelineScope>,
-    /// Pipeline status filter
-    pub status: Option<PipelineStatus>,
+    /// GitHub workflow run filters
+    pub filter: RunFilter,
 }
 
-/// Pipeline scope for filtering
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub enum PipelineScope {
-    Running,
-    Pending,
-    Finished,
-    Branches,
-    Tags,
+/// Filters supported by GitHub's list workflow runs endpoint
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RunFilter {
+    /// Only runs associated with this branch
+    pub branch: Option<CompactString>,
+    /// Only runs triggered by this event, e.g. `push` or `pull_request`
+    pub event: Option<CompactString>,
+    /// Only runs triggered by this user
+    pub actor: Option<CompactString>,
+    /// Only runs with this status or conclusion
+    pub status: Option<RunStatusFilter>,
+    /// Only runs for this head commit
+    pub head_sha: Option<CompactString>,
+    /// Omit pull requests from the response
+    pub exclude_pull_requests: bool,
+    /// Only runs created within this date range
+    pub created: Option<CreatedRange>,
 }
 
-/// Pipeline status for filtering
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-pub enum PipelineStatus {
-    Created,
-    WaitingForResource,
-    Preparing,
-    Pending,
-    Running,
-    Success,
-    Failed,
-    Canceled,
+/// Status or conclusion accepted by the `status` run filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatusFilter {
+    Completed,
+    ActionRequired,
+    Cancelled,
+    Failure,
+    Neutral,
     Skipped,
-    Manual,
-    Scheduled,
+    Stale,
+    Success,
+    TimedOut,
+    InProgress,
+    Queued,
+    Requested,
+    Waiting,
+    Pending,
+}
+
+/// Inclusive date range for the `created` run filter; open ended if a bound is missing
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreatedRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
 }
 
 impl Default for PollingConfig {
@@ -123,6 +173,8 @@ impl Default for PollingConfig {
         Self {
             projects_interval: Duration::from_secs(60),
             jobs_interval: Duration::from_secs(30),
+            watch_interval: Duration::from_secs(10),
+            webhook_backoff: 4,
         }
     }
 }
@@ -133,6 +185,7 @@ impl Default for RequestConfig {
             per_page: 100,
             timeout: Duration::from_secs(30),
             max_retries: 3,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
         }
     }
 }
@@ -159,6 +212,9 @@ impl ClientConfig {
             polling: PollingConfig::default(),
             request: RequestConfig::default(),
             debug: DebugConfig::default(),
+            webhook: None,
+            run_filters: BTreeMap::new(),
+            fixtures: None,
         }
     }
 
@@ -171,7 +227,10 @@ impl ClientConfig {
             ));
         }
 
-        if self.private_token.is_empty() {
+        // fixtures are answered without a token
+        let requires_token = self.fixtures.is_none();
+
+        if requires_token && self.private_token.is_empty() {
             return Err(ClientError::config_validation(
                 "github_token",
                 "Private token cannot be empty",
@@ -202,14 +261,15 @@ impl ClientConfig {
             ));
         }
 
-        if self.private_token.len() < 20 {
+        if requires_token && self.private_token.len() < 20 {
             return Err(ClientError::config_validation(
                 "github_token",
                 "GitHub token must be at least 20 characters long",
             ));
         }
 
-        if !self.private_token.starts_with("ghp_")
+        if requires_token
+            && !self.private_token.starts_with("ghp_")
             && !self.private_token.starts_with("gho_")
             && !self.private_token.starts_with("ghu_")
             && !self.private_token.starts_with("ghs_")
@@ -258,11 +318,37 @@ impl ClientConfig {
             ..Default::default()
         }
     }
+
+    /// Workflow run filter configured for a project
+    pub fn run_filter(&self, project_id: &ProjectId) -> RunFilter {
+        self.run_filters
+            .get(project_id.to_compact_string().as_str())
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 impl From<GlomConfig> for ClientConfig {
     fn from(config: GlomConfig) -> Self {
-        Self::new(config.github_url, config.github_token).with_search_filter(config.search_filter)
+        // a blank secret would let anyone sign deliveries
+        let secret = config
+            .webhook_secret
+            .filter(|secret| !secret.trim().is_empty());
+        let webhook = match (config.webhook_listen, secret) {
+            (Some(listen), Some(secret)) => Some(WebhookConfig { listen, secret }),
+            _ => None,
+        };
+
+        let request = RequestConfig {
+            max_concurrent_requests: config.max_concurrent_requests,
+            ..RequestConfig::default()
+        };
+
+        Self::new(config.github_url, config.github_token)
+            .with_search_filter(config.search_filter)
+            .with_request(request)
+            .with_webhook(webhook)
+            .with_run_filters(config.run_filters)
     }
 }
 
@@ -292,6 +378,24 @@ impl ClientConfig {
         self
     }
 
+    /// Set webhook receiver configuration
+    pub fn with_webhook(mut self, webhook: Option<WebhookConfig>) -> Self {
+        self.webhook = webhook;
+        self
+    }
+
+    /// Set workflow run filters per project
+    pub fn with_run_filters(mut self, run_filters: BTreeMap<CompactString, RunFilter>) -> Self {
+        self.run_filters = run_filters;
+        self
+    }
+
+    /// Answer requests from JSON fixtures instead of GitHub
+    pub fn with_fixtures(mut self, fixtures: Option<FixtureSource>) -> Self {
+        self.fixtures = fixtures;
+        self
+    }
+
     /// Enable debug logging
     pub fn with_debug_logging(mut self, enabled: bool) -> Self {
         self.debug.log_responses = enabled;
@@ -340,4 +444,195 @@ impl PipelineQuery {
         self.per_page = per_page;
         self
     }
+
+    /// Set workflow run filter
+    pub fn with_filter(mut self, filter: RunFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Query parameters for the list workflow runs endpoint, not yet url-encoded
+    pub fn query_pairs(&self) -> Vec<(&'static str, CompactString)> {
+        let filter = &self.filter;
+        let mut pairs = vec![("per_page", self.per_page.to_compact_string())];
+
+        let optional = [
+            ("branch", filter.branch.clone()),
+            ("event", filter.event.clone()),
+            ("actor", filter.actor.clone()),
+            ("status", filter.status.map(|s| s.as_str().into())),
+            ("head_sha", filter.head_sha.clone()),
+        ];
+        pairs.extend(
+            optional
+                .into_iter()
+                .filter_map(|(key, value)| value.map(|v| (key, v))),
+        );
+
+        if filter.exclude_pull_requests {
+            pairs.push(("exclude_pull_requests", "true".into()));
+        }
+
+        match (&filter.created, self.updated_after) {
+            (Some(created), _) if !created.is_unbounded() => {
+                pairs.push(("created", created.to_compact_string()))
+            },
+            (_, Some(updated_after)) => pairs.push((
+                "created",
+                format_compact!(">={}", updated_after.format("%Y-%m-%dT%H:%M:%SZ")),
+            )),
+            _ => {},
+        }
+
+        pairs
+    }
+}
+
+impl RunFilter {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Checks a single run against the filter, e.g. for runs delivered by webhooks
+    pub fn matches(&self, run: &PipelineDto) -> bool {
+        let branch = self
+            .branch
+            .as_ref()
+            .is_none_or(|b| run.head_branch.as_ref() == Some(b));
+        let event = self
+            .event
+            .as_ref()
+            .is_none_or(|e| PipelineSource::from_event(e) == run.event);
+        let actor = self.actor.as_ref().is_none_or(|a| {
+            run.actor
+                .as_ref()
+                .is_some_and(|actor| actor.login == *a)
+        });
+        let status = self.status.is_none_or(|s| {
+            [Some(&run.status), run.conclusion.as_ref()]
+                .into_iter()
+                .flatten()
+                .any(|status| s.as_str() == status.as_api_str())
+        });
+        let head_sha = self
+            .head_sha
+            .as_ref()
+            .is_none_or(|sha| run.head_sha == *sha);
+        let created = self
+            .created
+            .as_ref()
+            .is_none_or(|c| c.contains(run.created_at.date_naive()));
+
+        branch && event && actor && status && head_sha && created
+    }
+
+    /// Short human-readable description, e.g. `branch:main event:push`
+    pub fn summary(&self) -> CompactString {
+        let mut parts: Vec<CompactString> = [
+            ("branch", self.branch.clone()),
+            ("event", self.event.clone()),
+            ("actor", self.actor.clone()),
+            ("status", self.status.map(|s| s.as_str().into())),
+            ("sha", self.head_sha.clone()),
+            (
+                "created",
+                self.created
+                    .as_ref()
+                    .filter(|c| !c.is_unbounded())
+                    .map(|c| c.to_compact_string()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.map(|v| format_compact!("{key}:{v}")))
+        .collect();
+
+        if self.exclude_pull_requests {
+            parts.push("no-prs".into());
+        }
+
+        parts.join(" ").into()
+    }
+}
+
+impl RunStatusFilter {
+    pub const ALL: [RunStatusFilter; 14] = [
+        RunStatusFilter::Completed,
+        RunStatusFilter::ActionRequired,
+        RunStatusFilter::Cancelled,
+        RunStatusFilter::Failure,
+        RunStatusFilter::Neutral,
+        RunStatusFilter::Skipped,
+        RunStatusFilter::Stale,
+        RunStatusFilter::Success,
+        RunStatusFilter::TimedOut,
+        RunStatusFilter::InProgress,
+        RunStatusFilter::Queued,
+        RunStatusFilter::Requested,
+        RunStatusFilter::Waiting,
+        RunStatusFilter::Pending,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunStatusFilter::Completed => "completed",
+            RunStatusFilter::ActionRequired => "action_required",
+            RunStatusFilter::Cancelled => "cancelled",
+            RunStatusFilter::Failure => "failure",
+            RunStatusFilter::Neutral => "neutral",
+            RunStatusFilter::Skipped => "skipped",
+            RunStatusFilter::Stale => "stale",
+            RunStatusFilter::Success => "success",
+            RunStatusFilter::TimedOut => "timed_out",
+            RunStatusFilter::InProgress => "in_progress",
+            RunStatusFilter::Queued => "queued",
+            RunStatusFilter::Requested => "requested",
+            RunStatusFilter::Waiting => "waiting",
+            RunStatusFilter::Pending => "pending",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.as_str() == value)
+    }
+}
+
+impl CreatedRange {
+    /// Parses GitHub's date range syntax: `2025-01-01`, `>=2025-01-01`, `<=2025-01-31`
+    /// or `2025-01-01..2025-01-31`
+    pub fn parse(value: &str) -> Option<Self> {
+        let date = |s: &str| NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok();
+        let value = value.trim();
+
+        if let Some((from, to)) = value.split_once("..") {
+            Some(Self { from: date(from), to: date(to) }).filter(|r| !r.is_unbounded())
+        } else if let Some(from) = value.strip_prefix(">=") {
+            date(from).map(|from| Self { from: Some(from), to: None })
+        } else if let Some(to) = value.strip_prefix("<=") {
+            date(to).map(|to| Self { from: None, to: Some(to) })
+        } else {
+            date(value).map(|day| Self { from: Some(day), to: Some(day) })
+        }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| from <= date) && self.to.is_none_or(|to| date <= to)
+    }
+}
+
+impl std::fmt::Display for CreatedRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) if from == to => write!(f, "{from}"),
+            (Some(from), Some(to)) => write!(f, "{from}..{to}"),
+            (Some(from), None) => write!(f, ">={from}"),
+            (None, Some(to)) => write!(f, "<={to}"),
+            (None, None) => Ok(()),
+        }
+    }
 }
@@ -108,7 +108,6 @@ impl ClientError {
     }
 
     /// Check if this error is retryable
-    #[allow(dead_code)]
     pub fn is_retryable(&self) -> bool {
         match self {
             ClientError::Http(e) => e.is_timeout() || e.is_connect(),
@@ -0,0 +1,553 @@
+//! Answers GitHub API requests from JSON fixtures instead of the network, to demo glom
+//! or work on the UI without a token
+//!
+//! A fixture is the body of a response, named after the path of its request with `/`
+//! replaced by `_`, e.g. `_repos_owner_repo_actions_runs.json`. The responses dumped
+//! into `glom-logs/` by `DebugConfig::log_responses` are replayed as they are; their
+//! timestamp prefix is ignored, and the most recent dump of a path wins.
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Duration, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use itertools::Itertools;
+use reqwest::{Method, StatusCode};
+use serde_json::{Value, json};
+use tracing::{debug, warn};
+
+use super::error::{ClientError, Result};
+
+/// Time the bundled demo fixtures were captured at, moved to the start of the demo
+const DEMO_CAPTURED_AT: (i32, u32, u32) = (2025, 6, 2);
+
+/// Pause between the last job of an in progress demo run and its next cycle
+const DEMO_PAUSE_SECS: i64 = 60;
+
+/// Completed cycles of each in progress demo run listed below its current one
+const DEMO_HISTORY: u64 = 4;
+
+/// Cycles are encoded in the ids of the demo runs and jobs, as `id * CYCLE_IDS + cycle`
+const CYCLE_IDS: u64 = 100_000;
+
+/// Timestamp prefixed to the dumped responses, e.g. `2025-06-02_12-00-00_`
+const DUMP_TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+const DUMP_TIMESTAMP_LEN: usize = 19;
+
+const DEMO_FIXTURES: &[(&str, &str)] = &[
+    (
+        "_user_repos",
+        include_str!("../../fixtures/demo/_user_repos.json"),
+    ),
+    (
+        "_repos_acme_api_actions_runs",
+        include_str!("../../fixtures/demo/_repos_acme_api_actions_runs.json"),
+    ),
+    (
+        "_repos_acme_api_actions_runs_9100001_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_api_actions_runs_9100001_jobs.json"),
+    ),
+    (
+        "_repos_acme_api_actions_runs_9100002_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_api_actions_runs_9100002_jobs.json"),
+    ),
+    (
+        "_repos_acme_api_actions_runs_9100003_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_api_actions_runs_9100003_jobs.json"),
+    ),
+    (
+        "_repos_acme_web_actions_runs",
+        include_str!("../../fixtures/demo/_repos_acme_web_actions_runs.json"),
+    ),
+    (
+        "_repos_acme_web_actions_runs_9200001_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_web_actions_runs_9200001_jobs.json"),
+    ),
+    (
+        "_repos_acme_web_actions_runs_9200002_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_web_actions_runs_9200002_jobs.json"),
+    ),
+    (
+        "_repos_acme_web_actions_runs_9200003_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_web_actions_runs_9200003_jobs.json"),
+    ),
+    (
+        "_repos_acme_infra_actions_runs",
+        include_str!("../../fixtures/demo/_repos_acme_infra_actions_runs.json"),
+    ),
+    (
+        "_repos_acme_infra_actions_runs_9300001_jobs",
+        include_str!("../../fixtures/demo/_repos_acme_infra_actions_runs_9300001_jobs.json"),
+    ),
+];
+
+/// Where fixture responses are read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixtureSource {
+    /// Bundled repos, runs and jobs, with the in progress runs repeating over time
+    Demo,
+    /// Directory of responses, e.g. as dumped into `glom-logs/`
+    Directory(PathBuf),
+}
+
+impl FixtureSource {
+    /// Keeps the snapshot, run history and watch list apart from those of the real
+    /// projects
+    pub fn state_dir(&self) -> PathBuf {
+        let name = match self {
+            FixtureSource::Demo => "glom-demo",
+            FixtureSource::Directory(_) => "glom-fixtures",
+        };
+        std::env::temp_dir().join(name)
+    }
+}
+
+#[derive(Debug)]
+enum Fixture {
+    Bundled(&'static str),
+    File(PathBuf),
+}
+
+/// Serves fixtures in place of the GitHub API
+#[derive(Debug)]
+pub struct FixtureBackend {
+    fixtures: HashMap<String, Fixture>,
+    /// Path of the API root, e.g. `/api/v3`, which the bundled fixtures are relative to
+    base_path: String,
+    /// Moves the bundled fixtures to the present; `None` when replaying a directory
+    demo_shift: Option<Duration>,
+}
+
+impl FixtureBackend {
+    pub fn open(source: &FixtureSource, base_url: &str) -> Result<Self> {
+        match source {
+            FixtureSource::Demo => {
+                let (year, month, day) = DEMO_CAPTURED_AT;
+                let captured_at = Utc
+                    .with_ymd_and_hms(year, month, day, 12, 0, 0)
+                    .unwrap();
+
+                Ok(Self {
+                    fixtures: DEMO_FIXTURES
+                        .iter()
+                        .map(|(key, body)| (key.to_string(), Fixture::Bundled(body)))
+                        .collect(),
+                    base_path: url::Url::parse(base_url)
+                        .map(|url| url.path().trim_end_matches('/').to_string())
+                        .unwrap_or_default(),
+                    demo_shift: Some(Utc::now() - captured_at),
+                })
+            },
+            FixtureSource::Directory(dir) => Ok(Self {
+                fixtures: index_directory(dir)?,
+                base_path: String::new(),
+                demo_shift: None,
+            }),
+        }
+    }
+
+    /// Status and body answering the request; list endpoints without a fixture answer
+    /// an empty list, other paths without one are not found
+    pub fn respond(&self, method: &Method, url: &str) -> (StatusCode, String) {
+        if method != Method::GET {
+            return error_response(StatusCode::METHOD_NOT_ALLOWED, "Fixtures are read-only");
+        }
+
+        let path = url::Url::parse(url)
+            .map(|url| url.path().to_string())
+            .unwrap_or_default();
+
+        let path = path
+            .strip_prefix(self.base_path.as_str())
+            .unwrap_or(&path);
+        let body = match self.demo_shift {
+            Some(shift) => self
+                .demo_response(path, shift)
+                .map(|body| body.to_string()),
+            None => self.fixture(path).map(Cow::into_owned),
+        }
+        .or_else(|| empty_collection(path).map(|body| body.to_string()));
+
+        match body {
+            Some(body) => {
+                debug!(path, "Answered from fixture");
+                (StatusCode::OK, body)
+            },
+            None => error_response(StatusCode::NOT_FOUND, "Not Found"),
+        }
+    }
+
+    fn fixture(&self, path: &str) -> Option<Cow<'static, str>> {
+        self.fixtures
+            .get(&fixture_key(path))
+            .and_then(Fixture::read)
+    }
+
+    /// The fixture of the path, moved to the present
+    fn shifted_fixture(&self, path: &str, shift: Duration) -> Option<Value> {
+        let body = self.fixture(path)?;
+        let mut value: Value = serde_json::from_str(&body)
+            .inspect_err(|e| warn!(path, error = %e, "Discarding unreadable fixture"))
+            .ok()?;
+
+        shift_timestamps(&mut value, shift);
+        Some(value)
+    }
+
+    /// Answers from the bundled fixtures, replacing in progress runs with the cycles
+    /// they went through since the start of the demo
+    fn demo_response(&self, path: &str, shift: Duration) -> Option<Value> {
+        let segments: Vec<&str> = path.split('/').collect();
+        match segments.as_slice() {
+            ["", "repos", _, _, "actions", "runs"] => {
+                let mut body = self.shifted_fixture(path, shift)?;
+                if let Some(runs) = body
+                    .get_mut("workflow_runs")
+                    .and_then(Value::as_array_mut)
+                {
+                    let now = Utc::now();
+                    *runs = runs
+                        .drain(..)
+                        .flat_map(|run| match self.live_run(path, &run, shift) {
+                            Some(live) => live.runs(now),
+                            None => vec![run],
+                        })
+                        .collect();
+                }
+                Some(body)
+            },
+            ["", "repos", owner, repo, "actions", "runs", run_id, "jobs"]
+                if !self.fixtures.contains_key(&fixture_key(path)) =>
+            {
+                let run_id: u64 = run_id.parse().ok()?;
+                let runs_path = format!("/repos/{owner}/{repo}/actions/runs");
+                let runs = self.shifted_fixture(&runs_path, shift)?;
+                let live = runs
+                    .get("workflow_runs")
+                    .and_then(Value::as_array)?
+                    .iter()
+                    .filter(|run| run["id"].as_u64() == Some(run_id / CYCLE_IDS))
+                    .find_map(|run| self.live_run(&runs_path, run, shift))?;
+
+                let jobs = live.jobs_at(run_id % CYCLE_IDS, Utc::now());
+                Some(json!({ "total_count": jobs.len(), "jobs": jobs }))
+            },
+            _ => self.shifted_fixture(path, shift),
+        }
+    }
+
+    /// The run repeating in the demo, if it is in progress in the fixtures
+    fn live_run(&self, runs_path: &str, run: &Value, shift: Duration) -> Option<LiveRun> {
+        if run["status"].as_str() == Some("completed") {
+            return None;
+        }
+
+        let id = run["id"].as_u64()?;
+        let jobs = self
+            .shifted_fixture(&format!("{runs_path}/{id}/jobs"), shift)?
+            .get("jobs")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        LiveRun::new(run.clone(), jobs)
+    }
+}
+
+/// Empty answer of the list endpoints, so panels without fixtures show nothing rather
+/// than an error
+fn empty_collection(path: &str) -> Option<Value> {
+    let segments: Vec<&str> = path.split('/').collect();
+    let body = match segments.as_slice() {
+        ["", "repos", _, _, "languages"] => json!({}),
+        ["", "repos", _, _, "commits" | "pulls" | "deployments"]
+        | ["", "repos", _, _, "pulls", _, "reviews"]
+        | ["", "repos", _, _, "deployments", _, "statuses"] => json!([]),
+        ["", "repos", _, _, "commits", _, "status"] => json!({ "statuses": [] }),
+        ["", "repos", _, _, "commits", _, "check-runs"] => {
+            json!({ "total_count": 0, "check_runs": [] })
+        },
+        ["", "repos", _, _, "environments"] => json!({ "total_count": 0, "environments": [] }),
+        ["", "repos", _, _, "actions", "runs", _, "jobs"] => {
+            json!({ "total_count": 0, "jobs": [] })
+        },
+        ["", "repos", _, _, "actions", "runs", _, "timing"] => json!({ "billable": {} }),
+        ["", "repos", _, _, "actions", "caches"] => {
+            json!({ "total_count": 0, "actions_caches": [] })
+        },
+        ["", "repos", _, _, "actions", "cache", "usage"] => {
+            json!({ "active_caches_size_in_bytes": 0, "active_caches_count": 0 })
+        },
+        ["", "repos", _, _, "actions", "runners"] | ["", "orgs", _, "actions", "runners"] => {
+            json!({ "total_count": 0, "runners": [] })
+        },
+        ["", "repos", _, _, "actions", "artifacts"] => json!({ "total_count": 0, "artifacts": [] }),
+        _ => return None,
+    };
+
+    debug!(path, "Answered with an empty collection");
+    Some(body)
+}
+
+impl Fixture {
+    fn read(&self) -> Option<Cow<'static, str>> {
+        match self {
+            Fixture::Bundled(body) => Some(Cow::Borrowed(body)),
+            Fixture::File(path) => fs::read_to_string(path)
+                .inspect_err(
+                    |e| warn!(path = %path.display(), error = %e, "Failed to read fixture"),
+                )
+                .ok()
+                .map(Cow::Owned),
+        }
+    }
+}
+
+/// A job of an in progress demo run, along the timeline of its run
+#[derive(Debug)]
+struct LiveJob {
+    fixture: Value,
+    started: Duration,
+    completed: Duration,
+}
+
+/// An in progress demo run, started over every `cycle`; its jobs are captured
+/// completed, and progress along their captured timeline. Failed jobs only fail
+/// every other cycle, so the branch of the run breaks and recovers.
+#[derive(Debug)]
+struct LiveRun {
+    fixture: Value,
+    id: u64,
+    jobs: Vec<LiveJob>,
+    /// Start of the first cycle, `DEMO_HISTORY` cycles before the demo started
+    origin: DateTime<Utc>,
+    cycle: Duration,
+}
+
+impl LiveRun {
+    fn new(fixture: Value, jobs: Vec<Value>) -> Option<Self> {
+        let id = fixture["id"].as_u64()?;
+        let created = timestamp(&fixture, "created_at")?;
+        let offset = |job: &Value, key| {
+            timestamp(job, key)
+                .map(|at| at - created)
+                .unwrap_or_default()
+        };
+
+        let jobs: Vec<LiveJob> = jobs
+            .into_iter()
+            .map(|job| LiveJob {
+                started: offset(&job, "started_at"),
+                completed: offset(&job, "completed_at"),
+                fixture: job,
+            })
+            .collect();
+
+        let cycle = jobs
+            .iter()
+            .map(|job| job.completed)
+            .max()
+            .unwrap_or_default()
+            + Duration::seconds(DEMO_PAUSE_SECS);
+        let origin = created - cycle * DEMO_HISTORY as i32;
+
+        Some(Self { fixture, id, jobs, origin, cycle })
+    }
+
+    /// The runs of the latest cycles, newest first
+    fn runs(&self, now: DateTime<Utc>) -> Vec<Value> {
+        let elapsed = (now - self.origin).num_seconds().max(0) as u64;
+        let current = elapsed / self.cycle.num_seconds().max(1) as u64;
+
+        (current.saturating_sub(DEMO_HISTORY)..=current)
+            .rev()
+            .map(|cycle| self.run_at(cycle, now))
+            .collect()
+    }
+
+    fn run_at(&self, cycle: u64, now: DateTime<Utc>) -> Value {
+        let start = self.start_of(cycle);
+        let jobs = self.jobs_at(cycle, now);
+        let job_status = |status: &str| {
+            jobs.iter()
+                .filter(|job| job["status"].as_str() == Some(status))
+                .count()
+        };
+
+        let completed = job_status("completed");
+        let (status, conclusion, updated_at) = match completed == jobs.len() {
+            true => {
+                let failed = jobs
+                    .iter()
+                    .any(|job| job["conclusion"].as_str() == Some("failure"));
+                let finished = self
+                    .jobs
+                    .iter()
+                    .map(|job| job.completed)
+                    .max()
+                    .unwrap_or_default();
+                let conclusion = if failed { "failure" } else { "success" };
+                ("completed", json!(conclusion), start + finished)
+            },
+            false if job_status("queued") == jobs.len() => ("queued", Value::Null, start),
+            false => ("in_progress", Value::Null, now),
+        };
+
+        let id = self.id * CYCLE_IDS + cycle;
+        let mut run = self.fixture.clone();
+        run["id"] = json!(id);
+        run["html_url"] = json!(self.url(id));
+        run["status"] = json!(status);
+        run["conclusion"] = conclusion;
+        run["created_at"] = json!(format_timestamp(start));
+        run["run_started_at"] = json!(format_timestamp(start));
+        run["updated_at"] = json!(format_timestamp(updated_at));
+        run
+    }
+
+    fn jobs_at(&self, cycle: u64, now: DateTime<Utc>) -> Vec<Value> {
+        let start = self.start_of(cycle);
+        let progress = now - start;
+        let run_id = self.id * CYCLE_IDS + cycle;
+        // the captured conclusions apply to the cycle in progress when the demo started
+        let fails = cycle % 2 == DEMO_HISTORY % 2;
+
+        self.jobs
+            .iter()
+            .map(|live| {
+                let mut job = live.fixture.clone();
+                let id = job["id"].as_u64().unwrap_or_default() * CYCLE_IDS + cycle;
+                job["id"] = json!(id);
+                job["run_id"] = json!(run_id);
+                job["html_url"] = json!(format!("{}/job/{id}", self.url(run_id)));
+                job["created_at"] = json!(format_timestamp(start));
+
+                let conclusion = match job["conclusion"].as_str() {
+                    Some("failure") if !fails => json!("success"),
+                    _ => job["conclusion"].take(),
+                };
+
+                let (status, conclusion, started_at, completed_at) = if progress < live.started {
+                    ("queued", Value::Null, Value::Null, Value::Null)
+                } else if progress < live.completed {
+                    let started_at = json!(format_timestamp(start + live.started));
+                    ("in_progress", Value::Null, started_at, Value::Null)
+                } else {
+                    let started_at = json!(format_timestamp(start + live.started));
+                    let completed_at = json!(format_timestamp(start + live.completed));
+                    ("completed", conclusion, started_at, completed_at)
+                };
+
+                job["status"] = json!(status);
+                job["conclusion"] = conclusion;
+                job["started_at"] = started_at;
+                job["completed_at"] = completed_at;
+                job
+            })
+            .collect()
+    }
+
+    fn start_of(&self, cycle: u64) -> DateTime<Utc> {
+        self.origin + self.cycle * cycle as i32
+    }
+
+    /// Web page of the run with the given id, next to the captured run
+    fn url(&self, id: u64) -> String {
+        let captured = self.fixture["html_url"]
+            .as_str()
+            .unwrap_or_default();
+        let actions = captured
+            .rsplit_once("/runs/")
+            .map_or(captured, |(actions, _)| actions);
+
+        format!("{actions}/runs/{id}")
+    }
+}
+
+/// Indexes the fixtures of the directory by the path they answer
+fn index_directory(dir: &Path) -> Result<HashMap<String, Fixture>> {
+    let names: Vec<String> = fs::read_dir(dir)
+        .map_err(|e| {
+            ClientError::config(format!(
+                "Failed to read fixtures from {}: {e}",
+                dir.display()
+            ))
+        })?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .sorted()
+        .collect();
+
+    // dumps sort by their timestamp, so later ones replace earlier dumps of a path
+    let fixtures: HashMap<String, Fixture> = names
+        .iter()
+        .filter_map(|name| {
+            file_key(name).map(|key| (key.to_string(), Fixture::File(dir.join(name))))
+        })
+        .collect();
+
+    if fixtures.is_empty() {
+        return Err(ClientError::config(format!(
+            "No JSON fixtures found in {}",
+            dir.display()
+        )));
+    }
+
+    debug!(dir = %dir.display(), count = fixtures.len(), "Indexed fixtures");
+    Ok(fixtures)
+}
+
+/// Fixture name of a request path, as dumped by `DebugConfig::log_responses`
+fn fixture_key(path: &str) -> String {
+    path.replace('/', "_")
+}
+
+/// Fixture name of a file, without its extension and timestamp prefix
+fn file_key(name: &str) -> Option<&str> {
+    let stem = name.strip_suffix(".json")?;
+    let dated = stem
+        .get(..DUMP_TIMESTAMP_LEN)
+        .is_some_and(|ts| NaiveDateTime::parse_from_str(ts, DUMP_TIMESTAMP_FORMAT).is_ok())
+        && stem[DUMP_TIMESTAMP_LEN..].starts_with('_');
+
+    match dated {
+        true => Some(&stem[DUMP_TIMESTAMP_LEN + 1..]),
+        false => Some(stem),
+    }
+}
+
+/// Moves the `*_at` timestamps of the response by `shift`
+fn shift_timestamps(value: &mut Value, shift: Duration) {
+    match value {
+        Value::Object(fields) => fields
+            .iter_mut()
+            .for_each(|(key, value)| match value {
+                Value::String(at) if key.ends_with("_at") => {
+                    if let Ok(parsed) = at.parse::<DateTime<Utc>>() {
+                        *at = format_timestamp(parsed + shift);
+                    }
+                },
+                value => shift_timestamps(value, shift),
+            }),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| shift_timestamps(item, shift)),
+        _ => (),
+    }
+}
+
+fn timestamp(value: &Value, key: &str) -> Option<DateTime<Utc>> {
+    value.get(key)?.as_str()?.parse().ok()
+}
+
+fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Error response in the shape of GitHub's
+fn error_response(status: StatusCode, message: &str) -> (StatusCode, String) {
+    (status, json!({ "message": message }).to_string())
+}
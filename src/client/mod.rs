@@ -6,15 +6,22 @@
 pub mod api;
 pub mod config;
 pub mod error;
+pub mod fixtures;
 pub mod poller;
+pub mod scheduler;
 pub mod service;
+pub mod stats;
+pub mod webhook;
 
 // Re-export main types for convenience
 pub use api::GithubApi;
 pub use config::ClientConfig;
 pub use error::ClientError;
+pub use fixtures::FixtureSource;
 pub use poller::GithubPoller;
 pub use service::GithubService;
+pub use stats::ApiStats;
+pub use webhook::WebhookReceiver;
 
 #[allow(dead_code)]
 pub type Result<T> = std::result::Result<T, ClientError>;
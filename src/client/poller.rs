@@ -5,18 +5,24 @@ use std::{sync::Arc, time::Duration};
 use tokio::{sync::broadcast, time::sleep};
 use tracing::{debug, error, info, instrument};
 
-use super::{api::GithubApi, config::PollingConfig, service::GithubService};
+use super::{config::PollingConfig, service::GithubService, webhook::WebhookActivity};
 use crate::{dispatcher::Dispatcher, event::GlomEvent};
 
+/// Webhook deliveries within this window count as flowing
+const WEBHOOK_ACTIVITY_WINDOW: Duration = Duration::from_secs(10 * 60);
+
 /// Background poller for GitHub resources
 ///
-/// Manages periodic fetching of projects and active jobs with configurable intervals
+/// Manages periodic fetching of projects and active jobs with configurable intervals.
+/// Intervals are stretched by `PollingConfig::webhook_backoff` while webhooks are flowing.
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct GithubPoller {
-    api: Arc<GithubApi>,
+    /// Shares the request scheduler of the application's service
+    service: GithubService,
     sender: std::sync::mpsc::Sender<GlomEvent>,
     config: PollingConfig,
+    webhook_activity: Option<Arc<WebhookActivity>>,
     shutdown_tx: broadcast::Sender<()>,
     shutdown_rx: broadcast::Receiver<()>,
 }
@@ -25,49 +31,73 @@ pub struct GithubPoller {
 impl GithubPoller {
     /// Create a new GitHub poller
     pub fn new(
-        api: Arc<GithubApi>,
+        service: GithubService,
         sender: std::sync::mpsc::Sender<GlomEvent>,
         config: PollingConfig,
     ) -> Self {
         let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
 
-        Self { api, sender, config, shutdown_tx, shutdown_rx }
+        Self {
+            service,
+            sender,
+            config,
+            webhook_activity: None,
+            shutdown_tx,
+            shutdown_rx,
+        }
+    }
+
+    /// Lower the polling frequency while webhook deliveries are flowing
+    pub fn with_webhook_activity(mut self, activity: Option<Arc<WebhookActivity>>) -> Self {
+        self.webhook_activity = activity;
+        self
     }
 
     /// Start polling in the background
     ///
-    /// This will spawn two separate async tasks:
+    /// This will spawn three separate async tasks:
     /// - One for polling projects at the configured interval
     /// - One for polling active jobs at the configured interval
+    /// - One for polling watched runs and branches at the configured interval
     #[instrument(skip(self))]
     pub async fn start(mut self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!(
             projects_interval = ?self.config.projects_interval,
             jobs_interval = ?self.config.jobs_interval,
+            watch_interval = ?self.config.watch_interval,
             "Starting GitHub poller"
         );
 
         // Spawn projects polling task
         let projects_task = {
-            let api = Arc::clone(&self.api);
-            let sender = self.sender.clone();
-            let interval = self.config.projects_interval;
+            let service = self.service.clone();
+            let interval = self.interval(self.config.projects_interval);
             let mut shutdown_rx = self.shutdown_tx.subscribe();
 
             tokio::spawn(async move {
-                Self::poll_projects(api, sender, interval, &mut shutdown_rx).await;
+                Self::poll_projects(service, interval, &mut shutdown_rx).await;
             })
         };
 
         // Spawn jobs polling task
         let jobs_task = {
-            let api = Arc::clone(&self.api);
             let sender = self.sender.clone();
-            let interval = self.config.jobs_interval;
+            let interval = self.interval(self.config.jobs_interval);
+            let mut shutdown_rx = self.shutdown_tx.subscribe();
+
+            tokio::spawn(async move {
+                Self::poll_active_jobs(sender, interval, &mut shutdown_rx).await;
+            })
+        };
+
+        // Spawn watched runs polling task
+        let watch_task = {
+            let sender = self.sender.clone();
+            let interval = self.interval(self.config.watch_interval);
             let mut shutdown_rx = self.shutdown_tx.subscribe();
 
             tokio::spawn(async move {
-                Self::poll_active_jobs(api, sender, interval, &mut shutdown_rx).await;
+                Self::poll_watched(sender, interval, &mut shutdown_rx).await;
             })
         };
 
@@ -79,6 +109,7 @@ impl GithubPoller {
         // Cancel polling tasks
         projects_task.abort();
         jobs_task.abort();
+        watch_task.abort();
 
         // Wait a bit for graceful shutdown
         sleep(Duration::from_millis(100)).await;
@@ -109,21 +140,28 @@ impl GithubPoller {
 
     // Private polling implementations
 
+    /// Interval provider which backs off while webhook deliveries are flowing
+    fn interval(&self, base: Duration) -> PollInterval {
+        PollInterval {
+            base,
+            backoff: self.config.webhook_backoff.max(1),
+            activity: self.webhook_activity.clone(),
+        }
+    }
+
     /// Poll projects at regular intervals
-    #[instrument(skip(api, sender, shutdown_rx), fields(interval = ?interval))]
+    #[instrument(skip(service, shutdown_rx), fields(interval = ?interval.base))]
     async fn poll_projects(
-        api: Arc<GithubApi>,
-        sender: std::sync::mpsc::Sender<GlomEvent>,
-        interval: Duration,
+        service: GithubService,
+        interval: PollInterval,
         shutdown_rx: &mut broadcast::Receiver<()>,
     ) {
         debug!("Starting projects polling loop");
 
         loop {
             tokio::select! {
-                _ = sleep(interval) => {
+                _ = sleep(interval.current()) => {
                     debug!("Polling projects");
-                    let service = GithubService::from_api(api.clone(), sender.clone()).unwrap();
                     service.spawn_fetch_projects(None);
                 }
                 _ = shutdown_rx.recv() => {
@@ -137,18 +175,17 @@ impl GithubPoller {
     }
 
     /// Poll active jobs at regular intervals
-    #[instrument(skip(_api, sender, shutdown_rx), fields(interval = ?interval))]
+    #[instrument(skip(sender, shutdown_rx), fields(interval = ?interval.base))]
     async fn poll_active_jobs(
-        _api: Arc<GithubApi>,
         sender: std::sync::mpsc::Sender<GlomEvent>,
-        interval: Duration,
+        interval: PollInterval,
         shutdown_rx: &mut broadcast::Receiver<()>,
     ) {
         debug!("Starting active jobs polling loop");
 
         loop {
             tokio::select! {
-                _ = sleep(interval) => {
+                _ = sleep(interval.current()) => {
                     debug!("Requesting active jobs refresh");
                     // Dispatch event to request active jobs refresh
                     // The main application will handle which jobs to fetch
@@ -163,13 +200,61 @@ impl GithubPoller {
 
         debug!("Active jobs polling loop ended");
     }
+
+    /// Poll watched runs and branches at regular intervals
+    #[instrument(skip(sender, shutdown_rx), fields(interval = ?interval.base))]
+    async fn poll_watched(
+        sender: std::sync::mpsc::Sender<GlomEvent>,
+        interval: PollInterval,
+        shutdown_rx: &mut broadcast::Receiver<()>,
+    ) {
+        debug!("Starting watched runs polling loop");
+
+        loop {
+            tokio::select! {
+                _ = sleep(interval.current()) => {
+                    // The main application knows which runs are watched
+                    sender.dispatch(GlomEvent::WatchedFetch);
+                }
+                _ = shutdown_rx.recv() => {
+                    debug!("Watched runs polling received shutdown signal");
+                    break;
+                }
+            }
+        }
+
+        debug!("Watched runs polling loop ended");
+    }
+}
+
+/// Polling interval that is stretched while webhooks are delivering updates
+#[derive(Debug, Clone)]
+struct PollInterval {
+    base: Duration,
+    backoff: u32,
+    activity: Option<Arc<WebhookActivity>>,
+}
+
+impl PollInterval {
+    fn current(&self) -> Duration {
+        match &self.activity {
+            Some(activity) if activity.is_flowing(WEBHOOK_ACTIVITY_WINDOW) => {
+                debug!(
+                    backoff = self.backoff,
+                    "Webhooks are flowing, polling less often"
+                );
+                self.base * self.backoff
+            },
+            _ => self.base,
+        }
+    }
 }
 
 /// Builder for GithubPoller with fluent API
 #[derive(Debug)]
 #[allow(dead_code)]
 pub struct GithubPollerBuilder {
-    api: Option<Arc<GithubApi>>,
+    service: Option<GithubService>,
     sender: Option<std::sync::mpsc::Sender<GlomEvent>>,
     config: PollingConfig,
 }
@@ -179,15 +264,15 @@ impl GithubPollerBuilder {
     /// Create a new poller builder
     pub fn new() -> Self {
         Self {
-            api: None,
+            service: None,
             sender: None,
             config: PollingConfig::default(),
         }
     }
 
-    /// Set the GitHub API
-    pub fn api(mut self, api: Arc<GithubApi>) -> Self {
-        self.api = Some(api);
+    /// Set the GitHub service
+    pub fn service(mut self, service: GithubService) -> Self {
+        self.service = Some(service);
         self
     }
 
@@ -217,9 +302,9 @@ impl GithubPollerBuilder {
 
     /// Build the GitHub poller
     pub fn build(self) -> Result<GithubPoller, String> {
-        let api = self.api.ok_or("GitHub API is required")?;
+        let service = self.service.ok_or("GitHub service is required")?;
         let sender = self.sender.ok_or("Event sender is required")?;
-        Ok(GithubPoller::new(api, sender, self.config))
+        Ok(GithubPoller::new(service, sender, self.config))
     }
 }
 
@@ -234,11 +319,11 @@ impl Default for GithubPollerBuilder {
 /// This is a convenience function for quickly starting background polling
 #[allow(dead_code)]
 pub async fn spawn_poller(
-    api: Arc<GithubApi>,
+    service: GithubService,
     sender: std::sync::mpsc::Sender<GlomEvent>,
     config: PollingConfig,
 ) -> broadcast::Sender<()> {
-    let poller = GithubPoller::new(api, sender, config);
+    let poller = GithubPoller::new(service, sender, config);
     let shutdown_sender = poller.shutdown_sender();
 
     tokio::spawn(async move {
@@ -0,0 +1,89 @@
+//! Deduplication and concurrency limiting of GitHub API requests
+
+use std::{
+    collections::HashSet,
+    future::Future,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use compact_str::CompactString;
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+/// Coalesces duplicate requests and caps the number of concurrent requests
+///
+/// Requests are keyed by endpoint. A request for an endpoint which is already in
+/// flight is dropped, as the results of requests are dispatched as events to
+/// everyone interested in them.
+#[derive(Debug)]
+pub struct RequestScheduler {
+    in_flight: Mutex<HashSet<CompactString>>,
+    permits: Semaphore,
+    /// Requests waiting for a permit
+    queued: AtomicUsize,
+}
+
+/// Removes the endpoint from the in-flight registry once the request completes
+/// or its task is aborted
+struct InFlightGuard<'a> {
+    scheduler: &'a RequestScheduler,
+    endpoint: CompactString,
+}
+
+impl RequestScheduler {
+    pub fn new(max_concurrent_requests: usize) -> Self {
+        Self {
+            in_flight: Mutex::new(HashSet::new()),
+            permits: Semaphore::new(max_concurrent_requests.max(1)),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Runs the request once a permit is available, unless a request for the same
+    /// endpoint is already in flight, in which case `None` is returned.
+    pub async fn run<T>(
+        &self,
+        endpoint: CompactString,
+        request: impl Future<Output = T>,
+    ) -> Option<T> {
+        let _guard = self.register(endpoint.clone())?;
+
+        let queued = self.queued.fetch_add(1, Ordering::Relaxed) + 1;
+        debug!(
+            endpoint = %endpoint,
+            queue_depth = queued,
+            available_permits = self.permits.available_permits(),
+            "Request queued"
+        );
+
+        let permit = self.permits.acquire().await;
+        self.queued.fetch_sub(1, Ordering::Relaxed);
+        // the semaphore is never closed
+        let _permit = permit.ok()?;
+
+        Some(request.await)
+    }
+
+    fn register(&self, endpoint: CompactString) -> Option<InFlightGuard<'_>> {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if !in_flight.insert(endpoint.clone()) {
+            debug!(endpoint = %endpoint, "Coalescing request already in flight");
+            return None;
+        }
+
+        Some(InFlightGuard { scheduler: self, endpoint })
+    }
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.scheduler
+            .in_flight
+            .lock()
+            .unwrap()
+            .remove(&self.endpoint);
+    }
+}
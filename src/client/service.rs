@@ -1,18 +1,24 @@
 //! High-level GitHub service operations
 
-use std::sync::{Arc, mpsc::Sender};
+use std::{
+    future::Future,
+    sync::{Arc, mpsc::Sender},
+};
 
 use chrono::{DateTime, Utc};
+use compact_str::{CompactString, format_compact};
 use tokio::runtime::Handle;
 use tracing::{debug, error, info, instrument, warn};
 
 use super::{
     api::GithubApi,
-    config::ClientConfig,
+    config::{ClientConfig, PipelineQuery, RunFilter},
     error::{ClientError, Result},
+    scheduler::RequestScheduler,
 };
 use crate::{
     dispatcher::Dispatcher,
+    domain::CacheDeletion,
     event::{GlomEvent, IntoGlomEvent},
     id::{JobId, PipelineId, ProjectId},
     result::GlomError::{self, GeneralError},
@@ -21,20 +27,25 @@ use crate::{
 /// High-level service for GitHub operations
 ///
 /// Orchestrates API calls and handles event dispatching to the application
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GithubService {
     api: Arc<GithubApi>,
     sender: Sender<GlomEvent>,
     handle: Handle,
+    /// Shared by all clones, deduplicating and limiting their requests
+    scheduler: Arc<RequestScheduler>,
 }
 
 impl GithubService {
-    /// Create service from existing API client
+    /// Create service from existing API client, limiting concurrent requests to the
+    /// configured maximum
     pub fn from_api(api: Arc<GithubApi>, sender: Sender<GlomEvent>) -> Result<Self> {
         let handle = Handle::try_current().map_err(|_| {
             ClientError::config("GithubService must be created within a Tokio runtime context")
         })?;
-        Ok(Self { api, sender, handle })
+        let max_concurrent_requests = api.config().request.max_concurrent_requests;
+        let scheduler = Arc::new(RequestScheduler::new(max_concurrent_requests));
+        Ok(Self { api, sender, handle, scheduler })
     }
 
     /// Fetch projects and dispatch results as events
@@ -72,22 +83,12 @@ impl GithubService {
     }
 
     /// Fetch pipelines for a project and dispatch results as events
-    #[instrument(skip(self), fields(project_id = %project_id, updated_after = ?updated_after))]
-    pub async fn fetch_pipelines(
-        &self,
-        project_id: ProjectId,
-        updated_after: Option<DateTime<Utc>>,
-    ) -> Result<()> {
+    #[instrument(skip(self, query), fields(project_id = %project_id, updated_after = ?query.updated_after))]
+    pub async fn fetch_pipelines(&self, project_id: ProjectId, query: PipelineQuery) -> Result<()> {
         if !self.api.is_configured() {
             return Ok(());
         }
 
-        let query = self
-            .api
-            .config()
-            .default_pipeline_query()
-            .with_updated_after(updated_after);
-
         match self
             .api
             .get_pipelines(project_id.clone(), &query)
@@ -116,7 +117,8 @@ impl GithubService {
         }
     }
 
-    /// Fetch all jobs (regular + trigger jobs) for a pipeline and dispatch results
+    /// Fetch the jobs of every attempt of a pipeline and dispatch results; earlier
+    /// attempts are recorded in the run history, the latest one is shown
     #[instrument(skip(self), fields(project_id = %project_id, pipeline_id = %pipeline_id))]
     pub async fn fetch_all_jobs(
         &self,
@@ -129,7 +131,7 @@ impl GithubService {
 
         match self
             .api
-            .get_jobs(project_id.clone(), pipeline_id)
+            .get_jobs_of_all_attempts(project_id.clone(), pipeline_id)
             .await
         {
             Ok(jobs) => {
@@ -238,6 +240,316 @@ impl GithubService {
         }
     }
 
+    /// Fetch the Actions caches and their usage, and dispatch them as an event
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn fetch_caches(&self, project_id: ProjectId) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        info!(project_id = %project_id, "Fetching caches from GitHub");
+
+        let result = async {
+            let usage = self
+                .api
+                .get_cache_usage(project_id.clone())
+                .await?;
+            let caches = self
+                .api
+                .get_caches(project_id.clone(), None)
+                .await?;
+            Ok::<_, ClientError>((usage, caches))
+        }
+        .await;
+
+        match result {
+            Ok((usage, caches)) => {
+                self.sender
+                    .dispatch(GlomEvent::CachesLoaded(project_id, usage, caches));
+                Ok(())
+            },
+            Err(e) => {
+                error!(
+                    error = %e,
+                    project_id = %project_id,
+                    "Failed to fetch caches"
+                );
+                let glom_error = crate::result::GlomError::from(&e);
+                self.sender
+                    .dispatch(GlomEvent::AppError(glom_error));
+                Err(e)
+            },
+        }
+    }
+
+    /// Delete caches, then reload the remaining caches
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn delete_caches(
+        &self,
+        project_id: ProjectId,
+        deletion: CacheDeletion,
+    ) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        let result = async {
+            let ids = match &deletion {
+                CacheDeletion::Id(id) => vec![*id],
+                CacheDeletion::KeyPrefix(prefix) => self
+                    .api
+                    .get_caches(project_id.clone(), Some(prefix))
+                    .await?
+                    .iter()
+                    .map(|c| c.id)
+                    .collect(),
+            };
+
+            for id in &ids {
+                self.api
+                    .delete_cache(project_id.clone(), *id)
+                    .await?;
+            }
+            Ok::<_, ClientError>(ids.len())
+        }
+        .await;
+
+        match result {
+            Ok(count) => {
+                info!(project_id = %project_id, count = count, "Deleted caches");
+                self.fetch_caches(project_id).await
+            },
+            Err(e) => {
+                error!(
+                    error = %e,
+                    project_id = %project_id,
+                    "Failed to delete caches"
+                );
+                let glom_error = crate::result::GlomError::from(&e);
+                self.sender
+                    .dispatch(GlomEvent::AppError(glom_error));
+                // reflect any caches deleted before the failure
+                let _ = self.fetch_caches(project_id).await;
+                Err(e)
+            },
+        }
+    }
+
+    /// Fetch the self-hosted runners of a project and dispatch them as an event
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn fetch_runners(&self, project_id: ProjectId) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        info!(project_id = %project_id, "Fetching runners from GitHub");
+
+        match self.api.get_runners(project_id.clone()).await {
+            Ok(runners) => {
+                self.sender
+                    .dispatch(GlomEvent::RunnersLoaded(project_id, runners));
+                Ok(())
+            },
+            Err(e) => {
+                error!(
+                    error = %e,
+                    project_id = %project_id,
+                    "Failed to fetch runners"
+                );
+                let glom_error = crate::result::GlomError::from(&e);
+                self.sender
+                    .dispatch(GlomEvent::AppError(glom_error));
+                Err(e)
+            },
+        }
+    }
+
+    /// Fetch the latest deployment of each environment of a project and dispatch them as an event
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn fetch_environments(&self, project_id: ProjectId) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        info!(project_id = %project_id, "Fetching environments from GitHub");
+
+        match self
+            .api
+            .get_environment_deployments(project_id.clone())
+            .await
+        {
+            Ok(environments) => {
+                self.sender
+                    .dispatch(GlomEvent::EnvironmentsLoaded(project_id, environments));
+                Ok(())
+            },
+            Err(e) => {
+                error!(
+                    error = %e,
+                    project_id = %project_id,
+                    "Failed to fetch environments"
+                );
+                let glom_error = crate::result::GlomError::from(&e);
+                self.sender
+                    .dispatch(GlomEvent::AppError(glom_error));
+                Err(e)
+            },
+        }
+    }
+
+    /// Fetch the open pull requests of a project and dispatch them as an event
+    #[instrument(skip(self), fields(project_id = %project_id))]
+    pub async fn fetch_pull_requests(&self, project_id: ProjectId) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        info!(project_id = %project_id, "Fetching pull requests from GitHub");
+
+        match self
+            .api
+            .get_pull_requests(project_id.clone())
+            .await
+        {
+            Ok(pull_requests) => {
+                self.sender
+                    .dispatch(GlomEvent::PullRequestsLoaded(project_id, pull_requests));
+                Ok(())
+            },
+            Err(e) => {
+                error!(
+                    error = %e,
+                    project_id = %project_id,
+                    "Failed to fetch pull requests"
+                );
+                let glom_error = crate::result::GlomError::from(&e);
+                self.sender
+                    .dispatch(GlomEvent::AppError(glom_error));
+                Err(e)
+            },
+        }
+    }
+
+    /// Fetch the workflow runs of a pull request's head commit and dispatch them as an event
+    #[instrument(skip(self), fields(project_id = %project_id, number = number))]
+    pub async fn fetch_pull_request_runs(
+        &self,
+        project_id: ProjectId,
+        number: u64,
+        head_sha: CompactString,
+    ) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        let filter = RunFilter { head_sha: Some(head_sha), ..RunFilter::default() };
+        let query = self
+            .api
+            .config()
+            .default_pipeline_query()
+            .with_filter(filter);
+
+        match self
+            .api
+            .get_pipelines(project_id.clone(), &query)
+            .await
+        {
+            Ok(pipelines) => {
+                self.sender
+                    .dispatch(GlomEvent::PullRequestRunsLoaded(
+                        project_id, number, pipelines,
+                    ));
+                Ok(())
+            },
+            Err(e) => {
+                error!(
+                    error = %e,
+                    project_id = %project_id,
+                    number = number,
+                    "Failed to fetch pull request runs"
+                );
+                let glom_error = crate::result::GlomError::from(&e);
+                self.sender
+                    .dispatch(GlomEvent::AppError(glom_error));
+                Err(e)
+            },
+        }
+    }
+
+    /// Fetch the external checks of a commit and dispatch them as an event
+    #[instrument(skip(self), fields(project_id = %project_id, sha = %sha))]
+    pub async fn fetch_commit_checks(
+        &self,
+        project_id: ProjectId,
+        sha: CompactString,
+    ) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        match self
+            .api
+            .get_commit_checks(project_id.clone(), &sha, false)
+            .await
+        {
+            Ok(checks) => {
+                self.sender
+                    .dispatch(GlomEvent::CommitChecksLoaded(project_id, sha, checks));
+                Ok(())
+            },
+            Err(e) => {
+                // the runs themselves are still shown; not worth interrupting the user over
+                warn!(
+                    error = %e,
+                    project_id = %project_id,
+                    sha = %sha,
+                    "Failed to fetch commit checks"
+                );
+                Err(e)
+            },
+        }
+    }
+
+    /// Fetch the billable time of a workflow run and dispatch it as an event
+    #[instrument(skip(self), fields(project_id = %project_id, pipeline_id = %pipeline_id))]
+    pub async fn fetch_run_timing(
+        &self,
+        project_id: ProjectId,
+        pipeline_id: PipelineId,
+    ) -> Result<()> {
+        if !self.api.is_configured() {
+            return Ok(());
+        }
+
+        match self
+            .api
+            .get_run_timing(project_id.clone(), pipeline_id)
+            .await
+        {
+            Ok(timing) => {
+                debug!(
+                    project_id = %project_id,
+                    pipeline_id = %pipeline_id,
+                    runner_os_count = timing.billable.len(),
+                    "Successfully fetched run timing"
+                );
+                self.sender
+                    .dispatch(GlomEvent::RunTimingLoaded(project_id, pipeline_id, timing));
+                Ok(())
+            },
+            Err(e) => {
+                // billing data is supplementary; not worth interrupting the user over
+                warn!(
+                    error = %e,
+                    project_id = %project_id,
+                    pipeline_id = %pipeline_id,
+                    "Failed to fetch run timing"
+                );
+                Err(e)
+            },
+        }
+    }
+
     /// Update service configuration
     pub fn update_config(&self, config: ClientConfig) -> Result<()> {
         self.api.update_config(config)
@@ -258,76 +570,164 @@ impl GithubService {
     ///
     /// This is a convenience method for fire-and-forget project fetching
     pub fn spawn_fetch_projects(&self, updated_after: Option<DateTime<Utc>>) {
-        let api = self.api.clone();
-        let sender = self.sender.clone();
-        self.handle.spawn(async move {
-            let temp_service = Self::from_api(api, sender).unwrap();
-            if let Err(e) = temp_service.fetch_projects(updated_after).await {
-                warn!("Background project fetch failed: {}", e);
-            }
-        });
+        self.spawn_request(
+            "/user/repos".into(),
+            "project fetch",
+            move |service| async move { service.fetch_projects(updated_after).await },
+        );
     }
 
-    /// Spawn an async task to fetch pipelines
+    /// Spawn an async task to fetch pipelines. Requests are deduplicated with their
+    /// query, so neither a changed run filter nor a full fetch is swallowed by a fetch
+    /// already in flight
     pub fn spawn_fetch_pipelines(
         &self,
         project_id: ProjectId,
         updated_after: Option<DateTime<Utc>>,
     ) {
-        let api = self.api.clone();
-        let sender = self.sender.clone();
-        self.handle.spawn(async move {
-            let temp_service = Self::from_api(api, sender).unwrap();
-            if let Err(e) = temp_service
-                .fetch_pipelines(project_id, updated_after)
-                .await
-            {
-                warn!("Background pipeline fetch failed: {}", e);
-            }
+        let config = self.api.config();
+        let query = config
+            .default_pipeline_query()
+            .with_filter(config.run_filter(&project_id))
+            .with_updated_after(updated_after);
+
+        let params = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query.query_pairs())
+            .finish();
+        let endpoint = format_compact!("/repos/{project_id}/actions/runs?{params}");
+        self.spawn_request(endpoint, "pipeline fetch", move |service| async move {
+            service.fetch_pipelines(project_id, query).await
         });
     }
 
     /// Spawn an async task to fetch jobs
     pub fn spawn_fetch_jobs(&self, project_id: ProjectId, pipeline_id: PipelineId) {
-        let api = self.api.clone();
-        let sender = self.sender.clone();
-        self.handle.spawn(async move {
-            let temp_service = Self::from_api(api, sender).unwrap();
-            if let Err(e) = temp_service
+        let endpoint = format_compact!("/repos/{project_id}/actions/runs/{pipeline_id}/jobs");
+        self.spawn_request(endpoint, "job fetch", move |service| async move {
+            service
                 .fetch_all_jobs(project_id, pipeline_id)
                 .await
-            {
-                warn!("Background job fetch failed: {}", e);
-            }
         });
     }
 
     /// Spawn an async task to download job log
     pub fn spawn_download_job_log(&self, project_id: ProjectId, job_id: JobId) {
-        let api = self.api.clone();
-        let sender = self.sender.clone();
-        self.handle.spawn(async move {
-            let temp_service = Self::from_api(api, sender).unwrap();
-            if let Err(e) = temp_service
-                .download_job_log(project_id, job_id)
+        let endpoint = format_compact!("/repos/{project_id}/actions/jobs/{job_id}/logs");
+        self.spawn_request(endpoint, "job log download", move |service| async move {
+            service.download_job_log(project_id, job_id).await
+        });
+    }
+
+    /// Spawn an async task to fetch caches
+    pub fn spawn_fetch_caches(&self, project_id: ProjectId) {
+        let endpoint = format_compact!("/repos/{project_id}/actions/caches");
+        self.spawn_request(endpoint, "cache fetch", move |service| async move {
+            service.fetch_caches(project_id).await
+        });
+    }
+
+    /// Spawn an async task to delete caches
+    pub fn spawn_delete_caches(&self, project_id: ProjectId, deletion: CacheDeletion) {
+        let endpoint = match &deletion {
+            CacheDeletion::Id(id) => {
+                format_compact!("DELETE /repos/{project_id}/actions/caches/{id}")
+            },
+            CacheDeletion::KeyPrefix(prefix) => {
+                format_compact!("DELETE /repos/{project_id}/actions/caches?key={prefix}")
+            },
+        };
+        self.spawn_request(endpoint, "cache deletion", move |service| async move {
+            service.delete_caches(project_id, deletion).await
+        });
+    }
+
+    /// Spawn an async task to fetch runners
+    pub fn spawn_fetch_runners(&self, project_id: ProjectId) {
+        let endpoint = format_compact!("/repos/{project_id}/actions/runners");
+        self.spawn_request(endpoint, "runner fetch", move |service| async move {
+            service.fetch_runners(project_id).await
+        });
+    }
+
+    /// Spawn an async task to fetch environments and their deployments
+    pub fn spawn_fetch_environments(&self, project_id: ProjectId) {
+        let endpoint = format_compact!("/repos/{project_id}/environments");
+        self.spawn_request(endpoint, "environment fetch", move |service| async move {
+            service.fetch_environments(project_id).await
+        });
+    }
+
+    /// Spawn an async task to fetch open pull requests
+    pub fn spawn_fetch_pull_requests(&self, project_id: ProjectId) {
+        let endpoint = format_compact!("/repos/{project_id}/pulls");
+        self.spawn_request(endpoint, "pull request fetch", move |service| async move {
+            service.fetch_pull_requests(project_id).await
+        });
+    }
+
+    /// Spawn an async task to fetch the workflow runs of a pull request
+    pub fn spawn_fetch_pull_request_runs(
+        &self,
+        project_id: ProjectId,
+        number: u64,
+        head_sha: CompactString,
+    ) {
+        let endpoint = format_compact!("/repos/{project_id}/actions/runs?head_sha={head_sha}");
+        self.spawn_request(
+            endpoint,
+            "pull request run fetch",
+            move |service| async move {
+                service
+                    .fetch_pull_request_runs(project_id, number, head_sha)
+                    .await
+            },
+        );
+    }
+
+    /// Spawn an async task to fetch the external checks of a commit
+    pub fn spawn_fetch_commit_checks(&self, project_id: ProjectId, sha: CompactString) {
+        let endpoint = format_compact!("/repos/{project_id}/commits/{sha}/check-runs");
+        self.spawn_request(endpoint, "commit checks fetch", move |service| async move {
+            service.fetch_commit_checks(project_id, sha).await
+        });
+    }
+
+    /// Spawn an async task to fetch the billable time of a workflow run
+    pub fn spawn_fetch_run_timing(&self, project_id: ProjectId, pipeline_id: PipelineId) {
+        let endpoint = format_compact!("/repos/{project_id}/actions/runs/{pipeline_id}/timing");
+        self.spawn_request(endpoint, "run timing fetch", move |service| async move {
+            service
+                .fetch_run_timing(project_id, pipeline_id)
                 .await
-            {
-                warn!("Background job log download failed: {}", e);
-            }
         });
     }
 
     /// Spawn an async task to fetch repository statistics
     pub fn spawn_fetch_repository_statistics(&self, project_id: ProjectId) {
-        let api = self.api.clone();
-        let sender = self.sender.clone();
+        let endpoint = format_compact!("/repos/{project_id}");
+        self.spawn_request(
+            endpoint,
+            "repository statistics fetch",
+            move |service| async move {
+                service
+                    .fetch_repository_statistics(project_id)
+                    .await
+            },
+        );
+    }
+
+    /// Spawn a request through the scheduler, which drops it if a request for the
+    /// same endpoint is already in flight and otherwise waits for a free slot
+    fn spawn_request<F, Fut>(&self, endpoint: CompactString, description: &'static str, request: F)
+    where
+        F: FnOnce(GithubService) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<()>> + Send,
+    {
+        let service = self.clone();
         self.handle.spawn(async move {
-            let temp_service = Self::from_api(api, sender).unwrap();
-            if let Err(e) = temp_service
-                .fetch_repository_statistics(project_id)
-                .await
-            {
-                warn!("Background repository statistics fetch failed: {}", e);
+            let scheduler = service.scheduler.clone();
+            if let Some(Err(e)) = scheduler.run(endpoint, request(service)).await {
+                warn!("Background {} failed: {}", description, e);
             }
         });
     }
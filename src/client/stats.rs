@@ -0,0 +1,68 @@
+//! Counters of GitHub API requests, e.g. for the Prometheus exporter
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+use reqwest::Response;
+
+/// Requests sent by a `GithubApi`, shared with whoever reports them
+#[derive(Debug)]
+pub struct ApiStats {
+    requests: AtomicU64,
+    /// Requests failing to send, or answered with an error status
+    errors: AtomicU64,
+    /// From the `x-ratelimit-remaining` header of the last response; -1 until known
+    rate_limit_remaining: AtomicI64,
+}
+
+impl Default for ApiStats {
+    fn default() -> Self {
+        Self {
+            requests: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            rate_limit_remaining: AtomicI64::new(-1),
+        }
+    }
+}
+
+impl ApiStats {
+    pub fn record(&self, response: &reqwest::Result<Response>) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        let response = match response {
+            Ok(response) => response,
+            Err(_) => {
+                self.errors.fetch_add(1, Ordering::Relaxed);
+                return;
+            },
+        };
+
+        let status = response.status();
+        if status.is_client_error() || status.is_server_error() {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+
+        if let Some(remaining) = response
+            .headers()
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok())
+        {
+            self.rate_limit_remaining
+                .store(remaining, Ordering::Relaxed);
+        }
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    /// `None` until a response carried the rate limit headers
+    pub fn rate_limit_remaining(&self) -> Option<i64> {
+        let remaining = self.rate_limit_remaining.load(Ordering::Relaxed);
+        (remaining >= 0).then_some(remaining)
+    }
+}
@@ -0,0 +1,383 @@
+//! Embedded HTTP listener for GitHub webhook deliveries
+//!
+//! Accepts `workflow_run` and `workflow_job` payloads, e.g. forwarded by
+//! `gh webhook forward`, and turns them into the same events the poller produces.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicI64, Ordering},
+        mpsc::Sender,
+    },
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::{
+    Method, Request, Response, StatusCode,
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use itertools::Itertools;
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+use tokio::net::TcpListener;
+use tracing::{debug, info, instrument, warn};
+
+use super::{
+    api::GithubApi,
+    config::WebhookConfig,
+    error::{ClientError, Result},
+};
+use crate::{
+    dispatcher::Dispatcher,
+    domain::{JobDto, WorkflowJobEventDto, WorkflowRunEventDto},
+    event::{GlomEvent, IntoGlomEvent},
+    id::{PipelineId, ProjectId},
+};
+
+/// GitHub caps webhook payloads at 25 MB
+const MAX_PAYLOAD_BYTES: usize = 25 * 1024 * 1024;
+
+/// Runs whose jobs are cached at most; the least recently updated are evicted first
+const MAX_CACHED_RUNS: usize = 256;
+
+/// Jobs of runs without a delivery for this long are dropped, e.g. if the completed
+/// `workflow_run` delivery was missed or the repository is no longer watched
+const CACHED_JOBS_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Jobs of a run, as seeded from the API and updated by deliveries
+#[derive(Debug)]
+struct CachedJobs {
+    jobs: Vec<JobDto>,
+    /// Set while the run's first delivery fetches its jobs from the API
+    seeding: bool,
+    updated: Instant,
+}
+
+impl CachedJobs {
+    fn seeding() -> Self {
+        Self {
+            jobs: Vec::new(),
+            seeding: true,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Merges a delivered job, dropping the jobs of earlier attempts
+    fn merge(&mut self, job: JobDto) {
+        self.updated = Instant::now();
+        self.jobs
+            .retain(|j| j.run_attempt >= job.run_attempt);
+        match self.jobs.iter_mut().find(|j| j.id == job.id) {
+            Some(existing) => *existing = job,
+            None => {
+                self.jobs.push(job);
+                self.jobs.sort_by_key(|j| j.id);
+            },
+        }
+    }
+
+    /// Merges the jobs fetched from the API; jobs delivered meanwhile are newer
+    fn seed(&mut self, jobs: Vec<JobDto>) {
+        let latest_attempt = self.jobs.iter().map(|j| j.run_attempt).max();
+        for job in jobs {
+            let delivered = self.jobs.iter().any(|j| j.id == job.id);
+            if !delivered && latest_attempt.is_none_or(|attempt| job.run_attempt >= attempt) {
+                self.merge(job);
+            }
+        }
+        self.seeding = false;
+    }
+}
+
+/// Tracks when the last verified webhook delivery was received
+#[derive(Debug, Default)]
+pub struct WebhookActivity {
+    /// Unix timestamp in seconds; zero if nothing has been received yet
+    last_delivery: AtomicI64,
+}
+
+impl WebhookActivity {
+    pub fn record_delivery(&self) {
+        self.last_delivery
+            .store(Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    /// Returns true if a delivery was received within the given window
+    pub fn is_flowing(&self, window: Duration) -> bool {
+        let last = self.last_delivery.load(Ordering::Relaxed);
+        last > 0 && Utc::now().timestamp() - last <= window.as_secs() as i64
+    }
+}
+
+/// Receives GitHub webhook deliveries and dispatches them as application events
+#[derive(Debug)]
+pub struct WebhookReceiver {
+    api: Arc<GithubApi>,
+    sender: Sender<GlomEvent>,
+    config: WebhookConfig,
+    activity: Arc<WebhookActivity>,
+    /// Jobs per run, seeded from the API and updated by `workflow_job` deliveries
+    jobs: Mutex<HashMap<PipelineId, CachedJobs>>,
+}
+
+impl WebhookReceiver {
+    pub fn new(api: Arc<GithubApi>, sender: Sender<GlomEvent>, config: WebhookConfig) -> Self {
+        Self {
+            api,
+            sender,
+            config,
+            activity: Arc::new(WebhookActivity::default()),
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Shared handle for observing webhook activity, e.g. from the poller
+    pub fn activity(&self) -> Arc<WebhookActivity> {
+        self.activity.clone()
+    }
+
+    /// Bind the listener and serve deliveries until the task is dropped
+    #[instrument(skip(self), fields(listen = %self.config.listen))]
+    pub async fn start(self) -> Result<()> {
+        let addr: SocketAddr = self.config.listen.parse().map_err(|_| {
+            ClientError::config_validation("webhook_listen", "not a valid socket address")
+        })?;
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| ClientError::config(format!("Failed to bind {addr}: {e}")))?;
+
+        info!(%addr, "Webhook receiver listening");
+
+        let receiver = Arc::new(self);
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!(error = %e, "Failed to accept webhook connection");
+                    continue;
+                },
+            };
+
+            let receiver = receiver.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req| receiver.clone().handle(req));
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!(%peer, error = %e, "Webhook connection closed with error");
+                }
+            });
+        }
+    }
+
+    async fn handle(
+        self: Arc<Self>,
+        req: Request<Incoming>,
+    ) -> std::result::Result<Response<Full<Bytes>>, Infallible> {
+        if req.method() != Method::POST {
+            return Ok(reply(StatusCode::METHOD_NOT_ALLOWED, "expected POST"));
+        }
+
+        let event = header(&req, "X-GitHub-Event");
+        let signature = header(&req, "X-Hub-Signature-256");
+
+        let body = match Limited::new(req.into_body(), MAX_PAYLOAD_BYTES)
+            .collect()
+            .await
+        {
+            Ok(body) => body.to_bytes(),
+            Err(_) => return Ok(reply(StatusCode::PAYLOAD_TOO_LARGE, "payload too large")),
+        };
+
+        if !verify_signature(&self.config.secret, signature.as_deref(), &body) {
+            warn!(event = ?event, "Rejected webhook delivery with invalid signature");
+            return Ok(reply(StatusCode::UNAUTHORIZED, "invalid signature"));
+        }
+
+        self.activity.record_delivery();
+
+        let outcome = match event.as_deref() {
+            Some("workflow_run") => match parse::<WorkflowRunEventDto>(&body) {
+                Ok(payload) => {
+                    self.on_workflow_run(payload);
+                    Ok(())
+                },
+                Err(e) => Err(e),
+            },
+            Some("workflow_job") => match parse::<WorkflowJobEventDto>(&body) {
+                Ok(payload) => {
+                    self.on_workflow_job(payload).await;
+                    Ok(())
+                },
+                Err(e) => Err(e),
+            },
+            Some("ping") => {
+                info!("Received webhook ping");
+                Ok(())
+            },
+            other => {
+                debug!(event = ?other, "Ignoring unsupported webhook event");
+                Ok(())
+            },
+        };
+
+        match outcome {
+            Ok(()) => Ok(reply(StatusCode::ACCEPTED, "accepted")),
+            Err(e) => {
+                warn!(error = %e, "Failed to parse webhook payload");
+                Ok(reply(StatusCode::BAD_REQUEST, "malformed payload"))
+            },
+        }
+    }
+
+    fn on_workflow_run(&self, payload: WorkflowRunEventDto) {
+        let mut run = payload.workflow_run;
+        run.project_id = ProjectId::new(payload.repository.full_name);
+        run.delivered = true;
+        debug!(
+            project_id = %run.project_id,
+            pipeline_id = %run.id,
+            action = %payload.action,
+            "Received workflow_run delivery"
+        );
+
+        if payload.action == "completed" {
+            self.jobs.lock().unwrap().remove(&run.id);
+        }
+
+        if !self
+            .api
+            .config()
+            .run_filter(&run.project_id)
+            .matches(&run)
+        {
+            debug!(pipeline_id = %run.id, "Run excluded by the project's run filter");
+            return;
+        }
+
+        self.sender.dispatch(vec![run].into_glom_event());
+    }
+
+    async fn on_workflow_job(&self, payload: WorkflowJobEventDto) {
+        let project_id = ProjectId::new(payload.repository.full_name);
+        let job = payload.workflow_job;
+        let pipeline_id = job.run_id;
+        debug!(
+            project_id = %project_id,
+            pipeline_id = %pipeline_id,
+            job_id = %job.id,
+            action = %payload.action,
+            "Received workflow_job delivery"
+        );
+
+        // a single job payload would replace the run's job list, so the run's first
+        // delivery seeds it from the API; deliveries meanwhile are only merged
+        {
+            let mut cache = self.jobs.lock().unwrap();
+            evict_stale_jobs(&mut cache);
+            if let Some(cached) = cache.get_mut(&pipeline_id) {
+                cached.merge(job);
+                // otherwise dispatched along with the seeded jobs
+                if !cached.seeding {
+                    self.sender
+                        .dispatch((project_id, pipeline_id, cached.jobs.clone()).into_glom_event());
+                }
+                return;
+            }
+
+            let mut cached = CachedJobs::seeding();
+            cached.merge(job);
+            cache.insert(pipeline_id, cached);
+        }
+
+        let seeded = self
+            .api
+            .get_jobs(project_id.clone(), pipeline_id)
+            .await
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to seed jobs for webhook delivery");
+                Vec::new()
+            });
+
+        let jobs = {
+            let mut cache = self.jobs.lock().unwrap();
+            let Some(cached) = cache.get_mut(&pipeline_id) else {
+                debug!(pipeline_id = %pipeline_id, "Run left the job cache while seeding its jobs");
+                return;
+            };
+            cached.seed(seeded);
+            cached.jobs.clone()
+        };
+
+        self.sender
+            .dispatch((project_id, pipeline_id, jobs).into_glom_event());
+    }
+}
+
+/// Drops the jobs of runs without recent deliveries, then the least recently updated
+/// ones beyond the cache's capacity
+fn evict_stale_jobs(cache: &mut HashMap<PipelineId, CachedJobs>) {
+    cache.retain(|_, cached| cached.updated.elapsed() < CACHED_JOBS_TTL);
+
+    let excess = cache.len().saturating_sub(MAX_CACHED_RUNS - 1);
+    if excess > 0 {
+        let evicted: Vec<PipelineId> = cache
+            .iter()
+            .sorted_by_key(|(_, cached)| cached.updated)
+            .take(excess)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in evicted {
+            cache.remove(&id);
+        }
+    }
+}
+
+/// Validate a `sha256=<hex>` signature against the HMAC of the payload
+fn verify_signature(secret: &str, signature: Option<&str>, payload: &[u8]) -> bool {
+    if secret.trim().is_empty() {
+        return false;
+    }
+
+    let Some(digest) = signature
+        .and_then(|s| s.strip_prefix("sha256="))
+        .and_then(|hex_digest| hex::decode(hex_digest).ok())
+    else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(payload);
+    mac.verify_slice(&digest).is_ok()
+}
+
+fn parse<T: DeserializeOwned>(body: &[u8]) -> Result<T> {
+    serde_json::from_slice(body)
+        .map_err(|e| ClientError::json_parse("webhook", "Failed to parse payload", e))
+}
+
+fn header(req: &Request<Incoming>, name: &str) -> Option<String> {
+    req.headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+fn reply(status: StatusCode, message: &'static str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from_static(message.as_bytes())));
+    *response.status_mut() = status;
+    response
+}
@@ -1,11 +1,13 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Duration, Local, Utc};
-use compact_str::{CompactString, ToCompactString};
+use compact_str::{CompactString, ToCompactString, format_compact};
 use itertools::Itertools;
 use ratatui::{
     text::{Line, Span, Text},
     widgets::Row,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, de::IntoDeserializer};
 
 use crate::{
     id::{JobId, PipelineId, ProjectId},
@@ -13,7 +15,10 @@ use crate::{
     ui::{format_duration, widget::text_from},
 };
 
-#[derive(Clone, Debug)]
+/// Upper bound of pipelines retained per project
+const MAX_PIPELINES_PER_PROJECT: usize = 100;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Project {
     pub id: ProjectId,
     pub path: CompactString,
@@ -23,13 +28,22 @@ pub struct Project {
     pub url: CompactString,
     pub last_activity_at: DateTime<Utc>,
     pub pipelines: Option<Vec<Pipeline>>,
+    // statistics are not persisted, so they are requested again in each session
+    #[serde(skip)]
     pub commit_count: u32,
+    #[serde(skip)]
     pub repo_size_kb: u64,
+    #[serde(skip)]
     pub artifacts_size_kb: u64,
+    /// Size of the active Actions caches
+    #[serde(skip)]
+    pub cache_size_kb: u64,
+    pub repository: RepositoryInfo,
+    #[serde(skip)]
     pub statistics_loading: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Pipeline {
     pub id: PipelineId,
     pub project_id: ProjectId,
@@ -39,31 +53,122 @@ pub struct Pipeline {
     pub source: PipelineSource,
     pub branch: CompactString,
     pub url: CompactString,
+    pub head_sha: CompactString,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub jobs: Option<Vec<Job>>,
     pub commit: Option<Commit>,
+    /// Billable time, loaded from the run timing once the run has completed
+    pub billable: Option<BillableTime>,
+    /// Commit statuses and third-party check runs reported for the head commit
+    pub checks: Option<CommitChecks>,
+    /// Last updated by a webhook delivery rather than by listing the runs
+    #[serde(default)]
+    pub delivered: bool,
+}
+
+/// Billable milliseconds of a workflow run per runner OS, e.g. `UBUNTU`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BillableTime {
+    pub by_os: BTreeMap<CompactString, u64>,
+}
+
+impl BillableTime {
+    pub fn total(&self) -> Duration {
+        Duration::milliseconds(self.by_os.values().sum::<u64>() as i64)
+    }
+}
+
+/// Checks reported for a commit, e.g. by external CI through commit statuses or by
+/// third-party apps through check runs
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CommitChecks {
+    pub checks: Vec<CommitCheck>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommitCheck {
+    /// Context of a commit status, or name of a check run
+    pub name: CompactString,
+    pub status: PipelineStatus,
 }
 
-#[derive(Clone, Debug)]
+impl CommitChecks {
+    /// Merges commit statuses and check runs; the check runs of GitHub Actions are
+    /// only included if `include_actions` is set, as workflow runs already represent them
+    pub fn new(
+        combined: CombinedStatusDto,
+        check_runs: Vec<CheckRunDto>,
+        include_actions: bool,
+    ) -> Self {
+        let statuses = combined
+            .statuses
+            .into_iter()
+            .map(|s| CommitCheck {
+                status: match s.state.as_str() {
+                    "success" => PipelineStatus::Success,
+                    "pending" => PipelineStatus::Pending,
+                    _ => PipelineStatus::Failure,
+                },
+                name: s.context,
+            });
+
+        let check_runs = check_runs
+            .into_iter()
+            .filter(|c| {
+                include_actions
+                    || c.app
+                        .as_ref()
+                        .is_none_or(|app| app.slug != "github-actions")
+            })
+            .map(|c| CommitCheck {
+                status: PipelineStatus::resolve(c.status, c.conclusion),
+                name: c.name,
+            });
+
+        Self { checks: statuses.chain(check_runs).collect() }
+    }
+
+    /// The most severe status among the checks, if any
+    pub fn status(&self) -> Option<PipelineStatus> {
+        self.checks
+            .iter()
+            .map(|c| c.status.clone())
+            .max_by_key(PipelineStatus::severity)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.checks.iter().any(|c| c.status.is_active())
+    }
+
+    pub fn failed_check(&self) -> Option<&CommitCheck> {
+        self.checks.iter().find(|c| c.status.is_failure())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Commit {
     pub title: CompactString,
     #[allow(dead_code)]
     pub author_name: CompactString,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Job {
     pub id: JobId,
     pub name: CompactString,
     pub status: PipelineStatus,
     #[allow(dead_code)]
     pub stage: CompactString,
-    #[allow(dead_code)]
     pub created_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
     pub finished_at: Option<DateTime<Utc>>,
     pub url: CompactString,
+    /// Runner the job was assigned to
+    pub runner_name: Option<CompactString>,
+    /// Labels of the runners the job can run on, e.g. `ubuntu-latest`
+    #[serde(default)]
+    pub labels: Vec<CompactString>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -84,8 +189,79 @@ fn default_ssh_url() -> CompactString {
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct StatisticsDto {
     pub commit_count: u32,
+    /// Branch the commit count refers to
+    pub commit_branch: CompactString,
     pub job_artifacts_size: u64,
     pub repository_size: u64,
+    pub cache_size: u64,
+    pub repository: RepositoryInfo,
+}
+
+/// Repository metadata shown in the project details popup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepositoryInfo {
+    /// Primary language as reported by GitHub
+    pub language: Option<CompactString>,
+    /// Bytes of code per language, largest first
+    pub languages: Vec<(CompactString, u64)>,
+    pub stars: u32,
+    pub forks: u32,
+    pub watchers: u32,
+    pub open_issues: u32,
+    pub open_pull_requests: u32,
+    pub topics: Vec<CompactString>,
+    pub license: Option<CompactString>,
+    pub visibility: CompactString,
+    pub archived: bool,
+    pub fork: bool,
+}
+
+impl RepositoryInfo {
+    pub fn new(
+        details: RepositoryDetailsDto,
+        languages: Vec<(CompactString, u64)>,
+        open_pull_requests: u32,
+    ) -> Self {
+        let license = details.license.map(|l| match l.spdx_id {
+            Some(id) if id != "NOASSERTION" => id,
+            _ => l.name,
+        });
+
+        Self {
+            language: details.language,
+            languages,
+            stars: details.stargazers_count,
+            forks: details.forks_count,
+            watchers: details.subscribers_count,
+            // GitHub counts pull requests as issues
+            open_issues: details
+                .open_issues_count
+                .saturating_sub(open_pull_requests),
+            open_pull_requests,
+            topics: details.topics,
+            license,
+            visibility: details.visibility,
+            archived: details.archived,
+            fork: details.fork,
+        }
+    }
+
+    /// Share of each language in percent, largest first
+    pub fn language_shares(&self) -> Vec<(&str, f64)> {
+        let total: u64 = self
+            .languages
+            .iter()
+            .map(|(_, bytes)| bytes)
+            .sum();
+        if total == 0 {
+            return Vec::new();
+        }
+
+        self.languages
+            .iter()
+            .map(|(name, bytes)| (name.as_str(), *bytes as f64 * 100.0 / total as f64))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -102,14 +278,395 @@ pub struct CommitDto {
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct JobDto {
     pub id: JobId,
+    #[serde(default)]
+    pub run_id: PipelineId,
+    #[serde(default)]
+    pub run_attempt: u32,
+    #[serde(default)]
+    pub head_sha: CompactString,
     pub name: CompactString,
     #[serde(skip)]
     pub commit: CommitDto,
     pub status: PipelineStatus,
+    pub conclusion: Option<PipelineStatus>,
     pub created_at: DateTime<Utc>,
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub html_url: CompactString,
+    #[serde(default)]
+    pub runner_name: Option<CompactString>,
+    #[serde(default)]
+    pub labels: Vec<CompactString>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RunTimingDto {
+    /// Billable time per runner OS; empty for runs not billed, e.g. in public repositories
+    #[serde(default)]
+    pub billable: BTreeMap<CompactString, BillableDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BillableDto {
+    pub total_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GitHubCachesResponse {
+    pub total_count: u32,
+    pub actions_caches: Vec<CacheDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CacheDto {
+    pub id: u64,
+    #[serde(rename = "ref")]
+    pub git_ref: CompactString,
+    pub key: CompactString,
+    pub last_accessed_at: DateTime<Utc>,
+    pub size_in_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CacheUsageDto {
+    pub active_caches_size_in_bytes: u64,
+    pub active_caches_count: u32,
+}
+
+/// Caches to delete from a repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheDeletion {
+    Id(u64),
+    /// All caches with a key starting with the prefix
+    KeyPrefix(CompactString),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GitHubRunnersResponse {
+    pub runners: Vec<RunnerDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RunnerDto {
+    pub name: CompactString,
+    pub os: CompactString,
+    /// `online` or `offline`
+    pub status: CompactString,
+    pub busy: bool,
+    #[serde(default)]
+    pub labels: Vec<RunnerLabelDto>,
+    /// Whether the runner is registered with the repository or its organization
+    #[serde(skip)]
+    pub scope: RunnerScope,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RunnerLabelDto {
+    pub name: CompactString,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RunnerScope {
+    #[default]
+    Repository,
+    Organization,
+}
+
+impl RunnerScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RunnerScope::Repository => "repo",
+            RunnerScope::Organization => "org",
+        }
+    }
+}
+
+impl RunnerDto {
+    pub fn is_online(&self) -> bool {
+        self.status == "online"
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PullRequestDto {
+    pub number: u64,
+    pub title: CompactString,
+    pub user: Option<ActorDto>,
+    #[serde(default)]
+    pub draft: bool,
+    pub html_url: CompactString,
+    pub head: PullRequestHeadDto,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PullRequestHeadDto {
+    #[serde(rename = "ref")]
+    pub git_ref: CompactString,
+    pub sha: CompactString,
+}
+
+/// Fields only included when fetching a single pull request
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PullRequestDetailsDto {
+    /// e.g. `clean`, `blocked`, `behind`, `dirty` or `unstable`; `unknown` while
+    /// GitHub computes it in the background
+    #[serde(default)]
+    pub mergeable_state: CompactString,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PullRequestReviewDto {
+    pub user: Option<ActorDto>,
+    /// e.g. `APPROVED`, `CHANGES_REQUESTED` or `COMMENTED`
+    pub state: CompactString,
+}
+
+/// Open pull request, along with its review decision, mergeability and checks
+#[derive(Debug, Clone, Default)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: CompactString,
+    pub author: CompactString,
+    pub draft: bool,
+    pub url: CompactString,
+    pub head_ref: CompactString,
+    pub head_sha: CompactString,
+    pub updated_at: DateTime<Utc>,
+    pub review: ReviewDecision,
+    pub mergeable_state: CompactString,
+    /// All checks of the head commit, including those of GitHub Actions
+    pub checks: CommitChecks,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReviewDecision {
+    #[default]
+    ReviewRequired,
+    Approved,
+    ChangesRequested,
+    /// The reviews could not be fetched
+    Unknown,
+}
+
+impl ReviewDecision {
+    /// Derives the decision from the latest approving or blocking review of each reviewer
+    pub fn from_reviews(reviews: &[PullRequestReviewDto]) -> Self {
+        let latest = reviews
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.state.as_str(),
+                    "APPROVED" | "CHANGES_REQUESTED" | "DISMISSED"
+                )
+            })
+            .filter_map(|r| {
+                r.user
+                    .as_ref()
+                    .map(|u| (&u.login, r.state.as_str()))
+            })
+            .rev()
+            .unique_by(|(login, _)| *login)
+            .map(|(_, state)| state)
+            .collect_vec();
+
+        match () {
+            _ if latest.contains(&"CHANGES_REQUESTED") => ReviewDecision::ChangesRequested,
+            _ if latest.contains(&"APPROVED") => ReviewDecision::Approved,
+            _ => ReviewDecision::ReviewRequired,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewDecision::ReviewRequired => "review required",
+            ReviewDecision::Approved => "approved",
+            ReviewDecision::ChangesRequested => "changes requested",
+            ReviewDecision::Unknown => "unknown",
+        }
+    }
+}
+
+impl PullRequest {
+    /// Details and reviews are `None` if they could not be fetched, leaving the
+    /// mergeable state and review decision unknown
+    pub fn new(
+        pr: PullRequestDto,
+        details: Option<PullRequestDetailsDto>,
+        reviews: Option<&[PullRequestReviewDto]>,
+        checks: CommitChecks,
+    ) -> Self {
+        Self {
+            number: pr.number,
+            title: pr.title,
+            author: pr.user.map(|u| u.login).unwrap_or_default(),
+            draft: pr.draft,
+            url: pr.html_url,
+            head_ref: pr.head.git_ref,
+            head_sha: pr.head.sha,
+            updated_at: pr.updated_at,
+            review: reviews.map_or(ReviewDecision::Unknown, ReviewDecision::from_reviews),
+            mergeable_state: details.map_or_else(|| "unknown".into(), |d| d.mergeable_state),
+            checks,
+        }
+    }
+}
+
+/// Combined commit status, as reported through the commit statuses API
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CombinedStatusDto {
+    pub statuses: Vec<CommitStatusDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CommitStatusDto {
+    pub context: CompactString,
+    /// `error`, `failure`, `pending` or `success`
+    pub state: CompactString,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GitHubCheckRunsResponse {
+    pub check_runs: Vec<CheckRunDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CheckRunDto {
+    pub name: CompactString,
+    pub status: PipelineStatus,
+    pub conclusion: Option<PipelineStatus>,
+    pub app: Option<CheckRunAppDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CheckRunAppDto {
+    pub slug: CompactString,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GitHubEnvironmentsResponse {
+    pub environments: Vec<EnvironmentDto>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EnvironmentDto {
+    pub name: CompactString,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeploymentDto {
+    pub id: u64,
+    #[serde(rename = "ref")]
+    pub git_ref: CompactString,
+    pub sha: CompactString,
+    pub creator: Option<ActorDto>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DeploymentStatusDto {
+    pub state: DeploymentState,
+    /// Logs of the deployment; the job's page for deployments made by Actions
+    pub log_url: Option<CompactString>,
+    pub target_url: Option<CompactString>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeploymentState {
+    Error,
+    Failure,
+    Inactive,
+    InProgress,
+    Queued,
+    #[default]
+    Pending,
+    Success,
+    #[serde(other)]
+    Unknown,
+}
+
+impl DeploymentState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeploymentState::Error => "error",
+            DeploymentState::Failure => "failure",
+            DeploymentState::Inactive => "inactive",
+            DeploymentState::InProgress => "in progress",
+            DeploymentState::Queued => "queued",
+            DeploymentState::Pending => "pending",
+            DeploymentState::Success => "success",
+            DeploymentState::Unknown => "unknown",
+        }
+    }
+}
+
+/// Latest deployment of an environment, along with its latest status
+#[derive(Debug, Clone, Default)]
+pub struct EnvironmentDeployment {
+    pub environment: CompactString,
+    /// `None` if nothing has been deployed to the environment yet
+    pub deployment: Option<DeploymentDto>,
+    /// `None` if the deployment has not reported any status
+    pub status: Option<DeploymentStatusDto>,
+}
+
+/// Condition of an environment, judged by its latest deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentHealth {
+    Healthy,
+    Failed,
+    /// Queued or in progress for longer than expected
+    Stuck,
+    /// Queued or in progress
+    Deploying,
+    /// Nothing deployed, or no status reported
+    Unknown,
+}
+
+impl EnvironmentDeployment {
+    /// Deployments pending for longer than this are considered stuck
+    const STUCK_AFTER: Duration = Duration::minutes(30);
+
+    pub fn health(&self, now: DateTime<Utc>) -> DeploymentHealth {
+        let Some(status) = &self.status else {
+            return DeploymentHealth::Unknown;
+        };
+
+        match status.state {
+            DeploymentState::Success | DeploymentState::Inactive => DeploymentHealth::Healthy,
+            DeploymentState::Error | DeploymentState::Failure => DeploymentHealth::Failed,
+            DeploymentState::InProgress | DeploymentState::Queued | DeploymentState::Pending => {
+                if now - status.created_at > Self::STUCK_AFTER {
+                    DeploymentHealth::Stuck
+                } else {
+                    DeploymentHealth::Deploying
+                }
+            },
+            DeploymentState::Unknown => DeploymentHealth::Unknown,
+        }
+    }
+
+    /// Page of the workflow run, or job, that made the deployment
+    pub fn run_url(&self) -> Option<&str> {
+        let status = self.status.as_ref()?;
+        [&status.log_url, &status.target_url]
+            .into_iter()
+            .flatten()
+            .map(CompactString::as_str)
+            .find(|url| url.contains("/actions/runs/"))
+    }
+
+    /// Id of the workflow run that made the deployment
+    pub fn run_id(&self) -> Option<PipelineId> {
+        let (_, path) = self.run_url()?.split_once("/actions/runs/")?;
+        path.split('/')
+            .next()?
+            .parse()
+            .ok()
+            .map(PipelineId::new)
+    }
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -121,6 +678,33 @@ pub struct GitHubJobsResponse {
 pub struct RepositoryDetailsDto {
     /// Repository size in KB
     pub size: u64,
+    pub default_branch: CompactString,
+    pub language: Option<CompactString>,
+    #[serde(default)]
+    pub stargazers_count: u32,
+    #[serde(default)]
+    pub forks_count: u32,
+    /// Users watching the repository; `watchers_count` mirrors the stars
+    #[serde(default)]
+    pub subscribers_count: u32,
+    /// Open issues, including pull requests
+    #[serde(default)]
+    pub open_issues_count: u32,
+    #[serde(default)]
+    pub topics: Vec<CompactString>,
+    pub license: Option<LicenseDto>,
+    #[serde(default)]
+    pub visibility: CompactString,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LicenseDto {
+    pub spdx_id: Option<CompactString>,
+    pub name: CompactString,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -133,11 +717,6 @@ pub struct ArtifactDto {
     pub size_in_bytes: u64,
 }
 
-#[derive(Debug, Clone, Default, Deserialize)]
-pub struct ContributorDto {
-    pub contributions: u32,
-}
-
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct PipelineDto {
     pub id: PipelineId,
@@ -145,11 +724,28 @@ pub struct PipelineDto {
     pub project_id: ProjectId,
     pub name: CompactString,
     pub status: PipelineStatus,
+    pub conclusion: Option<PipelineStatus>,
     pub event: PipelineSource,
     pub head_branch: Option<CompactString>,
+    #[serde(default)]
+    pub head_sha: CompactString,
+    pub actor: Option<ActorDto>,
     pub html_url: CompactString,
+    #[serde(default)]
+    pub run_attempt: u32,
+    /// Start of the latest attempt
+    #[serde(default)]
+    pub run_started_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Received through a webhook delivery rather than by listing the runs
+    #[serde(skip)]
+    pub delivered: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ActorDto {
+    pub login: CompactString,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -157,7 +753,29 @@ pub struct GitHubWorkflowRunsResponse {
     pub workflow_runs: Vec<PipelineDto>,
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Deserialize)]
+/// Repository reference included in webhook payloads
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WebhookRepositoryDto {
+    pub full_name: CompactString,
+}
+
+/// Payload of the `workflow_run` webhook event
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorkflowRunEventDto {
+    pub action: CompactString,
+    pub workflow_run: PipelineDto,
+    pub repository: WebhookRepositoryDto,
+}
+
+/// Payload of the `workflow_job` webhook event
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct WorkflowJobEventDto {
+    pub action: CompactString,
+    pub workflow_job: JobDto,
+    pub repository: WebhookRepositoryDto,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PipelineStatus {
     #[default]
@@ -173,11 +791,14 @@ pub enum PipelineStatus {
     Stale,
     Success,
     TimedOut,
+    Requested,
+    Waiting,
+    Pending,
     #[serde(other)]
     Unknown,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum PipelineSource {
     #[default]
@@ -256,12 +877,79 @@ impl PipelineStatus {
     pub(crate) fn is_active(&self) -> bool {
         matches!(
             self,
-            PipelineStatus::Queued | PipelineStatus::InProgress | PipelineStatus::ActionRequired
+            PipelineStatus::Queued
+                | PipelineStatus::InProgress
+                | PipelineStatus::ActionRequired
+                | PipelineStatus::Requested
+                | PipelineStatus::Waiting
+                | PipelineStatus::Pending
         )
     }
+
+    pub(crate) fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            PipelineStatus::Failure | PipelineStatus::TimedOut | PipelineStatus::Cancelled
+        )
+    }
+
+    /// Ranks statuses when merging them into one; failures outrank pending
+    /// statuses, which outrank successful ones
+    pub(crate) fn severity(&self) -> u8 {
+        match self {
+            _ if self.is_failure() => 3,
+            _ if self.is_active() => 2,
+            PipelineStatus::Unknown => 0,
+            _ => 1,
+        }
+    }
+
+    /// Resolves the effective status of a run or job; GitHub reports the outcome of
+    /// completed runs in a separate `conclusion` field.
+    pub(crate) fn resolve(status: PipelineStatus, conclusion: Option<PipelineStatus>) -> Self {
+        match (status, conclusion) {
+            (PipelineStatus::Completed, Some(conclusion)) => conclusion,
+            (status, _) => status,
+        }
+    }
+
+    /// Maps a status name as used by the GitHub API, e.g. `timed_out`, to its status
+    pub fn from_api_str(status: &str) -> PipelineStatus {
+        let deserializer: serde::de::value::StrDeserializer<'_, serde::de::value::Error> =
+            status.into_deserializer();
+        PipelineStatus::deserialize(deserializer).unwrap_or(PipelineStatus::Unknown)
+    }
+
+    /// Name of the status as used by the GitHub API
+    pub fn as_api_str(&self) -> &'static str {
+        match self {
+            PipelineStatus::Queued => "queued",
+            PipelineStatus::InProgress => "in_progress",
+            PipelineStatus::Completed => "completed",
+            PipelineStatus::ActionRequired => "action_required",
+            PipelineStatus::Cancelled => "cancelled",
+            PipelineStatus::Failure => "failure",
+            PipelineStatus::Neutral => "neutral",
+            PipelineStatus::Skipped => "skipped",
+            PipelineStatus::Stale => "stale",
+            PipelineStatus::Success => "success",
+            PipelineStatus::TimedOut => "timed_out",
+            PipelineStatus::Requested => "requested",
+            PipelineStatus::Waiting => "waiting",
+            PipelineStatus::Pending => "pending",
+            PipelineStatus::Unknown => "unknown",
+        }
+    }
 }
 
 impl PipelineSource {
+    /// Maps a GitHub event name, e.g. `pull_request`, to its pipeline source
+    pub fn from_event(event: &str) -> PipelineSource {
+        let deserializer: serde::de::value::StrDeserializer<'_, serde::de::value::Error> =
+            event.into_deserializer();
+        PipelineSource::deserialize(deserializer).unwrap_or(PipelineSource::Unknown)
+    }
+
     pub(crate) fn is_interesting(&self) -> bool {
         matches!(
             self,
@@ -288,6 +976,15 @@ impl Project {
         self.last_activity_at
     }
 
+    /// Whether the path or description contains the lowercase filter, ignoring case
+    pub fn matches_filter(&self, filter_lower: &str) -> bool {
+        self.path.to_lowercase().contains(filter_lower)
+            || self
+                .description
+                .as_ref()
+                .is_some_and(|d| d.to_lowercase().contains(filter_lower))
+    }
+
     pub fn title(&self) -> CompactString {
         match self.path.rfind('/') {
             Some(i) => self.path[i + 1..].into(),
@@ -359,6 +1056,8 @@ impl From<ProjectDto> for Project {
             commit_count: 0,
             repo_size_kb: 0,
             artifacts_size_kb: 0,
+            cache_size_kb: 0,
+            repository: RepositoryInfo::default(),
             statistics_loading: false,
         }
     }
@@ -372,28 +1071,60 @@ impl Job {
             _ => Duration::zero(),
         }
     }
+
+    /// Time the job waited for a runner, up to now if it is still queued; `None` if
+    /// it never started, e.g. when skipped or cancelled while queued
+    pub fn queue_time(&self) -> Option<Duration> {
+        let queued_until = match self.started_at {
+            Some(started_at) => started_at,
+            None if self.status.is_active() => Utc::now(),
+            None => return None,
+        };
+
+        Some(
+            queued_until
+                .signed_duration_since(self.created_at)
+                .max(Duration::zero()),
+        )
+    }
 }
 
 impl Project {
+    /// Updates the pipelines of the project. Runs delivered through webhooks are merged
+    /// into the loaded runs, while a listing replaces them, keeping only the delivered
+    /// runs it does not include yet, unless they are older than the listing.
     pub fn update_pipelines(&mut self, pipelines: Vec<Pipeline>) {
+        let existing = self.pipelines.take().unwrap_or_default();
+        let delivery = pipelines.iter().all(|p| p.delivered);
+        let oldest_listed = pipelines.iter().map(|p| p.created_at).min();
+        let retained: Vec<Pipeline> = existing
+            .iter()
+            .filter(|ep| !pipelines.iter().any(|p| p.id == ep.id))
+            .filter(|ep| {
+                delivery
+                    || (ep.delivered && oldest_listed.is_none_or(|oldest| ep.created_at >= oldest))
+            })
+            .cloned()
+            .collect();
+
         self.pipelines = Some(
             pipelines
                 .iter()
                 .map(|p| {
-                    if let Some(existing) = self
-                        .pipelines
-                        .as_ref()
-                        .and_then(|ps| ps.iter().find(|ep| ep.id == p.id))
-                    {
+                    if let Some(existing) = existing.iter().find(|ep| ep.id == p.id) {
                         let mut new = p.clone();
                         new.jobs.clone_from(&existing.jobs);
                         new.commit.clone_from(&existing.commit);
+                        new.billable.clone_from(&existing.billable);
+                        new.checks.clone_from(&existing.checks);
                         new
                     } else {
                         p.clone()
                     }
                 })
+                .chain(retained)
                 .sorted_by(|a, b| b.updated_at.cmp(&a.updated_at))
+                .take(MAX_PIPELINES_PER_PROJECT)
                 .collect(),
         );
     }
@@ -407,6 +1138,98 @@ impl Project {
         self.last_activity_at = project.last_activity_at;
     }
 
+    /// Active job currently assigned to the given runner, along with its run
+    pub fn job_on_runner(&self, runner_name: &str) -> Option<(&Pipeline, &Job)> {
+        self.pipelines
+            .iter()
+            .flatten()
+            .filter(|p| p.status.is_active())
+            .find_map(|p| {
+                p.jobs
+                    .iter()
+                    .flatten()
+                    .find(|j| j.status.is_active() && j.runner_name.as_deref() == Some(runner_name))
+                    .map(|j| (p, j))
+            })
+    }
+
+    pub fn update_billable(&mut self, pipeline_id: PipelineId, billable: BillableTime) {
+        if let Some(pipelines) = self.pipelines.as_mut()
+            && let Some(pipeline) = pipelines.iter_mut().find(|p| p.id == pipeline_id)
+        {
+            pipeline.billable = Some(billable);
+        }
+    }
+
+    /// Average queue time of the loaded jobs per runner label, by label
+    pub fn queue_time_by_label(&self) -> Vec<(CompactString, Duration)> {
+        let mut by_label: BTreeMap<CompactString, (Duration, i32)> = BTreeMap::new();
+        let jobs = self
+            .pipelines
+            .iter()
+            .flatten()
+            .flat_map(|p| p.jobs.iter().flatten());
+
+        for job in jobs {
+            let Some(queue_time) = job.queue_time() else {
+                continue;
+            };
+            for label in &job.labels {
+                let (total, count) = by_label.entry(label.clone()).or_default();
+                *total += queue_time;
+                *count += 1;
+            }
+        }
+
+        by_label
+            .into_iter()
+            .map(|(label, (total, count))| (label, total / count))
+            .collect()
+    }
+
+    /// Billable time summed per runner OS over the runs with loaded timing,
+    /// along with the number of runs included
+    pub fn billable_summary(&self) -> (usize, BillableTime) {
+        let runs: Vec<&BillableTime> = self
+            .pipelines
+            .iter()
+            .flatten()
+            .filter_map(|p| p.billable.as_ref())
+            .collect();
+
+        let mut summary = BillableTime::default();
+        for (os, ms) in runs.iter().flat_map(|b| b.by_os.iter()) {
+            *summary.by_os.entry(os.clone()).or_default() += ms;
+        }
+
+        (runs.len(), summary)
+    }
+
+    /// Runs at the head of each branch whose commit checks are missing or may
+    /// still change
+    pub fn branch_heads_to_check(&self) -> Vec<&Pipeline> {
+        self.first_pipeline_per_branch(3, |p| p.status.is_active())
+            .into_iter()
+            .filter(|p| !p.head_sha.is_empty())
+            .filter(|p| {
+                p.status.is_active()
+                    || p.checks
+                        .as_ref()
+                        .is_none_or(CommitChecks::is_pending)
+            })
+            .unique_by(|p| &p.head_sha)
+            .collect()
+    }
+
+    /// Updates the commit checks of all runs of the given commit
+    pub fn update_checks(&mut self, head_sha: &str, checks: CommitChecks) {
+        self.pipelines
+            .iter_mut()
+            .flatten()
+            .filter(|p| p.head_sha == head_sha)
+            .for_each(|p| p.checks = Some(checks.clone()));
+    }
+
     pub fn update_jobs(&mut self, pipeline_id: PipelineId, jobs: Vec<Job>) {
         if let Some(pipelines) = self.pipelines.as_mut()
             && let Some(pipeline) = pipelines.iter_mut().find(|p| p.id == pipeline_id)
@@ -430,14 +1253,30 @@ impl From<PipelineDto> for Pipeline {
             id: p.id,
             project_id: p.project_id,
             name: p.name,
-            status: p.status,
+            status: PipelineStatus::resolve(p.status, p.conclusion),
             source: p.event,
             branch: p.head_branch.unwrap_or_else(|| "unknown".into()),
             url: p.html_url,
+            head_sha: p.head_sha,
             created_at: p.created_at,
             updated_at: p.updated_at,
             jobs: None,
             commit: None,
+            billable: None,
+            checks: None,
+            delivered: p.delivered,
+        }
+    }
+}
+
+impl From<RunTimingDto> for BillableTime {
+    fn from(t: RunTimingDto) -> Self {
+        Self {
+            by_os: t
+                .billable
+                .into_iter()
+                .map(|(os, b)| (os, b.total_ms))
+                .collect(),
         }
     }
 }
@@ -448,11 +1287,13 @@ impl From<JobDto> for Job {
             id: j.id,
             name: j.name,
             stage: "job".into(),
-            status: j.status,
+            status: PipelineStatus::resolve(j.status, j.conclusion),
             created_at: j.created_at,
             started_at: j.started_at,
             finished_at: j.completed_at,
             url: j.html_url,
+            runner_name: j.runner_name,
+            labels: j.labels,
         }
     }
 }
@@ -464,6 +1305,24 @@ impl From<CommitDto> for Commit {
 }
 
 impl Pipeline {
+    /// Total time the run's jobs waited for runners
+    pub fn queue_time(&self) -> Option<Duration> {
+        self.jobs
+            .as_ref()?
+            .iter()
+            .filter_map(Job::queue_time)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Job which waited the longest for a runner, along with its queue time
+    pub fn longest_queued_job(&self) -> Option<(&Job, Duration)> {
+        self.jobs
+            .as_ref()?
+            .iter()
+            .filter_map(|j| j.queue_time().map(|t| (j, t)))
+            .max_by_key(|(_, t)| *t)
+    }
+
     pub fn has_active_jobs(&self) -> bool {
         self.jobs
             .as_ref()
@@ -493,8 +1352,29 @@ impl Pipeline {
         self.failed_job().is_some()
     }
 
+    /// Name of the failed job, or else of the failed external check of the head commit
     pub fn failing_job_name(&self) -> Option<CompactString> {
-        self.failed_job().map(|j| j.name.clone())
+        self.failed_job()
+            .map(|j| j.name.clone())
+            .or_else(|| {
+                self.checks
+                    .as_ref()
+                    .and_then(CommitChecks::failed_check)
+                    .map(|c| c.name.clone())
+            })
+    }
+
+    /// Status of the run merged with the external checks of its head commit, so that
+    /// a successful run does not hide e.g. a failed quality gate
+    pub fn combined_status(&self) -> PipelineStatus {
+        match self
+            .checks
+            .as_ref()
+            .and_then(CommitChecks::status)
+        {
+            Some(checks) if checks.severity() > self.status.severity() => checks,
+            _ => self.status.clone(),
+        }
     }
 
     pub fn job(&self, id: JobId) -> Option<&Job> {
@@ -528,7 +1408,7 @@ pub fn parse_row<'a>(project: &'a Project) -> Row<'a> {
     let distinct_by_branch = project.first_pipeline_per_branch(3, |p| p.status.is_active());
 
     let pipeline_to_span = |p: &'a Pipeline| -> Line<'a> {
-        let icon = p.status.icon();
+        let icon = p.combined_status().icon();
         let branch = p.branch.as_str();
 
         let updated_at = p.updated_at.with_timezone(&Local);
@@ -614,6 +1494,9 @@ impl IconRepresentable for PipelineStatus {
             PipelineStatus::Stale => "🟤",
             PipelineStatus::Success => "🟢",
             PipelineStatus::TimedOut => "⏰",
+            PipelineStatus::Requested => "🕒",
+            PipelineStatus::Waiting => "🟡",
+            PipelineStatus::Pending => "🕒",
             PipelineStatus::Unknown => "❓",
         }
         .into()
@@ -628,11 +1511,31 @@ impl IconRepresentable for &Vec<Job> {
     }
 }
 
+impl IconRepresentable for CommitChecks {
+    fn icon(&self) -> CompactString {
+        self.checks
+            .iter()
+            .map(|c| c.status.icon())
+            .collect::<CompactString>()
+    }
+}
+
 impl IconRepresentable for Pipeline {
+    /// Icons of the jobs, followed by those of the external checks of the head commit
     fn icon(&self) -> CompactString {
-        self.jobs
+        let jobs = self
+            .jobs
             .as_ref()
             .map(|jobs| jobs.icon())
-            .unwrap_or(self.status.icon())
+            .unwrap_or(self.status.icon());
+
+        match self
+            .checks
+            .as_ref()
+            .filter(|c| !c.checks.is_empty())
+        {
+            Some(checks) => format_compact!("{jobs} {}", checks.icon()),
+            None => jobs,
+        }
     }
 }
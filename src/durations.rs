@@ -0,0 +1,170 @@
+//! Durations of recent successful runs per workflow, for spotting CI slowdowns
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+use compact_str::CompactString;
+
+use crate::{
+    domain::{Pipeline, PipelineStatus, Project},
+    id::{PipelineId, ProjectId},
+};
+
+/// Percentile of recent durations a run must exceed to raise a notice
+pub const DEFAULT_DURATION_ALERT_PERCENTILE: f64 = 95.0;
+
+/// Successful runs kept per workflow
+const MAX_SAMPLES: usize = 30;
+/// Successful runs required before a run is compared against the others
+const MIN_SAMPLES: usize = 5;
+
+/// Durations of the recent successful runs of a workflow, oldest first
+#[derive(Debug, Clone)]
+pub struct WorkflowDurations {
+    pub workflow: CompactString,
+    pub durations: Vec<Duration>,
+}
+
+/// A successful run taking longer than the configured percentile of its workflow's
+/// recent runs
+#[derive(Debug, Clone)]
+pub struct DurationRegression {
+    pub project_id: ProjectId,
+    pub run_id: PipelineId,
+    pub workflow: CompactString,
+    pub duration: Duration,
+    /// Duration at the configured percentile of the preceding runs
+    pub threshold: Duration,
+    pub percentile: f64,
+}
+
+#[derive(Debug, Clone)]
+struct Sample {
+    run_id: PipelineId,
+    created_at: DateTime<Utc>,
+    duration: Duration,
+}
+
+/// Tracks the durations of successful runs as projects are updated
+pub struct RunDurations {
+    percentile: f64,
+    /// samples per project and workflow, oldest first
+    workflows: HashMap<ProjectId, HashMap<CompactString, VecDeque<Sample>>>,
+    /// runs completed before glom started only seed the samples
+    started_at: DateTime<Utc>,
+}
+
+impl RunDurations {
+    pub fn new(percentile: f64) -> Self {
+        Self {
+            percentile: percentile.clamp(1.0, 100.0),
+            workflows: HashMap::new(),
+            started_at: Utc::now(),
+        }
+    }
+
+    pub fn set_percentile(&mut self, percentile: f64) {
+        self.percentile = percentile.clamp(1.0, 100.0);
+    }
+
+    /// Records the project's newly completed successful runs, returning `None` if
+    /// nothing changed, or those slower than the configured percentile otherwise
+    pub fn record(&mut self, project: &Project) -> Option<Vec<DurationRegression>> {
+        let pipelines = project.pipelines.as_ref()?;
+
+        let workflows = self
+            .workflows
+            .entry(project.id.clone())
+            .or_default();
+
+        let mut changed = false;
+        let mut regressions = Vec::new();
+        let completed = pipelines
+            .iter()
+            .filter(|p| Self::is_completed_success(p))
+            .rev();
+
+        for p in completed {
+            let samples = workflows.entry(p.name.clone()).or_default();
+            let is_known = samples.iter().any(|s| s.run_id == p.id);
+            let is_older = samples.len() == MAX_SAMPLES
+                && samples
+                    .front()
+                    .is_some_and(|s| p.created_at <= s.created_at);
+            if is_known || is_older {
+                continue;
+            }
+
+            let sample = Sample {
+                run_id: p.id,
+                created_at: p.created_at,
+                duration: p.duration(),
+            };
+
+            let is_recent = p.created_at + sample.duration >= self.started_at;
+            if is_recent && samples.len() >= MIN_SAMPLES {
+                let threshold = percentile(samples.iter().map(|s| s.duration), self.percentile);
+                if sample.duration > threshold {
+                    regressions.push(DurationRegression {
+                        project_id: project.id.clone(),
+                        run_id: p.id,
+                        workflow: p.name.clone(),
+                        duration: sample.duration,
+                        threshold,
+                        percentile: self.percentile,
+                    });
+                }
+            }
+
+            // runs may arrive out of order, e.g. from webhooks
+            let idx = samples.partition_point(|s| s.created_at <= sample.created_at);
+            samples.insert(idx, sample);
+            if samples.len() > MAX_SAMPLES {
+                samples.pop_front();
+            }
+            changed = true;
+        }
+
+        changed.then_some(regressions)
+    }
+
+    /// Durations of the project's recent successful runs per workflow, by workflow name
+    pub fn durations(&self, project_id: &ProjectId) -> Vec<WorkflowDurations> {
+        let Some(workflows) = self.workflows.get(project_id) else {
+            return Vec::new();
+        };
+
+        let mut durations: Vec<WorkflowDurations> = workflows
+            .iter()
+            .filter(|(_, samples)| !samples.is_empty())
+            .map(|(workflow, samples)| WorkflowDurations {
+                workflow: workflow.clone(),
+                durations: samples.iter().map(|s| s.duration).collect(),
+            })
+            .collect();
+        durations.sort_by(|a, b| a.workflow.cmp(&b.workflow));
+
+        durations
+    }
+
+    /// Successful runs whose jobs are loaded, as their duration is taken from them
+    fn is_completed_success(p: &Pipeline) -> bool {
+        p.status == PipelineStatus::Success
+            && !p.has_active_jobs()
+            && p.jobs
+                .as_ref()
+                .is_some_and(|jobs| !jobs.is_empty())
+    }
+}
+
+/// Nearest-rank percentile of the durations
+fn percentile(durations: impl Iterator<Item = Duration>, percentile: f64) -> Duration {
+    let mut durations: Vec<Duration> = durations.collect();
+    durations.sort();
+
+    let rank = (percentile / 100.0 * durations.len() as f64).ceil() as usize;
+    durations
+        .get(rank.saturating_sub(1))
+        .copied()
+        .unwrap_or_default()
+}
@@ -61,17 +61,31 @@ use crate::{
 /// proper isolation and lifecycle management.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FxId {
+    /// Actions caches popup dialog effects
+    CachesPopup,
     /// Configuration popup dialog effects
     #[default]
     ConfigPopup,
+    /// Deployment environments popup dialog effects
+    EnvironmentsPopup,
     /// Global screen glitch effects
     Glitch,
+    /// Run history popup dialog effects
+    HistoryPopup,
+    /// Delivery metrics popup dialog effects
+    MetricsPopup,
     /// Notification message effects
     Notification,
     /// Pipeline actions popup dialog effects
     PipelineActionsPopup,
     /// Project details popup dialog effects
     ProjectDetailsPopup,
+    /// Pull requests popup dialog effects
+    PullRequestsPopup,
+    /// Run filter popup dialog effects
+    RunFilterPopup,
+    /// Self-hosted runners popup dialog effects
+    RunnersPopup,
 }
 
 /// Central registry for managing visual effects in the Glom TUI application.
@@ -146,6 +160,13 @@ impl EffectRegistry {
             ProjectDetailsClose => self.register_close_popup(FxId::ProjectDetailsPopup),
             PipelineActionsClose => self.register_close_popup(FxId::PipelineActionsPopup),
             ConfigClose => self.register_close_popup(FxId::ConfigPopup),
+            RunFilterClose => self.register_close_popup(FxId::RunFilterPopup),
+            RunnersClose => self.register_close_popup(FxId::RunnersPopup),
+            CachesClose => self.register_close_popup(FxId::CachesPopup),
+            EnvironmentsClose => self.register_close_popup(FxId::EnvironmentsPopup),
+            HistoryClose => self.register_close_popup(FxId::HistoryPopup),
+            MetricsClose => self.register_close_popup(FxId::MetricsPopup),
+            PullRequestsClose => self.register_close_popup(FxId::PullRequestsPopup),
             ConfigUpdate(config) => self.animations_enabled = config.animations,
             _ => (),
         }
@@ -284,6 +305,69 @@ impl EffectRegistry {
         self.register_popup(FxId::ConfigPopup, popup_area);
     }
 
+    /// Registers opening effects for the run filter popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_run_filter_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::RunFilterPopup, popup_area);
+    }
+
+    /// Registers opening effects for the self-hosted runners popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_runners_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::RunnersPopup, popup_area);
+    }
+
+    /// Registers opening effects for the Actions caches popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_caches_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::CachesPopup, popup_area);
+    }
+
+    /// Registers opening effects for the deployment environments popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_environments_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::EnvironmentsPopup, popup_area);
+    }
+
+    /// Registers opening effects for the run history popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_history_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::HistoryPopup, popup_area);
+    }
+
+    /// Registers opening effects for the delivery metrics popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_metrics_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::MetricsPopup, popup_area);
+    }
+
+    /// Registers opening effects for the pull requests popup.
+    ///
+    /// # Arguments
+    ///
+    /// * `popup_area` - Reference to the popup's screen area
+    pub fn register_pull_requests_popup(&mut self, popup_area: RefRect) {
+        self.register_popup(FxId::PullRequestsPopup, popup_area);
+    }
+
     /// Registers a generic popup with standard opening effects.
     ///
     /// Creates a combined effect that includes window opening animation
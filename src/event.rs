@@ -1,14 +1,23 @@
-use std::{fmt::Debug, sync::mpsc, thread};
+use std::{fmt::Debug, path::PathBuf, sync::mpsc, thread};
 
 use compact_str::CompactString;
 use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, KeyEventKind};
 use tracing::Level;
 
 use crate::{
+    client::config::RunFilter,
     dispatcher::Dispatcher,
-    domain::{JobDto, PipelineDto, Project, ProjectDto, StatisticsDto},
+    domain::{
+        CacheDeletion, CacheDto, CacheUsageDto, CommitChecks, EnvironmentDeployment, JobDto,
+        Pipeline, PipelineDto, Project, ProjectDto, PullRequest, RunTimingDto, RunnerDto,
+        StatisticsDto,
+    },
+    durations::{DurationRegression, WorkflowDurations},
+    export::ExportFormat,
     glom_app::GlomConfig,
+    history::{JobFlakiness, RunHistoryReport},
     id::{JobId, PipelineId, ProjectId},
+    metrics::MetricsReport,
     result,
 };
 
@@ -18,10 +27,25 @@ pub enum GlomEvent {
     AppExit,
     AppTick,
     ApplyTemporaryFilter(Option<CompactString>),
+    CacheDelete(ProjectId, CacheDeletion),
+    CachesClose,
+    CachesFetch(ProjectId),
+    CachesLoaded(ProjectId, CacheUsageDto, Vec<CacheDto>),
+    CachesOpen(ProjectId),
+    CommitChecksFetch(ProjectId, CompactString),
+    CommitChecksLoaded(ProjectId, CompactString, CommitChecks),
     ConfigApply,
     ConfigClose,
     ConfigOpen,
     ConfigUpdate(GlomConfig),
+    /// Export of the projects matching the temporary filter, if any
+    DashboardExport(Option<CompactString>),
+    /// Dashboard written to the file, or else copied to the clipboard
+    DashboardExported(ExportFormat, Option<PathBuf>),
+    EnvironmentsClose,
+    EnvironmentsFetch(ProjectId),
+    EnvironmentsLoaded(ProjectId, Vec<EnvironmentDeployment>),
+    EnvironmentsOpen(ProjectId),
     FilterClear,
     FilterInputBackspace,
     FilterInputChar(CompactString),
@@ -29,7 +53,12 @@ pub enum GlomEvent {
     FilterMenuShow,
     #[allow(dead_code)]
     GlitchOverride(GlitchState),
+    HistoryClose,
+    HistoryFetch(ProjectId),
+    HistoryLoaded(ProjectId, RunHistoryReport),
+    HistoryOpen(ProjectId),
     InputKey(KeyEvent),
+    JobFlakinessLoaded(ProjectId, Vec<JobFlakiness>),
     JobLogDownloaded(ProjectId, JobId, CompactString),
     JobLogFetch(ProjectId, PipelineId),
     JobOpenUrl(ProjectId, PipelineId, JobId),
@@ -38,6 +67,11 @@ pub enum GlomEvent {
     JobsLoaded(ProjectId, PipelineId, Vec<JobDto>),
     LogEntry(CompactString),
     LogLevelChanged(Level),
+    MetricsClose,
+    MetricsExport(MetricsReport),
+    MetricsFetch(ProjectId),
+    MetricsLoaded(ProjectId, MetricsReport),
+    MetricsOpen(ProjectId),
     NotificationDismiss,
     NotificationLast,
     PipelineActionsClose,
@@ -59,8 +93,34 @@ pub enum GlomEvent {
     ProjectsLoaded(Vec<ProjectDto>),
     ProjectStatisticsFetch(ProjectId),
     ProjectStatisticsLoaded(ProjectId, StatisticsDto),
+    PullRequestRunsFetch(ProjectId, u64, CompactString),
+    PullRequestRunsLoaded(ProjectId, u64, Vec<PipelineDto>),
+    PullRequestsClose,
+    PullRequestsFetch(ProjectId),
+    PullRequestsLoaded(ProjectId, Vec<PullRequest>),
+    PullRequestsOpen(ProjectId),
+    RunFilterApply,
+    RunFilterClose,
+    RunFilterOpen(ProjectId),
+    RunFilterUpdated(ProjectId, RunFilter),
+    RunnersClose,
+    RunnersFetch(ProjectId),
+    RunnersLoaded(ProjectId, Vec<RunnerDto>),
+    RunnersOpen(ProjectId),
+    /// A run seen queued or in progress changed to success or failure
+    RunCompleted(Box<Pipeline>),
+    RunDurationRegressed(DurationRegression),
+    RunDurationsLoaded(ProjectId, Vec<WorkflowDurations>),
+    RunTimingFetch(ProjectId, PipelineId),
+    RunTimingLoaded(ProjectId, PipelineId, RunTimingDto),
     ScreenCapture,
     ScreenCaptureToClipboard(String),
+    UrlOpen(CompactString),
+    /// Watches the branch of the run, or dismisses the watch
+    WatchBranchToggle(ProjectId, PipelineId),
+    WatchClear,
+    WatchRunToggle(ProjectId, PipelineId),
+    WatchedFetch,
 }
 
 impl GlomEvent {
@@ -71,17 +131,35 @@ impl GlomEvent {
             GlomEvent::AppExit => "AppExit",
             GlomEvent::AppTick => "AppTick",
             GlomEvent::ApplyTemporaryFilter(_) => "ApplyTemporaryFilter",
+            GlomEvent::CacheDelete(_, _) => "CacheDelete",
+            GlomEvent::CachesClose => "CachesClose",
+            GlomEvent::CachesFetch(_) => "CachesFetch",
+            GlomEvent::CachesLoaded(_, _, _) => "CachesLoaded",
+            GlomEvent::CachesOpen(_) => "CachesOpen",
+            GlomEvent::CommitChecksFetch(_, _) => "CommitChecksFetch",
+            GlomEvent::CommitChecksLoaded(_, _, _) => "CommitChecksLoaded",
             GlomEvent::ConfigApply => "ConfigApply",
             GlomEvent::ConfigClose => "ConfigClose",
             GlomEvent::ConfigOpen => "ConfigOpen",
             GlomEvent::ConfigUpdate(_) => "ConfigUpdate",
+            GlomEvent::DashboardExport(_) => "DashboardExport",
+            GlomEvent::DashboardExported(_, _) => "DashboardExported",
+            GlomEvent::EnvironmentsClose => "EnvironmentsClose",
+            GlomEvent::EnvironmentsFetch(_) => "EnvironmentsFetch",
+            GlomEvent::EnvironmentsLoaded(_, _) => "EnvironmentsLoaded",
+            GlomEvent::EnvironmentsOpen(_) => "EnvironmentsOpen",
             GlomEvent::FilterClear => "FilterClear",
             GlomEvent::FilterInputBackspace => "FilterInputBackspace",
             GlomEvent::FilterInputChar(_) => "FilterInputChar",
             GlomEvent::FilterMenuClose => "FilterMenuClose",
             GlomEvent::FilterMenuShow => "FilterMenuShow",
             GlomEvent::GlitchOverride(_) => "GlitchOverride",
+            GlomEvent::HistoryClose => "HistoryClose",
+            GlomEvent::HistoryFetch(_) => "HistoryFetch",
+            GlomEvent::HistoryLoaded(_, _) => "HistoryLoaded",
+            GlomEvent::HistoryOpen(_) => "HistoryOpen",
             GlomEvent::InputKey(_) => "InputKey",
+            GlomEvent::JobFlakinessLoaded(_, _) => "JobFlakinessLoaded",
             GlomEvent::JobLogDownloaded(_, _, _) => "JobLogDownloaded",
             GlomEvent::JobLogFetch(_, _) => "JobLogFetch",
             GlomEvent::JobOpenUrl(_, _, _) => "JobOpenUrl",
@@ -90,6 +168,11 @@ impl GlomEvent {
             GlomEvent::JobsLoaded(_, _, _) => "JobsLoaded",
             GlomEvent::LogEntry(_) => "LogEntry",
             GlomEvent::LogLevelChanged(_) => "LogLevelChanged",
+            GlomEvent::MetricsClose => "MetricsClose",
+            GlomEvent::MetricsExport(_) => "MetricsExport",
+            GlomEvent::MetricsFetch(_) => "MetricsFetch",
+            GlomEvent::MetricsLoaded(_, _) => "MetricsLoaded",
+            GlomEvent::MetricsOpen(_) => "MetricsOpen",
             GlomEvent::NotificationDismiss => "NotificationDismiss",
             GlomEvent::NotificationLast => "NotificationLast",
             GlomEvent::PipelineActionsClose => "PipelineActionsClose",
@@ -110,8 +193,32 @@ impl GlomEvent {
             GlomEvent::ProjectsLoaded(_) => "ProjectsLoaded",
             GlomEvent::ProjectStatisticsFetch(_) => "ProjectStatisticsFetch",
             GlomEvent::ProjectStatisticsLoaded(_, _) => "ProjectStatisticsLoaded",
+            GlomEvent::PullRequestRunsFetch(_, _, _) => "PullRequestRunsFetch",
+            GlomEvent::PullRequestRunsLoaded(_, _, _) => "PullRequestRunsLoaded",
+            GlomEvent::PullRequestsClose => "PullRequestsClose",
+            GlomEvent::PullRequestsFetch(_) => "PullRequestsFetch",
+            GlomEvent::PullRequestsLoaded(_, _) => "PullRequestsLoaded",
+            GlomEvent::PullRequestsOpen(_) => "PullRequestsOpen",
+            GlomEvent::RunFilterApply => "RunFilterApply",
+            GlomEvent::RunFilterClose => "RunFilterClose",
+            GlomEvent::RunFilterOpen(_) => "RunFilterOpen",
+            GlomEvent::RunFilterUpdated(_, _) => "RunFilterUpdated",
+            GlomEvent::RunnersClose => "RunnersClose",
+            GlomEvent::RunnersFetch(_) => "RunnersFetch",
+            GlomEvent::RunnersLoaded(_, _) => "RunnersLoaded",
+            GlomEvent::RunnersOpen(_) => "RunnersOpen",
+            GlomEvent::RunCompleted(_) => "RunCompleted",
+            GlomEvent::RunDurationRegressed(_) => "RunDurationRegressed",
+            GlomEvent::RunDurationsLoaded(_, _) => "RunDurationsLoaded",
+            GlomEvent::RunTimingFetch(_, _) => "RunTimingFetch",
+            GlomEvent::RunTimingLoaded(_, _, _) => "RunTimingLoaded",
             GlomEvent::ScreenCapture => "ScreenCapture",
             GlomEvent::ScreenCaptureToClipboard(_) => "ScreenCaptureToClipboard",
+            GlomEvent::UrlOpen(_) => "UrlOpen",
+            GlomEvent::WatchBranchToggle(_, _) => "WatchBranchToggle",
+            GlomEvent::WatchClear => "WatchClear",
+            GlomEvent::WatchRunToggle(_, _) => "WatchRunToggle",
+            GlomEvent::WatchedFetch => "WatchedFetch",
         }
     }
 }
@@ -0,0 +1,260 @@
+//! Export of the projects dashboard as JSON, CSV or a Markdown table, e.g. for
+//! pasting CI status into standup notes
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Local, Utc};
+use clap::ValueEnum;
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::{Pipeline, PipelineStatus, Project},
+    id::ProjectId,
+    ui::format_duration,
+};
+
+/// Latest runs exported per project, one per branch, as in the projects table
+pub const DASHBOARD_BRANCHES: usize = 3;
+
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    #[default]
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Markdown => "Markdown",
+        }
+    }
+}
+
+/// Projects as shown on the dashboard, with the latest run of each branch
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardExport {
+    pub generated_at: DateTime<Utc>,
+    /// Set if exported from the snapshot of the last session, rather than fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot_saved_at: Option<DateTime<Utc>>,
+    pub projects: Vec<ProjectExport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectExport {
+    pub project: ProjectId,
+    pub url: CompactString,
+    pub default_branch: CompactString,
+    pub runs: Vec<RunExport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RunExport {
+    pub branch: CompactString,
+    pub workflow: CompactString,
+    /// Status of the run merged with the external checks of its head commit
+    pub status: PipelineStatus,
+    pub url: CompactString,
+    pub head_sha: CompactString,
+    pub created_at: DateTime<Utc>,
+    /// Elapsed time of active runs; `None` until the jobs are loaded
+    pub duration_secs: Option<i64>,
+    pub failing_jobs: Vec<FailingJob>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailingJob {
+    pub name: CompactString,
+    pub url: CompactString,
+}
+
+impl DashboardExport {
+    pub fn new(projects: &[Project]) -> Self {
+        Self {
+            generated_at: Utc::now(),
+            snapshot_saved_at: None,
+            projects: projects.iter().map(ProjectExport::new).collect(),
+        }
+    }
+
+    /// Labels the export as made from a snapshot saved at the given time
+    pub fn with_snapshot_saved_at(mut self, saved_at: Option<DateTime<Utc>>) -> Self {
+        self.snapshot_saved_at = saved_at;
+        self
+    }
+
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            // serializing plain data does not fail
+            ExportFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            ExportFormat::Csv => self.to_csv(),
+            ExportFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "project,branch,workflow,status,duration_secs,failing_jobs,url,created_at\n",
+        );
+        for project in &self.projects {
+            if project.runs.is_empty() {
+                let _ = writeln!(
+                    csv,
+                    "{},,,,,,{},",
+                    csv_field(&project.project.to_string()),
+                    project.url
+                );
+            }
+
+            for run in &project.runs {
+                let failing_jobs = run
+                    .failing_jobs
+                    .iter()
+                    .map(|j| j.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{}",
+                    csv_field(&project.project.to_string()),
+                    csv_field(&run.branch),
+                    csv_field(&run.workflow),
+                    run.status.as_api_str(),
+                    run.duration_secs
+                        .map(|s| s.to_string())
+                        .unwrap_or_default(),
+                    csv_field(&failing_jobs),
+                    run.url,
+                    run.created_at.to_rfc3339(),
+                );
+            }
+        }
+
+        csv
+    }
+
+    fn to_markdown(&self) -> String {
+        let as_of = match self.snapshot_saved_at {
+            Some(saved_at) => format!(
+                "{} (snapshot of the last session)",
+                saved_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+            ),
+            None => self
+                .generated_at
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+        };
+        let mut md = format!(
+            "### CI status, {as_of}\n\n| | Project | Branch | Workflow | Duration | Failing jobs |\n|---|---|---|---|---|---|\n"
+        );
+
+        for project in &self.projects {
+            let name = format!(
+                "[{}]({})",
+                md_escape(&project.project.to_string()),
+                project.url
+            );
+            if project.runs.is_empty() {
+                let _ = writeln!(md, "| ⚪ | {name} | | no recent runs | | |");
+            }
+
+            for run in &project.runs {
+                let failing_jobs = run
+                    .failing_jobs
+                    .iter()
+                    .map(|j| format!("[{}]({})", md_escape(&j.name), j.url))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let _ = writeln!(
+                    md,
+                    "| {} | {name} | {} | [{}]({}) | {} | {failing_jobs} |",
+                    status_emoji(&run.status),
+                    md_escape(&run.branch),
+                    md_escape(&run.workflow),
+                    run.url,
+                    run.duration_secs
+                        .map(|s| format_duration(chrono::Duration::seconds(s)))
+                        .unwrap_or_default(),
+                );
+            }
+        }
+
+        md
+    }
+}
+
+impl ProjectExport {
+    fn new(project: &Project) -> Self {
+        let runs = project
+            .first_pipeline_per_branch(DASHBOARD_BRANCHES, |p| p.status.is_active())
+            .into_iter()
+            .map(RunExport::new)
+            .collect();
+
+        Self {
+            project: project.id.clone(),
+            url: project.url.clone(),
+            default_branch: project.default_branch.clone(),
+            runs,
+        }
+    }
+}
+
+impl RunExport {
+    fn new(pipeline: &Pipeline) -> Self {
+        let jobs = pipeline.jobs.as_deref().unwrap_or_default();
+        let failing_jobs = jobs
+            .iter()
+            .filter(|j| j.status.is_failure() && j.status != PipelineStatus::Cancelled)
+            .map(|j| FailingJob { name: j.name.clone(), url: j.url.clone() })
+            .collect();
+
+        Self {
+            branch: pipeline.branch.clone(),
+            workflow: pipeline.name.clone(),
+            status: pipeline.combined_status(),
+            url: pipeline.url.clone(),
+            head_sha: pipeline.head_sha.clone(),
+            created_at: pipeline.created_at,
+            duration_secs: (!jobs.is_empty()).then(|| pipeline.duration().num_seconds()),
+            failing_jobs,
+        }
+    }
+}
+
+fn status_emoji(status: &PipelineStatus) -> &'static str {
+    match status {
+        PipelineStatus::Success => "✅",
+        PipelineStatus::Failure | PipelineStatus::TimedOut => "❌",
+        s if s.is_active() => "🔄",
+        _ => "⚪",
+    }
+}
+
+/// Quotes fields containing separators, quotes or line breaks
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Escapes characters breaking a Markdown table cell or link
+fn md_escape(value: &str) -> String {
+    value
+        .replace('|', "\\|")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+}
@@ -0,0 +1,65 @@
+//! Resolves the commit and GitHub repository of the local git checkout
+
+use std::process::Command;
+
+use compact_str::CompactString;
+
+use crate::{
+    id::ProjectId,
+    result::{GlomError, Result},
+};
+
+/// SHA of the checked out commit
+pub fn head_sha() -> Result<CompactString> {
+    git(&["rev-parse", "HEAD"])
+}
+
+/// Repository of the remote tracked by the current branch, or of `origin`
+pub fn remote_project() -> Result<ProjectId> {
+    let remote = git(&["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{upstream}"])
+        .ok()
+        .and_then(|upstream| {
+            upstream
+                .split_once('/')
+                .map(|(remote, _)| remote.into())
+        })
+        .unwrap_or_else(|| CompactString::from("origin"));
+
+    let url = git(&["remote", "get-url", &remote])?;
+    project_from_url(&url).ok_or_else(|| {
+        GlomError::GeneralError(format!("remote {remote} is not a GitHub repository: {url}").into())
+    })
+}
+
+/// `owner/repo` of a remote url, e.g. `git@github.com:owner/repo.git` or
+/// `https://github.com/owner/repo`
+fn project_from_url(url: &str) -> Option<ProjectId> {
+    let path = url.trim_end_matches('/').trim_end_matches(".git");
+    let path = match path.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => path.split_once(':')?.1,
+    };
+
+    let mut segments = path.rsplit('/');
+    let repo = segments.next().filter(|s| !s.is_empty())?;
+    let owner = segments.next().filter(|s| !s.is_empty())?;
+    Some(ProjectId::new(format!("{owner}/{repo}")))
+}
+
+fn git(args: &[&str]) -> Result<CompactString> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| GlomError::GeneralError(format!("failed to run git: {e}").into()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GlomError::GeneralError(
+            format!("git {}: {}", args.join(" "), stderr.trim()).into(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .into())
+}
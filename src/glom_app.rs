@@ -1,25 +1,41 @@
-use std::{path::PathBuf, sync::mpsc::Sender};
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, mpsc::Sender},
+};
 
-use compact_str::CompactString;
+use compact_str::{CompactString, ToCompactString};
+use itertools::Itertools;
 use ratatui::layout::Rect;
 use serde::{Deserialize, Serialize};
 use tachyonfx::{Duration, RefRect};
 use tracing::{debug, info, instrument, warn};
 
 use crate::{
-    client::{ClientConfig, GithubService},
+    client::{
+        ClientConfig, FixtureSource, GithubService,
+        config::{DEFAULT_MAX_CONCURRENT_REQUESTS, RunFilter},
+    },
     config::save_config,
     dispatcher::Dispatcher,
     domain::Project,
+    durations::{DEFAULT_DURATION_ALERT_PERCENTILE, RunDurations},
     effect_registry::EffectRegistry,
     event::GlomEvent,
+    export::{DashboardExport, ExportFormat},
+    history::{RunHistory, RunHistoryReport, RunHistoryWorker, default_history_path},
     id::ProjectId,
     input::{InputMultiplexer, processor::NormalModeProcessor},
     logging::LoggingReloadHandle,
+    metrics::MetricsReport,
     notice_service::{Notice, NoticeLevel, NoticeService},
+    notify::{Notifier, TerminalNotification, WebhookNotifier},
+    prometheus::PrometheusMetrics,
     result::GlomError,
+    snapshot::{SNAPSHOT_INTERVAL, default_snapshot_path, load_snapshot, save_snapshot},
     stores::{ProjectStore, log_event},
     ui::{StatefulWidgets, widget::NotificationState},
+    watch::{WatchItem, WatchList, WatchedRuns, default_watch_path},
 };
 
 pub struct GlomApp {
@@ -34,9 +50,25 @@ pub struct GlomApp {
     clipboard: arboard::Clipboard,
     log_reload_handle: LoggingReloadHandle,
     current_log_level: tracing::Level,
+    billing_multipliers: BTreeMap<CompactString, f64>,
+    queue_time_threshold: chrono::Duration,
+    export_format: ExportFormat,
+    /// Dashboard exports are copied to the clipboard unless set
+    export_path: Option<PathBuf>,
+    snapshot_path: PathBuf,
+    last_snapshot: std::time::Instant,
+    /// `None` if the run history database could not be opened
+    history: Option<RunHistoryWorker>,
+    run_durations: RunDurations,
+    /// `None` unless the Prometheus exporter is enabled
+    metrics: Option<Arc<PrometheusMetrics>>,
+    notifier: Notifier,
+    webhook_notifier: WebhookNotifier,
+    /// Runs and branches pinned above the projects
+    watches: WatchList,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct GlomConfig {
     /// The URL of the GitHub instance
     pub github_url: CompactString,
@@ -49,6 +81,87 @@ pub struct GlomConfig {
     /// Enable animations (default: true)
     #[serde(default)]
     pub animations: bool,
+    /// Address for the embedded webhook listener, e.g. `127.0.0.1:8787`
+    #[serde(default)]
+    pub webhook_listen: Option<CompactString>,
+    /// Secret used to validate the signature of webhook deliveries
+    #[serde(default)]
+    pub webhook_secret: Option<CompactString>,
+    /// Workflow run filters per project, keyed by `owner/repo`
+    #[serde(default)]
+    pub run_filters: BTreeMap<CompactString, RunFilter>,
+    /// Multipliers applied to billable minutes per runner OS to estimate cost,
+    /// e.g. `UBUNTU = 0.008`
+    #[serde(default)]
+    pub billing_multipliers: BTreeMap<CompactString, f64>,
+    /// Maximum number of concurrent GitHub API requests; read on startup
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    /// Percentile of a workflow's recent run durations a successful run must exceed
+    /// to raise a notice (default: 95)
+    #[serde(default = "default_duration_alert_percentile")]
+    pub duration_alert_percentile: f64,
+    /// Seconds a job may wait for a runner before it is highlighted as a sign of
+    /// runner starvation (default: 300)
+    #[serde(default = "default_queue_time_threshold_secs")]
+    pub queue_time_threshold_secs: u64,
+    /// Format of dashboard exports: json, csv or markdown (default: markdown)
+    #[serde(default)]
+    pub export_format: ExportFormat,
+    /// File dashboard exports are written to, instead of the clipboard
+    #[serde(default)]
+    pub export_path: Option<PathBuf>,
+    /// Address for the Prometheus exporter, e.g. `127.0.0.1:9184`; read on startup
+    #[serde(default)]
+    pub metrics_listen: Option<CompactString>,
+    /// Terminal escape sequence notifying of completed runs: osc9 or osc777
+    #[serde(default)]
+    pub notify_terminal: Option<TerminalNotification>,
+    /// Ring the terminal bell when a run completes
+    #[serde(default)]
+    pub notify_bell: bool,
+    /// Shell command run when a run completes, with `GLOM_PROJECT`, `GLOM_BRANCH`,
+    /// `GLOM_WORKFLOW`, `GLOM_STATUS` and `GLOM_URL` set
+    #[serde(default)]
+    pub notify_command: Option<CompactString>,
+    /// URL posted to when a default branch run breaks or fixes a workflow, e.g. a
+    /// Slack or Teams incoming webhook
+    #[serde(default)]
+    pub notify_webhook_url: Option<CompactString>,
+    /// JSON body posted to `notify_webhook_url`, with `{{project}}`, `{{branch}}`,
+    /// `{{workflow}}`, `{{status}}`, `{{url}}`, `{{sha}}`, `{{emoji}}` and `{{summary}}`
+    /// placeholders (default: a Slack and Teams compatible `text` message)
+    #[serde(default)]
+    pub notify_webhook_template: Option<String>,
+    /// Projects posted to `notify_webhook_url`, as `owner/repo`; all projects if empty.
+    /// Independent of the watch list
+    #[serde(default)]
+    pub notify_webhook_projects: Vec<CompactString>,
+    /// Minimum seconds between failures posted per workflow (default: 900)
+    #[serde(default = "default_notify_webhook_throttle_secs")]
+    pub notify_webhook_throttle_secs: u64,
+}
+
+/// Queue time above which jobs are highlighted, unless configured
+const DEFAULT_QUEUE_TIME_THRESHOLD_SECS: u64 = 5 * 60;
+
+fn default_max_concurrent_requests() -> usize {
+    DEFAULT_MAX_CONCURRENT_REQUESTS
+}
+
+fn default_duration_alert_percentile() -> f64 {
+    DEFAULT_DURATION_ALERT_PERCENTILE
+}
+
+fn default_queue_time_threshold_secs() -> u64 {
+    DEFAULT_QUEUE_TIME_THRESHOLD_SECS
+}
+
+/// Failures posted per workflow at most once in this window, unless configured
+const DEFAULT_NOTIFY_WEBHOOK_THROTTLE_SECS: u64 = 15 * 60;
+
+fn default_notify_webhook_throttle_secs() -> u64 {
+    DEFAULT_NOTIFY_WEBHOOK_THROTTLE_SECS
 }
 
 impl Default for GlomConfig {
@@ -61,6 +174,23 @@ impl Default for GlomConfig {
             search_filter: None,
             log_level: Some("Error".into()),
             animations: true,
+            webhook_listen: None,
+            webhook_secret: None,
+            run_filters: BTreeMap::new(),
+            billing_multipliers: BTreeMap::new(),
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            duration_alert_percentile: DEFAULT_DURATION_ALERT_PERCENTILE,
+            queue_time_threshold_secs: DEFAULT_QUEUE_TIME_THRESHOLD_SECS,
+            export_format: ExportFormat::default(),
+            export_path: None,
+            metrics_listen: None,
+            notify_terminal: None,
+            notify_bell: false,
+            notify_command: None,
+            notify_webhook_url: None,
+            notify_webhook_template: None,
+            notify_webhook_projects: Vec::new(),
+            notify_webhook_throttle_secs: DEFAULT_NOTIFY_WEBHOOK_THROTTLE_SECS,
         }
     }
 }
@@ -71,6 +201,7 @@ impl GlomApp {
         config_path: PathBuf,
         github: GithubService,
         log_reload_handle: LoggingReloadHandle,
+        metrics: Option<Arc<PrometheusMetrics>>,
         config: &GlomConfig,
     ) -> Self {
         let mut input = InputMultiplexer::new(sender.clone());
@@ -83,18 +214,64 @@ impl GlomApp {
             .and_then(|level_str| level_str.parse().ok())
             .unwrap_or(tracing::Level::ERROR);
 
+        // fixtures keep their state apart from that of the real projects
+        let state_dir = github
+            .config()
+            .fixtures
+            .as_ref()
+            .map(FixtureSource::state_dir);
+        let state_path = |name: &str, default: fn() -> PathBuf| match &state_dir {
+            Some(dir) => dir.join(name),
+            None => default(),
+        };
+
+        // show the projects of the previous session until the first refresh completes;
+        // the runs of a previous demo would never complete
+        let snapshot_path = state_path("projects.json", default_snapshot_path);
+        let mut project_store = ProjectStore::new(sender.clone());
+        if state_dir.is_none()
+            && let Some(projects) = load_snapshot(&snapshot_path, &config.github_url)
+        {
+            project_store.restore(projects);
+        }
+        if let Some(metrics) = metrics.as_ref() {
+            project_store
+                .projects()
+                .iter()
+                .for_each(|p| metrics.update(p));
+        }
+
+        let history = RunHistory::open(&state_path("history.db", default_history_path))
+            .inspect_err(|e| warn!(error = %e, "Run history is unavailable"))
+            .ok()
+            .map(|history| RunHistoryWorker::spawn(history, sender.clone()));
+
         Self {
             running: true,
             config_path,
             github,
             last_tick: std::time::Instant::now(),
             sender: sender.clone(),
-            project_store: ProjectStore::new(sender),
+            project_store,
             notices: NoticeService::new(),
             input,
             clipboard: arboard::Clipboard::new().expect("failed to create clipboard"),
             log_reload_handle,
             current_log_level,
+            billing_multipliers: config.billing_multipliers.clone(),
+            queue_time_threshold: chrono::Duration::seconds(
+                config.queue_time_threshold_secs as i64,
+            ),
+            export_format: config.export_format,
+            export_path: config.export_path.clone(),
+            snapshot_path,
+            last_snapshot: std::time::Instant::now(),
+            history,
+            run_durations: RunDurations::new(config.duration_alert_percentile),
+            metrics,
+            notifier: Notifier::new(config),
+            webhook_notifier: WebhookNotifier::new(config, tokio::runtime::Handle::current()),
+            watches: WatchList::open(state_path("watches.json", default_watch_path)),
         }
     }
 
@@ -109,10 +286,16 @@ impl GlomApp {
         log_event(&event);
         effects.apply(&event);
         self.notices.apply(&event);
+        if let Some(history) = self.history.as_ref() {
+            history.apply(&event);
+        }
         self.project_store.apply(&event);
 
         match event {
-            GlomEvent::AppExit => self.running = false,
+            GlomEvent::AppExit => {
+                self.save_snapshot();
+                self.running = false
+            },
 
             // www
             GlomEvent::ProjectOpenUrl(id) => {
@@ -128,6 +311,10 @@ impl GlomApp {
 
                 open::that(&pipeline.url).expect("unable to open browser");
             },
+            GlomEvent::UrlOpen(url) => {
+                debug!(url = %url, "Opening url in browser");
+                open::that(url.as_str()).expect("unable to open browser")
+            },
             GlomEvent::JobOpenUrl(project_id, pipeline_id, job_id) => {
                 debug!(project_id = %project_id, pipeline_id = %pipeline_id, job_id = %job_id, "Opening job in browser");
                 let project = self.project(project_id.clone());
@@ -162,11 +349,7 @@ impl GlomApp {
             GlomEvent::JobsActiveFetch => {
                 debug!("Requesting active jobs for all projects");
                 self.project_store
-                    .sorted_projects()
-                    .iter()
-                    .flat_map(|p| p.pipelines.iter())
-                    .flatten()
-                    .filter(|p| p.status.is_active() || p.has_active_jobs())
+                    .active_pipelines()
                     .for_each(|p| {
                         self.github
                             .spawn_fetch_jobs(p.project_id.clone(), p.id)
@@ -176,29 +359,183 @@ impl GlomApp {
                 debug!(project_id = %id, "Requesting pipelines for project");
                 self.github.spawn_fetch_pipelines(id, None)
             },
-            GlomEvent::ProjectsFetch => {
-                let latest_activity = self
+            // restored projects are reconciled against the full list of projects
+            GlomEvent::ProjectsFetch => self
+                .github
+                .spawn_fetch_projects(self.project_store.projects_updated_after()),
+            GlomEvent::JobsFetch(project_id, pipeline_id) => {
+                debug!(project_id = %project_id, pipeline_id = %pipeline_id, "Requesting jobs for pipeline");
+                self.github
+                    .spawn_fetch_jobs(project_id, pipeline_id)
+            },
+            GlomEvent::CachesOpen(project_id) => self.dispatch(GlomEvent::CachesFetch(project_id)),
+            GlomEvent::CachesFetch(project_id) => self.github.spawn_fetch_caches(project_id),
+            GlomEvent::CacheDelete(project_id, deletion) => self
+                .github
+                .spawn_delete_caches(project_id, deletion),
+            GlomEvent::PullRequestsOpen(project_id) => {
+                self.dispatch(GlomEvent::PullRequestsFetch(project_id))
+            },
+            GlomEvent::PullRequestsFetch(project_id) => {
+                self.github.spawn_fetch_pull_requests(project_id)
+            },
+            GlomEvent::PullRequestRunsFetch(project_id, number, head_sha) => self
+                .github
+                .spawn_fetch_pull_request_runs(project_id, number, head_sha),
+            // notifies of watched runs, which are pinned until they finish, and posts
+            // default branch runs breaking or fixing their workflow
+            GlomEvent::RunCompleted(pipeline) => {
+                let project = self
                     .project_store
-                    .sorted_projects()
-                    .iter()
-                    .max_by_key(|p| p.last_activity_at)
-                    .map(|p| p.last_activity_at);
+                    .find(pipeline.project_id.clone());
+
+                if let Some(project) = project
+                    && let Err(e) = self
+                        .webhook_notifier
+                        .run_completed(&pipeline, &project.default_branch)
+                {
+                    self.dispatch(GlomEvent::AppError(e));
+                }
 
-                let updated_after = self
+                if self.watches.is_watched(&pipeline) {
+                    if let Err(e) = self.notifier.notify(&pipeline) {
+                        self.dispatch(GlomEvent::AppError(e));
+                    }
+                    self.watches.complete(&pipeline, project);
+                }
+            },
+            GlomEvent::WatchRunToggle(project_id, run_id) => {
+                self.watches
+                    .toggle(WatchItem::Run { project_id, run_id });
+            },
+            GlomEvent::WatchBranchToggle(project_id, run_id) => {
+                let branch = self
                     .project_store
-                    .sorted_projects()
-                    .iter()
-                    .filter(|p| p.has_active_pipelines())
-                    .min_by_key(|p| p.last_activity_at)
-                    .map(|p| p.last_activity_at)
-                    .map_or_else(|| latest_activity, Some);
+                    .find(project_id.clone())
+                    .and_then(|p| p.pipeline(run_id))
+                    .map(|p| p.branch.clone());
 
-                self.github.spawn_fetch_projects(updated_after)
+                if let Some(branch) = branch {
+                    self.watches
+                        .toggle(WatchItem::Branch { project_id, branch });
+                }
             },
-            GlomEvent::JobsFetch(project_id, pipeline_id) => {
-                debug!(project_id = %project_id, pipeline_id = %pipeline_id, "Requesting jobs for pipeline");
+            GlomEvent::WatchClear => self.watches.clear(),
+            GlomEvent::WatchedFetch => self
+                .watches
+                .items()
+                .map(WatchItem::project_id)
+                .unique()
+                .for_each(|id| {
+                    self.github
+                        .spawn_fetch_pipelines(id.clone(), None)
+                }),
+            GlomEvent::ProjectsLoaded(_) => {
+                if let Some(metrics) = self.metrics.as_ref() {
+                    metrics.retain(self.project_store.projects());
+                }
+            },
+            GlomEvent::ProjectUpdated(project) => {
+                if let Some(metrics) = self.metrics.as_ref() {
+                    metrics.update(&project);
+                }
+
+                // completions are notified first, as they are dispatched before the update
+                self.watches.prune(&project);
+
+                if let Some(regressions) = self.run_durations.record(&project) {
+                    for regression in regressions {
+                        self.dispatch(GlomEvent::RunDurationRegressed(regression));
+                    }
+
+                    let durations = self.run_durations.durations(&project.id);
+                    self.dispatch(GlomEvent::RunDurationsLoaded(project.id, durations));
+                }
+            },
+            // recent run durations, and the flakiness of the project's jobs marking failures
+            // of flaky jobs
+            GlomEvent::ProjectDetailsOpen(project_id) => {
+                let durations = self.run_durations.durations(&project_id);
+                self.dispatch(GlomEvent::RunDurationsLoaded(project_id.clone(), durations));
+
+                if let Some(history) = self.history.as_ref() {
+                    history.fetch_job_flakiness(project_id);
+                }
+            },
+            GlomEvent::HistoryOpen(project_id) => {
+                self.dispatch(GlomEvent::HistoryFetch(project_id))
+            },
+            GlomEvent::HistoryFetch(project_id) => match self.history.as_ref() {
+                Some(history) => history.fetch_report(project_id),
+                None => self.dispatch(GlomEvent::HistoryLoaded(
+                    project_id,
+                    RunHistoryReport::default(),
+                )),
+            },
+            GlomEvent::MetricsOpen(project_id) => {
+                self.dispatch(GlomEvent::MetricsFetch(project_id))
+            },
+            GlomEvent::MetricsFetch(project_id) => {
+                let branch = self
+                    .project(project_id.clone())
+                    .default_branch
+                    .clone();
+                match self.history.as_ref() {
+                    Some(history) => history.fetch_metrics(project_id, branch),
+                    None => {
+                        let report = MetricsReport::new(project_id.clone(), branch, &[]);
+                        self.dispatch(GlomEvent::MetricsLoaded(project_id, report))
+                    },
+                }
+            },
+            GlomEvent::MetricsExport(report) => {
+                if let Err(e) = self.clipboard.set_text(report.to_csv()) {
+                    warn!(error = %e, "Failed to copy delivery metrics to clipboard");
+                }
+            },
+            GlomEvent::DashboardExport(filter) => {
+                let filter = filter.or_else(|| self.github.config().search_filter.clone());
+                let (projects, _) = self.filtered_projects(&filter);
+                let content = DashboardExport::new(&projects).render(self.export_format);
+
+                let result = match &self.export_path {
+                    Some(path) => std::fs::write(path, content).map_err(|e| {
+                        GlomError::GeneralError(
+                            format!("Failed to export dashboard to {}: {e}", path.display()).into(),
+                        )
+                    }),
+                    None => self.clipboard.set_text(content).map_err(|e| {
+                        GlomError::GeneralError(
+                            format!("Failed to copy dashboard to clipboard: {e}").into(),
+                        )
+                    }),
+                };
+
+                match result {
+                    Ok(()) => self.dispatch(GlomEvent::DashboardExported(
+                        self.export_format,
+                        self.export_path.clone(),
+                    )),
+                    Err(e) => self.dispatch(GlomEvent::AppError(e)),
+                }
+            },
+            GlomEvent::EnvironmentsOpen(project_id) => {
+                self.dispatch(GlomEvent::EnvironmentsFetch(project_id))
+            },
+            GlomEvent::EnvironmentsFetch(project_id) => {
+                self.github.spawn_fetch_environments(project_id)
+            },
+            GlomEvent::RunnersOpen(project_id) => {
+                self.dispatch(GlomEvent::RunnersFetch(project_id))
+            },
+            GlomEvent::RunnersFetch(project_id) => self.github.spawn_fetch_runners(project_id),
+            GlomEvent::CommitChecksFetch(project_id, sha) => self
+                .github
+                .spawn_fetch_commit_checks(project_id, sha),
+            GlomEvent::RunTimingFetch(project_id, pipeline_id) => {
+                debug!(project_id = %project_id, pipeline_id = %pipeline_id, "Requesting run timing");
                 self.github
-                    .spawn_fetch_jobs(project_id, pipeline_id)
+                    .spawn_fetch_run_timing(project_id, pipeline_id)
             },
             GlomEvent::ProjectStatisticsFetch(project_id) => {
                 debug!(project_id = %project_id, "Requesting repository statistics");
@@ -209,8 +546,19 @@ impl GlomApp {
             // configuration
             GlomEvent::ConfigUpdate(config) => {
                 let client_config = ClientConfig::from(config.clone())
-                    .with_debug_logging(self.github.config().debug.log_responses);
+                    .with_debug_logging(self.github.config().debug.log_responses)
+                    .with_fixtures(self.github.config().fixtures);
                 let _ = self.github.update_config(client_config);
+                self.billing_multipliers
+                    .clone_from(&config.billing_multipliers);
+                self.run_durations
+                    .set_percentile(config.duration_alert_percentile);
+                self.queue_time_threshold =
+                    chrono::Duration::seconds(config.queue_time_threshold_secs as i64);
+                self.export_format = config.export_format;
+                self.export_path.clone_from(&config.export_path);
+                self.notifier = Notifier::new(&config);
+                self.webhook_notifier.update_config(&config);
 
                 // Update logging level
                 if let Some(ref log_level_str) = config.log_level {
@@ -226,7 +574,8 @@ impl GlomApp {
                 if let Some(config_popup) = ui.config_popup_state.as_ref() {
                     let config = config_popup.to_config();
                     let client_config = ClientConfig::from(config.clone())
-                        .with_debug_logging(self.github.config().debug.log_responses);
+                        .with_debug_logging(self.github.config().debug.log_responses)
+                        .with_fixtures(self.github.config().fixtures);
 
                     // Pre-validate configuration before attempting to connect
                     if let Err(validation_error) = client_config.validate() {
@@ -252,6 +601,62 @@ impl GlomApp {
                 }
             },
 
+            GlomEvent::RunFilterApply => {
+                let Some(popup) = ui.run_filter.as_mut() else {
+                    return;
+                };
+
+                let filter = match popup.to_filter() {
+                    Ok(filter) => filter,
+                    Err(message) => {
+                        popup.error_message = Some(message);
+                        return;
+                    },
+                };
+
+                let project_id = popup.project_id.clone();
+                let key = project_id.to_compact_string();
+                let update_filters = |filters: &mut BTreeMap<CompactString, RunFilter>| {
+                    if filter.is_empty() {
+                        filters.remove(&key);
+                    } else {
+                        filters.insert(key.clone(), filter.clone());
+                    }
+                };
+
+                if self.github.config().fixtures.is_some() {
+                    // fixtures leave the configuration file alone; the filter only
+                    // applies to the running client
+                    let mut client_config = self.github.config();
+                    update_filters(&mut client_config.run_filters);
+                    if let Err(e) = self.github.update_config(client_config) {
+                        let glom_error = GlomError::config_connection_error(e.to_string());
+                        self.dispatch(GlomEvent::AppError(glom_error));
+                        return;
+                    }
+                } else {
+                    // a missing or unreadable file must not be replaced by the defaults
+                    let mut config = match self.load_config() {
+                        Ok(config) => config,
+                        Err(e) => {
+                            self.dispatch(GlomEvent::AppError(e));
+                            return;
+                        },
+                    };
+                    update_filters(&mut config.run_filters);
+
+                    if let Err(e) = save_config(&self.config_path, config.clone()) {
+                        self.dispatch(GlomEvent::AppError(e));
+                        return;
+                    }
+
+                    self.dispatch(GlomEvent::ConfigUpdate(config));
+                }
+
+                self.dispatch(GlomEvent::RunFilterUpdated(project_id, filter));
+                self.dispatch(GlomEvent::RunFilterClose);
+            },
+
             GlomEvent::NotificationLast => {
                 if let Some(notice) = self.notices.last_notification() {
                     let content_area = RefRect::new(Rect::default());
@@ -339,22 +744,65 @@ impl GlomApp {
         let elapsed = now - self.last_tick;
         self.last_tick = now;
 
-        // do nothing with elapsed time for now;
-        // and consider moving to UiState
+        if now - self.last_snapshot >= SNAPSHOT_INTERVAL {
+            self.last_snapshot = now;
+            self.save_snapshot();
+        }
 
         Duration::from_millis(elapsed.as_millis() as u32)
     }
 
+    /// Persists the projects for the next startup, unless they have not been
+    /// refreshed since they were restored
+    fn save_snapshot(&self) {
+        let projects = self.project_store.projects();
+        if projects.is_empty() || self.project_store.is_stale() {
+            return;
+        }
+
+        save_snapshot(
+            &self.snapshot_path,
+            &self.github.config().base_url,
+            projects,
+        );
+    }
+
+    /// Whether the projects shown are from the previous session and not yet refreshed
+    pub fn is_stale(&self) -> bool {
+        self.project_store.is_stale()
+    }
+
     pub fn project(&self, id: ProjectId) -> &Project {
         self.project_store
             .find(id)
             .expect("project not found")
     }
 
+    /// Workflow run filter currently configured for a project
+    pub fn run_filter(&self, id: &ProjectId) -> RunFilter {
+        self.github.config().run_filter(id)
+    }
+
+    /// Multipliers for estimating the cost of billable minutes per runner OS
+    pub fn billing_multipliers(&self) -> &BTreeMap<CompactString, f64> {
+        &self.billing_multipliers
+    }
+
+    /// Queue time above which jobs are highlighted
+    pub fn queue_time_threshold(&self) -> chrono::Duration {
+        self.queue_time_threshold
+    }
+
     pub fn projects(&self) -> &[Project] {
         self.project_store.sorted_projects()
     }
 
+    /// Watched runs and branches with their current runs, in the order they were added
+    pub fn watched_runs(&self) -> Vec<WatchedRuns<'_>> {
+        self.watches
+            .resolve(self.project_store.projects())
+    }
+
     pub fn filtered_projects(
         &self,
         temporary_filter: &Option<CompactString>,
@@ -375,22 +823,8 @@ impl GlomApp {
             );
 
             for (index, project) in all_projects.iter().enumerate() {
-                let path_matches = project
-                    .path
-                    .to_lowercase()
-                    .contains(filter_lower.as_str());
-                let desc_matches = project
-                    .description
-                    .as_ref()
-                    .is_some_and(|d| d.to_lowercase().contains(filter_lower.as_str()));
-
-                if path_matches || desc_matches {
-                    tracing::debug!(
-                        "Project '{}' matches filter (path: {}, desc: {})",
-                        project.path,
-                        path_matches,
-                        desc_matches
-                    );
+                if project.matches_filter(&filter_lower) {
+                    tracing::debug!("Project '{}' matches filter", project.path);
                     filtered_projects.push(project.clone());
                     filtered_indices.push(index);
                 }
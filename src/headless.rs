@@ -0,0 +1,122 @@
+//! Headless mode, polling GitHub without the TUI to serve the Prometheus metrics and
+//! post default branch failures to a chat webhook
+
+use std::sync::mpsc;
+
+use tracing::{debug, info, warn};
+
+use crate::{
+    app_init::{GithubComponents, create_github_components, initialize_logging},
+    client::FixtureSource,
+    dispatcher::Dispatcher,
+    event::GlomEvent,
+    glom_app::GlomConfig,
+    notify::WebhookNotifier,
+    result::{GlomError, Result},
+    stores::{ProjectStore, log_event},
+};
+
+/// Polls the configured projects, serving their metrics and posting their failures,
+/// until interrupted
+pub fn run_headless(
+    config: GlomConfig,
+    debug: bool,
+    fixtures: Option<FixtureSource>,
+) -> Result<()> {
+    if config.metrics_listen.is_none() && config.notify_webhook_url.is_none() {
+        return Err(GlomError::config_validation_error(
+            "metrics_listen",
+            "metrics_listen or notify_webhook_url is required in headless mode",
+        ));
+    }
+
+    let (_log_guard, _) = initialize_logging(None, &config)?;
+    info!(
+        version = env!("CARGO_PKG_VERSION"),
+        "Glom starting headless"
+    );
+
+    let rt = tokio::runtime::Runtime::new()
+        .map_err(|e| GlomError::GeneralError(format!("Failed to create runtime: {e}").into()))?;
+
+    let mut webhook_notifier = WebhookNotifier::new(&config, rt.handle().clone());
+
+    let (sender, receiver) = mpsc::channel();
+    let GithubComponents { service, poller, webhook, exporter } = rt.block_on(
+        create_github_components(sender.clone(), config, debug, fixtures),
+    )?;
+
+    let metrics = exporter.as_ref().map(|e| e.metrics());
+
+    rt.spawn(async move {
+        if let Err(e) = poller.start().await {
+            tracing::error!("GitHub poller failed: {}", e);
+        }
+    });
+
+    if let Some(webhook) = webhook {
+        rt.spawn(async move {
+            if let Err(e) = webhook.start().await {
+                tracing::error!("Webhook receiver failed: {}", e);
+            }
+        });
+    }
+
+    // a failing listener ends headless mode, as there is nothing left to serve
+    let exporter = exporter.map(|exporter| rt.spawn(exporter.start()));
+
+    let mut project_store = ProjectStore::new(sender.clone());
+    sender.dispatch(GlomEvent::ProjectsFetch);
+
+    while !exporter.as_ref().is_some_and(|e| e.is_finished()) {
+        let Ok(event) = receiver.recv_timeout(std::time::Duration::from_secs(1)) else {
+            continue;
+        };
+
+        log_event(&event);
+        project_store.apply(&event);
+
+        match event {
+            GlomEvent::ProjectsFetch => {
+                service.spawn_fetch_projects(project_store.projects_updated_after())
+            },
+            GlomEvent::PipelinesFetch(id) => service.spawn_fetch_pipelines(id, None),
+            GlomEvent::JobsFetch(project_id, pipeline_id) => {
+                service.spawn_fetch_jobs(project_id, pipeline_id)
+            },
+            GlomEvent::JobsActiveFetch => project_store
+                .active_pipelines()
+                .for_each(|p| service.spawn_fetch_jobs(p.project_id.clone(), p.id)),
+            GlomEvent::ProjectsLoaded(_) => {
+                if let Some(metrics) = metrics.as_ref() {
+                    metrics.retain(project_store.projects());
+                }
+            },
+            GlomEvent::ProjectUpdated(project) => {
+                if let Some(metrics) = metrics.as_ref() {
+                    metrics.update(&project);
+                }
+            },
+            GlomEvent::RunCompleted(pipeline) => {
+                let default_branch = project_store
+                    .find(pipeline.project_id.clone())
+                    .map(|p| p.default_branch.clone())
+                    .unwrap_or_default();
+
+                if let Err(e) = webhook_notifier.run_completed(&pipeline, &default_branch) {
+                    warn!(error = %e, "Failed to notify of completed run");
+                }
+            },
+            GlomEvent::AppError(e) => warn!(error = %e, "Headless mode error"),
+            _ => debug!(event = event.variant_name(), "Ignored in headless mode"),
+        }
+    }
+
+    match exporter.map(|e| rt.block_on(e)) {
+        Some(Ok(result)) => result,
+        Some(Err(e)) => Err(GlomError::GeneralError(
+            format!("Prometheus exporter failed: {e}").into(),
+        )),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,548 @@
+//! Local run history, recording every workflow run and job observed once it completes
+
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+};
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Utc};
+use compact_str::CompactString;
+use directories::BaseDirs;
+use rusqlite::{Connection, OptionalExtension, params};
+use tracing::{debug, instrument, warn};
+
+use crate::{
+    dispatcher::Dispatcher,
+    domain::{JobDto, PipelineDto, PipelineStatus},
+    event::GlomEvent,
+    id::{PipelineId, ProjectId},
+    metrics::MetricsReport,
+    result::Result,
+};
+
+/// Schema migrations, applied in order; `PRAGMA user_version` counts those applied
+const MIGRATIONS: &[&str] = &[
+    "
+    CREATE TABLE IF NOT EXISTS runs (
+        project       TEXT    NOT NULL,
+        run_id        INTEGER NOT NULL,
+        attempt       INTEGER NOT NULL,
+        workflow      TEXT    NOT NULL,
+        branch        TEXT    NOT NULL,
+        event         TEXT    NOT NULL,
+        actor         TEXT,
+        status        TEXT    NOT NULL,
+        created_at    TEXT    NOT NULL,
+        started_at    TEXT    NOT NULL,
+        finished_at   TEXT    NOT NULL,
+        duration_secs INTEGER NOT NULL,
+        url           TEXT    NOT NULL,
+        PRIMARY KEY (project, run_id, attempt)
+    );
+    CREATE INDEX IF NOT EXISTS runs_by_branch ON runs (project, branch, created_at);
+
+    CREATE TABLE IF NOT EXISTS jobs (
+        job_id        INTEGER PRIMARY KEY,
+        project       TEXT    NOT NULL,
+        run_id        INTEGER NOT NULL,
+        attempt       INTEGER NOT NULL,
+        name          TEXT    NOT NULL,
+        status        TEXT    NOT NULL,
+        started_at    TEXT,
+        finished_at   TEXT    NOT NULL,
+        duration_secs INTEGER,
+        runner        TEXT,
+        url           TEXT    NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS jobs_by_name ON jobs (project, name, finished_at);
+    ",
+    // commit of the job, for telling flaky jobs from broken commits
+    "ALTER TABLE jobs ADD COLUMN head_sha TEXT;",
+    // commit of the run, for the change failure rate
+    "ALTER TABLE runs ADD COLUMN head_sha TEXT;",
+];
+
+/// Period over which the flakiness of jobs is judged
+pub const FLAKINESS_WINDOW: Duration = Duration::days(30);
+
+/// Statuses turning a branch red
+const RED: &str = "('failure', 'timed_out')";
+
+pub fn default_history_path() -> PathBuf {
+    if let Some(dirs) = BaseDirs::new() {
+        dirs.data_local_dir()
+            .join("glom")
+            .join("history.db")
+    } else {
+        PathBuf::from("glom-history.db")
+    }
+}
+
+/// Start of the local day, e.g. for querying failures since the start of the month
+pub fn local_day_start(day: NaiveDate) -> DateTime<Utc> {
+    Local
+        .from_local_datetime(&day.and_time(Default::default()))
+        .earliest()
+        .map_or_else(Utc::now, |d| d.with_timezone(&Utc))
+}
+
+/// Start of the current month in local time
+pub fn month_start() -> DateTime<Utc> {
+    let today = Local::now().date_naive();
+    local_day_start(today.with_day(1).unwrap_or(today))
+}
+
+/// SQLite database of completed workflow runs and jobs
+pub struct RunHistory {
+    conn: Connection,
+}
+
+/// Failing runs of a workflow on a branch since it last succeeded
+#[derive(Debug, Clone)]
+pub struct RedStreak {
+    pub project: ProjectId,
+    pub branch: CompactString,
+    pub workflow: CompactString,
+    /// Completion of the first failing run
+    pub since: DateTime<Utc>,
+    pub failed_runs: u32,
+    /// Latest failing run
+    pub url: CompactString,
+}
+
+/// Outcomes of a job over a period
+#[derive(Debug, Clone)]
+pub struct JobFailureRate {
+    pub project: ProjectId,
+    pub name: CompactString,
+    pub runs: u32,
+    pub failures: u32,
+}
+
+/// Commits on which a job both failed and succeeded, e.g. after a re-run, out of
+/// those on which it failed
+#[derive(Debug, Clone)]
+pub struct JobFlakiness {
+    pub name: CompactString,
+    pub flaky_commits: u32,
+    pub failed_commits: u32,
+}
+
+impl JobFlakiness {
+    /// Flaky commits required before failures are considered likely flaky
+    const LIKELY_FLAKY_COMMITS: u32 = 2;
+    /// Share of failures resolved by running the job again
+    const LIKELY_FLAKY_SCORE: f64 = 0.3;
+
+    /// Share of the commits the job failed on where it also succeeded, from 0 to 1
+    pub fn score(&self) -> f64 {
+        self.flaky_commits as f64 / self.failed_commits.max(1) as f64
+    }
+
+    /// Whether a failure of the job is more likely flaky than a real breakage
+    pub fn is_likely_flaky(&self) -> bool {
+        self.flaky_commits >= Self::LIKELY_FLAKY_COMMITS && self.score() >= Self::LIKELY_FLAKY_SCORE
+    }
+}
+
+/// Completed run of a workflow on a branch
+#[derive(Debug, Clone)]
+pub struct RecordedRun {
+    pub workflow: CompactString,
+    pub head_sha: Option<CompactString>,
+    pub status: PipelineStatus,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration: Duration,
+}
+
+/// Run history of a project, as shown in the history popup
+#[derive(Debug, Clone, Default)]
+pub struct RunHistoryReport {
+    pub red_streaks: Vec<RedStreak>,
+    /// Job failures of the current month
+    pub job_failures: Vec<JobFailureRate>,
+    pub recorded_since: Option<DateTime<Utc>>,
+}
+
+impl JobFailureRate {
+    /// Share of failed runs in percent
+    pub fn rate(&self) -> f64 {
+        self.failures as f64 * 100.0 / self.runs.max(1) as f64
+    }
+}
+
+impl RunHistory {
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| crate::result::GlomError::HistoryError(e.to_string().into()))?;
+        }
+
+        let conn = Connection::open(path)?;
+        Self::migrate(&conn)?;
+        debug!(path = %path.display(), "Opened run history");
+
+        Ok(Self { conn })
+    }
+
+    fn migrate(conn: &Connection) -> Result<()> {
+        let applied: usize = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+            debug!(version = version + 1, "Migrating run history");
+            conn.execute_batch(migration)?;
+            conn.pragma_update(None, "user_version", version + 1)?;
+        }
+
+        Ok(())
+    }
+
+    /// Records the runs and jobs carried by the event which have completed
+    #[instrument(skip(self, event), fields(event_type = %event.variant_name()))]
+    pub fn apply(&mut self, event: &GlomEvent) {
+        let result = match event {
+            GlomEvent::PipelinesLoaded(runs) => self.record_runs(runs),
+            GlomEvent::JobsLoaded(project_id, run_id, jobs) => {
+                self.record_jobs(project_id, *run_id, jobs)
+            },
+            _ => Ok(()),
+        };
+
+        if let Err(e) = result {
+            warn!(error = %e, "Failed to record run history");
+        }
+    }
+
+    fn record_runs(&mut self, runs: &[PipelineDto]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT OR IGNORE INTO runs (project, run_id, attempt, workflow, branch, event,
+                     actor, status, created_at, started_at, finished_at, duration_secs, url,
+                     head_sha)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            )?;
+
+            for run in runs {
+                let status = PipelineStatus::resolve(run.status.clone(), run.conclusion.clone());
+                if status.is_active() {
+                    continue;
+                }
+
+                let started_at = run.run_started_at.unwrap_or(run.created_at);
+                insert.execute(params![
+                    run.project_id.to_string(),
+                    run.id.to_string(),
+                    run.run_attempt.max(1),
+                    run.name.as_str(),
+                    run.head_branch.as_deref().unwrap_or("unknown"),
+                    run.event.to_string().as_str(),
+                    run.actor.as_ref().map(|a| a.login.as_str()),
+                    status.as_api_str(),
+                    run.created_at,
+                    started_at,
+                    run.updated_at,
+                    (run.updated_at - started_at).num_seconds().max(0),
+                    run.html_url.as_str(),
+                    Some(run.head_sha.as_str()).filter(|sha| !sha.is_empty()),
+                ])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn record_jobs(
+        &mut self,
+        project_id: &ProjectId,
+        run_id: PipelineId,
+        jobs: &[JobDto],
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut insert = tx.prepare_cached(
+                "INSERT OR IGNORE INTO jobs (job_id, project, run_id, attempt, name, status,
+                     started_at, finished_at, duration_secs, runner, url, head_sha)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            )?;
+
+            for job in jobs {
+                let status = PipelineStatus::resolve(job.status.clone(), job.conclusion.clone());
+                if status.is_active() {
+                    continue;
+                }
+
+                let duration = job
+                    .started_at
+                    .zip(job.completed_at)
+                    .map(|(start, end)| (end - start).num_seconds().max(0));
+
+                insert.execute(params![
+                    job.id.to_string(),
+                    project_id.to_string(),
+                    run_id.to_string(),
+                    job.run_attempt.max(1),
+                    job.name.as_str(),
+                    status.as_api_str(),
+                    job.started_at,
+                    job.completed_at.unwrap_or(job.created_at),
+                    duration,
+                    job.runner_name.as_deref(),
+                    job.html_url.as_str(),
+                    Some(job.head_sha.as_str()).filter(|sha| !sha.is_empty()),
+                ])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Workflows whose latest completed runs on a branch failed, longest red first
+    pub fn red_streaks(
+        &self,
+        project: Option<&str>,
+        branch: Option<&str>,
+    ) -> Result<Vec<RedStreak>> {
+        let mut query = self.conn.prepare_cached(&format!(
+            "WITH last_green AS (
+                 SELECT project, branch, workflow, MAX(created_at) AS at
+                 FROM runs WHERE status = 'success'
+                 GROUP BY project, branch, workflow
+             )
+             SELECT r.project, r.branch, r.workflow, MIN(r.finished_at), COUNT(*),
+                    (SELECT url FROM runs l
+                     WHERE l.project = r.project AND l.branch = r.branch
+                       AND l.workflow = r.workflow AND l.status IN {RED}
+                     ORDER BY l.created_at DESC LIMIT 1)
+             FROM runs r LEFT JOIN last_green g
+                 ON g.project = r.project AND g.branch = r.branch AND g.workflow = r.workflow
+             WHERE r.status IN {RED}
+               AND (g.at IS NULL OR r.created_at > g.at)
+               AND (?1 IS NULL OR r.project = ?1)
+               AND (?2 IS NULL OR r.branch = ?2)
+             GROUP BY r.project, r.branch, r.workflow
+             ORDER BY MIN(r.finished_at)"
+        ))?;
+
+        let streaks = query
+            .query_map(params![project, branch], |row| {
+                Ok(RedStreak {
+                    project: ProjectId::new(row.get::<_, String>(0)?),
+                    branch: row.get::<_, String>(1)?.into(),
+                    workflow: row.get::<_, String>(2)?.into(),
+                    since: row.get(3)?,
+                    failed_runs: row.get(4)?,
+                    url: row.get::<_, String>(5)?.into(),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(streaks)
+    }
+
+    /// Jobs which failed at least once since the given time, most failures first
+    pub fn job_failure_rates(
+        &self,
+        project: Option<&str>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<JobFailureRate>> {
+        let mut query = self.conn.prepare_cached(&format!(
+            "SELECT project, name, COUNT(*), SUM(status IN {RED}) AS failures
+             FROM jobs
+             WHERE finished_at >= ?1 AND status != 'skipped'
+               AND (?2 IS NULL OR project = ?2)
+             GROUP BY project, name
+             HAVING failures > 0
+             ORDER BY failures DESC, project, name"
+        ))?;
+
+        let rates = query
+            .query_map(params![since, project], |row| {
+                Ok(JobFailureRate {
+                    project: ProjectId::new(row.get::<_, String>(0)?),
+                    name: row.get::<_, String>(1)?.into(),
+                    runs: row.get(2)?,
+                    failures: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rates)
+    }
+
+    /// Red branches of the project, along with its job failures since the given time
+    pub fn report(&self, project_id: &ProjectId, since: DateTime<Utc>) -> Result<RunHistoryReport> {
+        let project = project_id.to_string();
+        Ok(RunHistoryReport {
+            red_streaks: self.red_streaks(Some(&project), None)?,
+            job_failures: self.job_failure_rates(Some(&project), since)?,
+            recorded_since: self.recorded_since()?,
+        })
+    }
+
+    /// Jobs of the project which both failed and succeeded on a commit completed since
+    /// the given time, most flaky first
+    pub fn job_flakiness(
+        &self,
+        project_id: &ProjectId,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<JobFlakiness>> {
+        let mut query = self.conn.prepare_cached(&format!(
+            "SELECT name, SUM(failed AND succeeded) AS flaky, SUM(failed)
+             FROM (
+                 SELECT name, MAX(status IN {RED}) AS failed, MAX(status = 'success') AS succeeded
+                 FROM jobs
+                 WHERE project = ?1 AND finished_at >= ?2 AND head_sha IS NOT NULL
+                 GROUP BY name, head_sha
+             )
+             GROUP BY name
+             HAVING flaky > 0
+             ORDER BY flaky * 1.0 / SUM(failed) DESC, flaky DESC, name"
+        ))?;
+
+        let flakiness = query
+            .query_map(params![project_id.to_string(), since], |row| {
+                Ok(JobFlakiness {
+                    name: row.get::<_, String>(0)?.into(),
+                    flaky_commits: row.get(1)?,
+                    failed_commits: row.get(2)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(flakiness)
+    }
+
+    /// Runs of the project on the branch created since the given time, oldest first
+    pub fn branch_runs(
+        &self,
+        project_id: &ProjectId,
+        branch: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<RecordedRun>> {
+        let mut query = self.conn.prepare_cached(
+            "SELECT workflow, head_sha, status, created_at, finished_at, duration_secs
+             FROM runs
+             WHERE project = ?1 AND branch = ?2 AND created_at >= ?3
+             ORDER BY created_at, attempt",
+        )?;
+
+        let runs = query
+            .query_map(params![project_id.to_string(), branch, since], |row| {
+                Ok(RecordedRun {
+                    workflow: row.get::<_, String>(0)?.into(),
+                    head_sha: row.get::<_, Option<String>>(1)?.map(Into::into),
+                    status: PipelineStatus::from_api_str(&row.get::<_, String>(2)?),
+                    created_at: row.get(3)?,
+                    finished_at: row.get(4)?,
+                    duration: Duration::seconds(row.get(5)?),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(runs)
+    }
+
+    /// Completion of the earliest recorded run, if any
+    pub fn recorded_since(&self) -> Result<Option<DateTime<Utc>>> {
+        let since = self
+            .conn
+            .query_row("SELECT MIN(finished_at) FROM runs", [], |row| row.get(0))
+            .optional()?
+            .flatten();
+
+        Ok(since)
+    }
+}
+
+/// Work handed to the run history thread
+enum HistoryRequest {
+    Record(Box<GlomEvent>),
+    JobFlakiness(ProjectId),
+    Report(ProjectId),
+    Metrics(ProjectId, CompactString),
+}
+
+/// Runs the run history on a dedicated thread, so its transactions and queries never
+/// stall the UI; query results are dispatched as events, like API results
+#[derive(Debug)]
+pub struct RunHistoryWorker {
+    requests: mpsc::Sender<HistoryRequest>,
+    _handler: thread::JoinHandle<()>,
+}
+
+impl RunHistoryWorker {
+    pub fn spawn(mut history: RunHistory, sender: mpsc::Sender<GlomEvent>) -> Self {
+        let (requests, receiver) = mpsc::channel();
+
+        let handler = thread::spawn(move || {
+            for request in receiver {
+                match request {
+                    HistoryRequest::Record(event) => history.apply(&event),
+                    HistoryRequest::JobFlakiness(project_id) => {
+                        match history.job_flakiness(&project_id, Utc::now() - FLAKINESS_WINDOW) {
+                            Ok(flakiness) => sender
+                                .dispatch(GlomEvent::JobFlakinessLoaded(project_id, flakiness)),
+                            Err(e) => warn!(error = %e, "Failed to query job flakiness"),
+                        }
+                    },
+                    HistoryRequest::Report(project_id) => {
+                        match history.report(&project_id, month_start()) {
+                            Ok(report) => {
+                                sender.dispatch(GlomEvent::HistoryLoaded(project_id, report))
+                            },
+                            Err(e) => sender.dispatch(GlomEvent::AppError(e)),
+                        }
+                    },
+                    HistoryRequest::Metrics(project_id, branch) => {
+                        let since = MetricsReport::oldest_window_start();
+                        match history.branch_runs(&project_id, &branch, since) {
+                            Ok(runs) => {
+                                let report = MetricsReport::new(project_id.clone(), branch, &runs);
+                                sender.dispatch(GlomEvent::MetricsLoaded(project_id, report))
+                            },
+                            Err(e) => sender.dispatch(GlomEvent::AppError(e)),
+                        }
+                    },
+                }
+            }
+            debug!("Run history thread stopped");
+        });
+
+        Self { requests, _handler: handler }
+    }
+
+    /// Records the runs and jobs carried by the event which have completed
+    pub fn apply(&self, event: &GlomEvent) {
+        if matches!(
+            event,
+            GlomEvent::PipelinesLoaded(_) | GlomEvent::JobsLoaded(..)
+        ) {
+            self.send(HistoryRequest::Record(Box::new(event.clone())));
+        }
+    }
+
+    /// Queries the flakiness of the project's jobs, dispatching `JobFlakinessLoaded`
+    pub fn fetch_job_flakiness(&self, project_id: ProjectId) {
+        self.send(HistoryRequest::JobFlakiness(project_id));
+    }
+
+    /// Queries the project's report for this month, dispatching `HistoryLoaded`
+    pub fn fetch_report(&self, project_id: ProjectId) {
+        self.send(HistoryRequest::Report(project_id));
+    }
+
+    /// Queries the delivery metrics of the project's branch, dispatching `MetricsLoaded`
+    pub fn fetch_metrics(&self, project_id: ProjectId, branch: CompactString) {
+        self.send(HistoryRequest::Metrics(project_id, branch));
+    }
+
+    fn send(&self, request: HistoryRequest) {
+        if self.requests.send(request).is_err() {
+            warn!("Run history thread is gone");
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use compact_str::CompactString;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Default, Clone, Copy, Eq, PartialEq, PartialOrd, Ord)]
 pub struct JobId {
@@ -12,7 +12,7 @@ pub struct ProjectId {
     value: CompactString,
 }
 
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct PipelineId {
     value: u64,
 }
@@ -21,6 +21,13 @@ impl ProjectId {
     pub fn new<S: Into<CompactString>>(id: S) -> Self {
         Self { value: id.into() }
     }
+
+    /// The user or organization owning the repository
+    pub fn owner(&self) -> &str {
+        self.value
+            .split_once('/')
+            .map_or(self.value.as_str(), |(owner, _)| owner)
+    }
 }
 
 impl PipelineId {
@@ -105,6 +112,33 @@ impl<'de> Deserialize<'de> for JobId {
     }
 }
 
+impl Serialize for ProjectId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.value)
+    }
+}
+
+impl Serialize for PipelineId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.value)
+    }
+}
+
+impl Serialize for JobId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(self.value)
+    }
+}
+
 impl std::fmt::Display for ProjectId {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", self.value)
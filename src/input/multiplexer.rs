@@ -4,7 +4,11 @@ use crate::{
     event::GlomEvent,
     input::{
         InputProcessor,
-        processor::{ConfigProcessor, PipelineActionsProcessor, ProjectDetailsProcessor},
+        processor::{
+            CachesProcessor, ConfigProcessor, EnvironmentsProcessor, HistoryProcessor,
+            MetricsProcessor, PipelineActionsProcessor, ProjectDetailsProcessor,
+            PullRequestsProcessor, RunFilterProcessor, RunnersProcessor,
+        },
     },
     ui::StatefulWidgets,
 };
@@ -50,6 +54,66 @@ impl InputMultiplexer {
             },
             GlomEvent::PipelineActionsClose => self.pop_processor(),
 
+            // run filter popup
+            GlomEvent::RunFilterOpen(_) => {
+                self.push(Box::new(RunFilterProcessor::new(self.sender.clone())));
+            },
+            GlomEvent::RunFilterClose => self.pop_processor(),
+
+            // caches popup
+            GlomEvent::CachesOpen(id) => {
+                self.push(Box::new(CachesProcessor::new(
+                    self.sender.clone(),
+                    id.clone(),
+                )));
+            },
+            GlomEvent::CachesClose => self.pop_processor(),
+
+            // pull requests popup
+            GlomEvent::PullRequestsOpen(id) => {
+                self.push(Box::new(PullRequestsProcessor::new(
+                    self.sender.clone(),
+                    id.clone(),
+                )));
+            },
+            GlomEvent::PullRequestsClose => self.pop_processor(),
+
+            // environments popup
+            GlomEvent::EnvironmentsOpen(id) => {
+                self.push(Box::new(EnvironmentsProcessor::new(
+                    self.sender.clone(),
+                    id.clone(),
+                )));
+            },
+            GlomEvent::EnvironmentsClose => self.pop_processor(),
+
+            // run history popup
+            GlomEvent::HistoryOpen(id) => {
+                self.push(Box::new(HistoryProcessor::new(
+                    self.sender.clone(),
+                    id.clone(),
+                )));
+            },
+            GlomEvent::HistoryClose => self.pop_processor(),
+
+            // delivery metrics popup
+            GlomEvent::MetricsOpen(id) => {
+                self.push(Box::new(MetricsProcessor::new(
+                    self.sender.clone(),
+                    id.clone(),
+                )));
+            },
+            GlomEvent::MetricsClose => self.pop_processor(),
+
+            // runners popup
+            GlomEvent::RunnersOpen(id) => {
+                self.push(Box::new(RunnersProcessor::new(
+                    self.sender.clone(),
+                    id.clone(),
+                )));
+            },
+            GlomEvent::RunnersClose => self.pop_processor(),
+
             // config
             GlomEvent::ConfigOpen => {
                 self.push(Box::new(ConfigProcessor::new(self.sender.clone())));
@@ -0,0 +1,87 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent};
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{
+    dispatcher::Dispatcher,
+    event::GlomEvent,
+    id::ProjectId,
+    input::InputProcessor,
+    ui::{StatefulWidgets, popup::CachesMode},
+};
+
+pub struct CachesProcessor {
+    sender: Sender<GlomEvent>,
+    project_id: ProjectId,
+}
+
+impl CachesProcessor {
+    pub fn new(sender: Sender<GlomEvent>, project_id: ProjectId) -> Self {
+        Self { sender, project_id }
+    }
+
+    fn process(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        let Some(popup) = ui.caches.as_mut() else {
+            return;
+        };
+
+        match popup.mode {
+            CachesMode::Browse => self.process_browse(event, ui),
+            CachesMode::KeyPrefixInput(_) => match event.code {
+                KeyCode::Enter => popup.submit_prefix(),
+                KeyCode::Esc => popup.cancel(),
+                _ => {
+                    if let Some(input) = popup.prefix_input_mut() {
+                        input.handle_event(&CrosstermEvent::Key(*event));
+                    }
+                },
+            },
+            CachesMode::Confirm(_) => match event.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    if let Some(deletion) = popup.confirm() {
+                        self.sender
+                            .dispatch(GlomEvent::CacheDelete(self.project_id.clone(), deletion));
+                    }
+                },
+                KeyCode::Char('n') | KeyCode::Esc => popup.cancel(),
+                _ => (),
+            },
+            CachesMode::Deleting => {
+                if event.code == KeyCode::Esc {
+                    self.sender.dispatch(GlomEvent::CachesClose)
+                }
+            },
+        }
+    }
+
+    fn process_browse(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => self.sender.dispatch(GlomEvent::CachesClose),
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::CachesClose),
+            KeyCode::Up => ui.handle_cache_selection(-1),
+            KeyCode::Down => ui.handle_cache_selection(1),
+            KeyCode::Char('k') => ui.handle_cache_selection(-1),
+            KeyCode::Char('j') => ui.handle_cache_selection(1),
+            KeyCode::Char('s') => ui.caches.as_mut().unwrap().toggle_sort(),
+            KeyCode::Char('d') => ui.caches.as_mut().unwrap().delete_selected(),
+            KeyCode::Char('D') => ui.caches.as_mut().unwrap().delete_by_prefix(),
+            KeyCode::Char('r') => self
+                .sender
+                .dispatch(GlomEvent::CachesFetch(self.project_id.clone())),
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+}
+
+impl InputProcessor for CachesProcessor {
+    fn apply(&mut self, event: &GlomEvent, ui: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(e) = event {
+            self.process(e, ui)
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
@@ -0,0 +1,55 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    dispatcher::Dispatcher, event::GlomEvent, id::ProjectId, input::InputProcessor,
+    ui::StatefulWidgets,
+};
+
+pub struct EnvironmentsProcessor {
+    sender: Sender<GlomEvent>,
+    project_id: ProjectId,
+}
+
+impl EnvironmentsProcessor {
+    pub fn new(sender: Sender<GlomEvent>, project_id: ProjectId) -> Self {
+        Self { sender, project_id }
+    }
+
+    fn process(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => self.sender.dispatch(GlomEvent::EnvironmentsClose),
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::EnvironmentsClose),
+            KeyCode::Up => ui.handle_environment_selection(-1),
+            KeyCode::Down => ui.handle_environment_selection(1),
+            KeyCode::Char('k') => ui.handle_environment_selection(-1),
+            KeyCode::Char('j') => ui.handle_environment_selection(1),
+            KeyCode::Char('r') => self
+                .sender
+                .dispatch(GlomEvent::EnvironmentsFetch(self.project_id.clone())),
+            KeyCode::Enter => {
+                if let Some(event) = ui
+                    .environments
+                    .as_ref()
+                    .and_then(|e| e.selected_run_url_event())
+                {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+}
+
+impl InputProcessor for EnvironmentsProcessor {
+    fn apply(&mut self, event: &GlomEvent, ui: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(e) = event {
+            self.process(e, ui)
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
@@ -0,0 +1,55 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    dispatcher::Dispatcher, event::GlomEvent, id::ProjectId, input::InputProcessor,
+    ui::StatefulWidgets,
+};
+
+pub struct HistoryProcessor {
+    sender: Sender<GlomEvent>,
+    project_id: ProjectId,
+}
+
+impl HistoryProcessor {
+    pub fn new(sender: Sender<GlomEvent>, project_id: ProjectId) -> Self {
+        Self { sender, project_id }
+    }
+
+    fn process(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => self.sender.dispatch(GlomEvent::HistoryClose),
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::HistoryClose),
+            KeyCode::Up => ui.handle_history_selection(-1),
+            KeyCode::Down => ui.handle_history_selection(1),
+            KeyCode::Char('k') => ui.handle_history_selection(-1),
+            KeyCode::Char('j') => ui.handle_history_selection(1),
+            KeyCode::Char('r') => self
+                .sender
+                .dispatch(GlomEvent::HistoryFetch(self.project_id.clone())),
+            KeyCode::Enter => {
+                if let Some(event) = ui
+                    .history
+                    .as_ref()
+                    .and_then(|h| h.selected_run_url_event())
+                {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+}
+
+impl InputProcessor for HistoryProcessor {
+    fn apply(&mut self, event: &GlomEvent, ui: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(e) = event {
+            self.process(e, ui)
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
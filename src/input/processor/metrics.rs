@@ -0,0 +1,47 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    dispatcher::Dispatcher, event::GlomEvent, id::ProjectId, input::InputProcessor,
+    ui::StatefulWidgets,
+};
+
+pub struct MetricsProcessor {
+    sender: Sender<GlomEvent>,
+    project_id: ProjectId,
+}
+
+impl MetricsProcessor {
+    pub fn new(sender: Sender<GlomEvent>, project_id: ProjectId) -> Self {
+        Self { sender, project_id }
+    }
+
+    fn process(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => self.sender.dispatch(GlomEvent::MetricsClose),
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::MetricsClose),
+            KeyCode::Char('r') => self
+                .sender
+                .dispatch(GlomEvent::MetricsFetch(self.project_id.clone())),
+            KeyCode::Char('y') => {
+                if let Some(event) = ui.metrics.as_ref().and_then(|m| m.export_event()) {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+}
+
+impl InputProcessor for MetricsProcessor {
+    fn apply(&mut self, event: &GlomEvent, ui: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(e) = event {
+            self.process(e, ui)
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
@@ -1,9 +1,23 @@
+mod caches;
 mod config;
+mod environments;
+mod history;
+mod metrics;
 mod normal;
 mod pipeline_actions;
 mod project_details;
+mod pull_requests;
+mod run_filter;
+mod runners;
 
+pub use caches::*;
 pub use config::*;
+pub use environments::*;
+pub use history::*;
+pub use metrics::*;
 pub use normal::*;
 pub use pipeline_actions::*;
 pub use project_details::*;
+pub use pull_requests::*;
+pub use run_filter::*;
+pub use runners::*;
@@ -18,7 +18,7 @@ impl NormalModeProcessor {
         Self { sender, selected: None }
     }
 
-    fn process(&self, event: &KeyEvent) {
+    fn process(&self, event: &KeyEvent, ui: &StatefulWidgets) {
         if let Some(e) = match event.code {
             KeyCode::Enter if self.selected.is_some() => Some(GlomEvent::ProjectDetailsOpen(
                 self.selected.clone().unwrap(),
@@ -36,6 +36,13 @@ impl NormalModeProcessor {
                 .map(GlomEvent::PipelinesFetch),
             KeyCode::Char('q') => Some(GlomEvent::AppExit),
             KeyCode::Char('r') => Some(GlomEvent::ProjectsFetch),
+            KeyCode::Char('P') => self
+                .selected
+                .clone()
+                .map(GlomEvent::PullRequestsOpen),
+            KeyCode::Char('R') => self.selected.clone().map(GlomEvent::RunnersOpen),
+            KeyCode::Char('u') => Some(GlomEvent::WatchClear),
+            KeyCode::Char('x') => Some(GlomEvent::DashboardExport(ui.temporary_filter.clone())),
             KeyCode::Char('w') => self
                 .selected
                 .clone()
@@ -82,7 +89,7 @@ impl InputProcessor for NormalModeProcessor {
                 if ui.filter_input_active {
                     self.process_filter_input(e, ui);
                 } else {
-                    self.process(e);
+                    self.process(e, ui);
                 }
             },
             _ => (),
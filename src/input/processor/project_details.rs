@@ -47,6 +47,30 @@ impl ProjectDetailsProcessor {
                         self.selected.unwrap(),
                     ))
             },
+            KeyCode::Char('w') if self.selected.is_some() => self.sender.dispatch(
+                GlomEvent::WatchRunToggle(self.project_id.clone(), self.selected.unwrap()),
+            ),
+            KeyCode::Char('W') if self.selected.is_some() => self.sender.dispatch(
+                GlomEvent::WatchBranchToggle(self.project_id.clone(), self.selected.unwrap()),
+            ),
+            KeyCode::Char('f') => self
+                .sender
+                .dispatch(GlomEvent::RunFilterOpen(self.project_id.clone())),
+            KeyCode::Char('c') => self
+                .sender
+                .dispatch(GlomEvent::CachesOpen(self.project_id.clone())),
+            KeyCode::Char('p') => self
+                .sender
+                .dispatch(GlomEvent::PullRequestsOpen(self.project_id.clone())),
+            KeyCode::Char('e') => self
+                .sender
+                .dispatch(GlomEvent::EnvironmentsOpen(self.project_id.clone())),
+            KeyCode::Char('h') => self
+                .sender
+                .dispatch(GlomEvent::HistoryOpen(self.project_id.clone())),
+            KeyCode::Char('m') => self
+                .sender
+                .dispatch(GlomEvent::MetricsOpen(self.project_id.clone())),
             KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
             _ => (),
         }
@@ -0,0 +1,104 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    dispatcher::Dispatcher,
+    event::GlomEvent,
+    id::ProjectId,
+    input::InputProcessor,
+    ui::{StatefulWidgets, popup::PullRequestsFocus},
+};
+
+pub struct PullRequestsProcessor {
+    sender: Sender<GlomEvent>,
+    project_id: ProjectId,
+}
+
+impl PullRequestsProcessor {
+    pub fn new(sender: Sender<GlomEvent>, project_id: ProjectId) -> Self {
+        Self { sender, project_id }
+    }
+
+    fn process(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        let Some(popup) = ui.pull_requests.as_ref() else {
+            return;
+        };
+
+        match popup.focus {
+            PullRequestsFocus::PullRequests => self.process_pull_requests(event, ui),
+            PullRequestsFocus::Runs => self.process_runs(event, ui),
+        }
+    }
+
+    fn process_pull_requests(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => self.sender.dispatch(GlomEvent::PullRequestsClose),
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::PullRequestsClose),
+            KeyCode::Up => ui.handle_pull_request_selection(-1),
+            KeyCode::Down => ui.handle_pull_request_selection(1),
+            KeyCode::Char('k') => ui.handle_pull_request_selection(-1),
+            KeyCode::Char('j') => ui.handle_pull_request_selection(1),
+            KeyCode::Char('r') => self
+                .sender
+                .dispatch(GlomEvent::PullRequestsFetch(self.project_id.clone())),
+            KeyCode::Enter => {
+                if let Some(event) = ui
+                    .pull_requests
+                    .as_mut()
+                    .and_then(|p| p.show_selected_runs())
+                {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::Char('w') => {
+                if let Some(event) = ui
+                    .pull_requests
+                    .as_ref()
+                    .and_then(|p| p.selected_pull_request_url_event())
+                {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+
+    fn process_runs(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => {
+                if let Some(p) = ui.pull_requests.as_mut() {
+                    p.focus_pull_requests()
+                }
+            },
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::PullRequestsClose),
+            KeyCode::Up => ui.handle_pull_request_run_selection(-1),
+            KeyCode::Down => ui.handle_pull_request_run_selection(1),
+            KeyCode::Char('k') => ui.handle_pull_request_run_selection(-1),
+            KeyCode::Char('j') => ui.handle_pull_request_run_selection(1),
+            KeyCode::Enter => {
+                if let Some(event) = ui
+                    .pull_requests
+                    .as_ref()
+                    .and_then(|p| p.selected_run_url_event())
+                {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+}
+
+impl InputProcessor for PullRequestsProcessor {
+    fn apply(&mut self, event: &GlomEvent, ui: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(e) = event {
+            self.process(e, ui)
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
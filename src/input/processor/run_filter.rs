@@ -0,0 +1,48 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{Event as CrosstermEvent, KeyCode};
+use tui_input::backend::crossterm::EventHandler;
+
+use crate::{dispatcher::Dispatcher, event::GlomEvent, input::InputProcessor, ui::StatefulWidgets};
+
+pub struct RunFilterProcessor {
+    sender: Sender<GlomEvent>,
+}
+
+impl RunFilterProcessor {
+    pub fn new(sender: Sender<GlomEvent>) -> Self {
+        Self { sender }
+    }
+}
+
+impl InputProcessor for RunFilterProcessor {
+    fn apply(&mut self, event: &GlomEvent, widgets: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(code) = event {
+            let Some(popup) = widgets.run_filter.as_mut() else {
+                return;
+            };
+
+            match code.code {
+                KeyCode::Enter => self.sender.dispatch(GlomEvent::RunFilterApply),
+                KeyCode::Esc => self.sender.dispatch(GlomEvent::RunFilterClose),
+                KeyCode::Down => popup.select_next_input(),
+                KeyCode::Up => popup.select_previous_input(),
+                KeyCode::Tab => popup.select_next_input(),
+                KeyCode::BackTab => popup.select_previous_input(),
+                KeyCode::Left if popup.is_current_field_dropdown() => popup.cycle_dropdown_prev(),
+                KeyCode::Right if popup.is_current_field_dropdown() => popup.cycle_dropdown_next(),
+                KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+                _ => {
+                    if !popup.is_current_field_dropdown() {
+                        popup
+                            .input_mut()
+                            .handle_event(&CrosstermEvent::Key(*code));
+                    }
+                },
+            }
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
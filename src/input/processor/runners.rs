@@ -0,0 +1,55 @@
+use std::sync::mpsc::Sender;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::{
+    dispatcher::Dispatcher, event::GlomEvent, id::ProjectId, input::InputProcessor,
+    ui::StatefulWidgets,
+};
+
+pub struct RunnersProcessor {
+    sender: Sender<GlomEvent>,
+    project_id: ProjectId,
+}
+
+impl RunnersProcessor {
+    pub fn new(sender: Sender<GlomEvent>, project_id: ProjectId) -> Self {
+        Self { sender, project_id }
+    }
+
+    fn process(&self, event: &KeyEvent, ui: &mut StatefulWidgets) {
+        match event.code {
+            KeyCode::Esc => self.sender.dispatch(GlomEvent::RunnersClose),
+            KeyCode::Char('q') => self.sender.dispatch(GlomEvent::RunnersClose),
+            KeyCode::Up => ui.handle_runner_selection(-1),
+            KeyCode::Down => ui.handle_runner_selection(1),
+            KeyCode::Char('k') => ui.handle_runner_selection(-1),
+            KeyCode::Char('j') => ui.handle_runner_selection(1),
+            KeyCode::Char('r') => self
+                .sender
+                .dispatch(GlomEvent::RunnersFetch(self.project_id.clone())),
+            KeyCode::Enter => {
+                if let Some(event) = ui
+                    .runners
+                    .as_ref()
+                    .and_then(|r| r.selected_job_url_event())
+                {
+                    self.sender.dispatch(event)
+                }
+            },
+            KeyCode::F(12) => self.sender.dispatch(GlomEvent::ScreenCapture),
+            _ => (),
+        }
+    }
+}
+
+impl InputProcessor for RunnersProcessor {
+    fn apply(&mut self, event: &GlomEvent, ui: &mut StatefulWidgets) {
+        if let GlomEvent::InputKey(e) = event {
+            self.process(e, ui)
+        }
+    }
+
+    fn on_pop(&self) {}
+    fn on_push(&self) {}
+}
@@ -1,9 +1,12 @@
 use std::{path::PathBuf, process::exit};
 
 use clap::Parser;
+use compact_str::CompactString;
 
 use crate::{
     app_init::{AppComponents, initialize_app},
+    cli::Command,
+    client::FixtureSource,
     config::default_config_path,
     glom_app::GlomConfig,
     rendering::render_main_ui,
@@ -11,24 +14,36 @@ use crate::{
 };
 
 mod app_init;
+mod cli;
 mod client;
 mod config;
 mod dispatcher;
 mod domain;
+mod durations;
 mod effect_registry;
 mod event;
+mod export;
+mod git;
 mod glom_app;
 mod gruvbox;
+mod headless;
+mod history;
 mod id;
 mod input;
 mod logging;
+mod metrics;
 mod notice_service;
+mod notify;
+mod prometheus;
 mod rendering;
 mod result;
+mod snapshot;
 mod stores;
 mod theme;
 mod tui;
 mod ui;
+mod wait;
+mod watch;
 
 /// A TUI for monitoring GitHub CI/CD pipelines and projects
 #[derive(Parser, Debug)]
@@ -40,6 +55,20 @@ pub struct Args {
     /// Print the path to the configuration file and exit.
     #[arg(short, long)]
     print_config_path: bool,
+    /// Serve Prometheus metrics on this address, e.g. 127.0.0.1:9184.
+    #[arg(long, value_name = "ADDR")]
+    metrics_listen: Option<CompactString>,
+    /// Poll without the TUI, serving the Prometheus metrics or posting to `notify_webhook_url`.
+    #[arg(long)]
+    headless: bool,
+    /// Answer from bundled demo repos instead of GitHub, with runs progressing over time.
+    #[arg(long, conflicts_with = "fixtures")]
+    demo: bool,
+    /// Answer from the JSON responses in this directory instead of GitHub, e.g. `glom-logs`.
+    #[arg(long, value_name = "DIR")]
+    fixtures: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
 fn main() -> Result<()> {
@@ -51,14 +80,30 @@ fn main() -> Result<()> {
         exit(0);
     }
 
-    let debug = std::env::var("GLOM_DEBUG").is_ok();
-
-    let config = if config_path.exists() {
+    let mut config: GlomConfig = if config_path.exists() {
         confy::load_path(&config_path)
             .map_err(|e| crate::result::GlomError::config_load_error(config_path.clone(), e))?
     } else {
         GlomConfig::default()
     };
+    if args.metrics_listen.is_some() {
+        config.metrics_listen = args.metrics_listen;
+    }
+
+    let fixtures = match args.demo {
+        true => Some(FixtureSource::Demo),
+        false => args.fixtures.map(FixtureSource::Directory),
+    };
+
+    if let Some(command) = args.command {
+        exit(cli::run(command, &config, fixtures));
+    }
+
+    let debug = std::env::var("GLOM_DEBUG").is_ok();
+
+    if args.headless {
+        return headless::run_headless(config, debug, fixtures);
+    }
 
     // Create a shared runtime for async operations
     let rt = tokio::runtime::Runtime::new().map_err(|e| {
@@ -71,8 +116,10 @@ fn main() -> Result<()> {
         mut widget_states,
         mut effects,
         poller,
+        webhook,
+        exporter,
         _log_guard,
-    } = rt.block_on(async { initialize_app(config_path, config, debug).await })?;
+    } = rt.block_on(async { initialize_app(config_path, config, debug, fixtures).await })?;
 
     // Start the poller in the background
     rt.spawn(async move {
@@ -81,6 +128,24 @@ fn main() -> Result<()> {
         }
     });
 
+    // Start the webhook receiver in the background, if configured
+    if let Some(webhook) = webhook {
+        rt.spawn(async move {
+            if let Err(e) = webhook.start().await {
+                tracing::error!("Webhook receiver failed: {}", e);
+            }
+        });
+    }
+
+    // Start the Prometheus exporter in the background, if configured
+    if let Some(exporter) = exporter {
+        rt.spawn(async move {
+            if let Err(e) = exporter.start().await {
+                tracing::error!("Prometheus exporter failed: {}", e);
+            }
+        });
+    }
+
     while app.is_running() {
         widget_states.last_frame = app.process_timers();
         tui.receive_events(|event| {
@@ -0,0 +1,169 @@
+//! Delivery metrics of a project's default branch, computed from the run history
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+use compact_str::{CompactString, format_compact};
+use serde::Serialize;
+
+use crate::{domain::PipelineStatus, export::csv_field, history::RecordedRun, id::ProjectId};
+
+/// Windows, in days, over which the metrics are computed
+pub const METRICS_WINDOWS: [i64; 2] = [7, 30];
+
+/// CI health of a branch over a window
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveryMetrics {
+    pub window_days: i64,
+    /// Completed runs, excluding cancelled and skipped ones
+    pub runs: u32,
+    /// Share of successful runs, in percent
+    pub success_rate: Option<f64>,
+    /// Mean time from the first failing run of a workflow to its next successful run
+    pub mttr_secs: Option<i64>,
+    /// Share of commits with at least one failing run, in percent
+    pub change_failure_rate: Option<f64>,
+    pub median_duration_secs: Option<i64>,
+}
+
+/// Delivery metrics of a project's default branch, for each window
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricsReport {
+    pub project: ProjectId,
+    pub branch: CompactString,
+    pub generated_at: DateTime<Utc>,
+    pub windows: Vec<DeliveryMetrics>,
+}
+
+impl MetricsReport {
+    /// Computes the metrics of each window from the branch's runs, oldest first
+    pub fn new(project: ProjectId, branch: CompactString, runs: &[RecordedRun]) -> Self {
+        let now = Utc::now();
+        let windows = METRICS_WINDOWS
+            .iter()
+            .map(|days| DeliveryMetrics::compute(*days, runs, now - Duration::days(*days)))
+            .collect();
+
+        Self { project, branch, generated_at: now, windows }
+    }
+
+    /// Start of the longest window, which includes the others
+    pub fn oldest_window_start() -> DateTime<Utc> {
+        let days = METRICS_WINDOWS
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or_default();
+        Utc::now() - Duration::days(days)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let optional = |value: Option<String>| value.unwrap_or_default();
+
+        let mut csv = String::from(
+            "project,branch,window_days,runs,success_rate,mttr_secs,change_failure_rate,median_duration_secs\n",
+        );
+        for m in &self.windows {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{}\n",
+                csv_field(&self.project.to_string()),
+                csv_field(&self.branch),
+                m.window_days,
+                m.runs,
+                optional(m.success_rate.map(|r| format!("{r:.1}"))),
+                optional(m.mttr_secs.map(|s| s.to_string())),
+                optional(m.change_failure_rate.map(|r| format!("{r:.1}"))),
+                optional(m.median_duration_secs.map(|s| s.to_string())),
+            ));
+        }
+
+        csv
+    }
+}
+
+impl DeliveryMetrics {
+    fn compute(window_days: i64, runs: &[RecordedRun], since: DateTime<Utc>) -> Self {
+        let runs: Vec<&RecordedRun> = runs
+            .iter()
+            .filter(|r| r.created_at >= since)
+            .filter(|r| is_red(&r.status) || r.status == PipelineStatus::Success)
+            .collect();
+
+        let successes = runs
+            .iter()
+            .filter(|r| r.status == PipelineStatus::Success)
+            .count();
+
+        Self {
+            window_days,
+            runs: runs.len() as u32,
+            success_rate: percent(successes, runs.len()),
+            mttr_secs: Self::mean_time_to_recovery(&runs).map(|d| d.num_seconds()),
+            change_failure_rate: Self::change_failure_rate(&runs),
+            median_duration_secs: Self::median_run_time(&runs).map(|d| d.num_seconds()),
+        }
+    }
+
+    /// Mean time from the completion of the first failing run of a workflow to the
+    /// completion of its next successful run
+    fn mean_time_to_recovery(runs: &[&RecordedRun]) -> Option<Duration> {
+        let mut red_since: HashMap<&str, DateTime<Utc>> = HashMap::new();
+        let mut recoveries = Vec::new();
+        for run in runs {
+            if is_red(&run.status) {
+                red_since
+                    .entry(run.workflow.as_str())
+                    .or_insert(run.finished_at);
+            } else if let Some(since) = red_since.remove(run.workflow.as_str()) {
+                recoveries.push(run.finished_at - since);
+            }
+        }
+
+        let total: Duration = recoveries.iter().copied().sum();
+        (!recoveries.is_empty()).then(|| total / recoveries.len() as i32)
+    }
+
+    fn change_failure_rate(runs: &[&RecordedRun]) -> Option<f64> {
+        let mut commits = HashSet::new();
+        let mut failed = HashSet::new();
+        for run in runs {
+            let Some(sha) = run.head_sha.as_deref() else {
+                continue;
+            };
+            commits.insert(sha);
+            if is_red(&run.status) {
+                failed.insert(sha);
+            }
+        }
+
+        percent(failed.len(), commits.len())
+    }
+
+    fn median_run_time(runs: &[&RecordedRun]) -> Option<Duration> {
+        let mut durations: Vec<Duration> = runs.iter().map(|r| r.duration).collect();
+        durations.sort();
+        durations.get(durations.len() / 2).copied()
+    }
+
+    pub fn mttr(&self) -> Option<Duration> {
+        self.mttr_secs.map(Duration::seconds)
+    }
+
+    pub fn median_duration(&self) -> Option<Duration> {
+        self.median_duration_secs.map(Duration::seconds)
+    }
+}
+
+/// Statuses turning a branch red, as in the run history
+fn is_red(status: &PipelineStatus) -> bool {
+    matches!(status, PipelineStatus::Failure | PipelineStatus::TimedOut)
+}
+
+fn percent(count: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| count as f64 * 100.0 / total as f64)
+}
+
+/// Percentage with one decimal, or `-` when there were no runs
+pub fn format_percent(value: Option<f64>) -> CompactString {
+    value.map_or_else(|| "-".into(), |v| format_compact!("{v:.1}%"))
+}
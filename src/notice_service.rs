@@ -1,9 +1,10 @@
 use std::collections::VecDeque;
 
-use compact_str::CompactString;
+use compact_str::{CompactString, format_compact};
 use serde_json::error::Category;
 
 use crate::{
+    durations::DurationRegression,
     event::GlomEvent,
     id::{JobId, PipelineId, ProjectId},
     result::GlomError,
@@ -45,6 +46,7 @@ pub enum NoticeMessage {
     #[allow(dead_code)]
     GithubGetPipelinesError(ProjectId, PipelineId, CompactString),
     LogLevelChanged(tracing::Level),
+    RunDurationRegressed(DurationRegression),
 }
 
 impl NoticeService {
@@ -77,6 +79,9 @@ impl NoticeService {
                     format!("Connection test failed: {message}").into(),
                 )),
                 GlomError::GeneralError(s) => Some(NoticeMessage::GeneralMessage(s)),
+                GlomError::HistoryError(s) => Some(NoticeMessage::GeneralMessage(format_compact!(
+                    "Run history: {s}"
+                ))),
                 GlomError::JsonDeserializeError(cat, json) => {
                     Some(NoticeMessage::JsonDeserializeError(cat, json))
                 },
@@ -96,9 +101,31 @@ impl NoticeService {
                 NoticeLevel::Info,
                 NoticeMessage::GeneralMessage("Job log downloaded".into()),
             ),
+            GlomEvent::MetricsExport(_) => self.push_notice(
+                NoticeLevel::Info,
+                NoticeMessage::GeneralMessage("Delivery metrics copied to clipboard as CSV".into()),
+            ),
+            GlomEvent::DashboardExported(format, path) => {
+                let message = match path {
+                    Some(path) => format!(
+                        "Dashboard exported to {} as {}",
+                        path.display(),
+                        format.label()
+                    ),
+                    None => format!("Dashboard copied to clipboard as {}", format.label()),
+                };
+                self.push_notice(
+                    NoticeLevel::Info,
+                    NoticeMessage::GeneralMessage(message.into()),
+                )
+            },
             GlomEvent::ScreenCaptureToClipboard(_) => {
                 self.push_notice(NoticeLevel::Info, NoticeMessage::ScreenCaptured)
             },
+            GlomEvent::RunDurationRegressed(r) => self.push_notice(
+                NoticeLevel::Info,
+                NoticeMessage::RunDurationRegressed(r.clone()),
+            ),
             GlomEvent::LogLevelChanged(new_level) => self.push_notice(
                 NoticeLevel::Info,
                 NoticeMessage::LogLevelChanged(*new_level),
@@ -0,0 +1,135 @@
+//! Desktop and terminal notifications when a run completes, e.g. while switched
+//! away from glom
+
+mod webhook;
+
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use compact_str::CompactString;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::{
+    domain::Pipeline,
+    glom_app::GlomConfig,
+    result::{GlomError, Result},
+};
+
+pub use webhook::WebhookNotifier;
+
+/// Escape sequence asking the terminal emulator for a desktop notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminalNotification {
+    /// `OSC 9`, e.g. iTerm2, Windows Terminal, kitty, WezTerm
+    Osc9,
+    /// `OSC 777`, e.g. foot, urxvt, Ghostty and VTE based terminals
+    Osc777,
+}
+
+/// Notifies of runs changing to success or failure, as configured
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    terminal: Option<TerminalNotification>,
+    bell: bool,
+    /// Run by `sh -c`, with the run in `GLOM_*` environment variables
+    command: Option<CompactString>,
+}
+
+impl Notifier {
+    pub fn new(config: &GlomConfig) -> Self {
+        Self {
+            terminal: config.notify_terminal,
+            bell: config.notify_bell,
+            command: config.notify_command.clone(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.terminal.is_some() || self.bell || self.command.is_some()
+    }
+
+    pub fn notify(&self, pipeline: &Pipeline) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        debug!(project_id = %pipeline.project_id, run_id = %pipeline.id, "Notifying of completed run");
+
+        let title = format!("{} {}", pipeline.project_id, pipeline.status.as_api_str());
+        let body = format!("{} on {}", pipeline.name, pipeline.branch);
+
+        let mut out = Vec::new();
+        match self.terminal {
+            Some(TerminalNotification::Osc9) => {
+                let _ = write!(out, "\x1b]9;{}: {}\x07", sanitize(&title), sanitize(&body));
+            },
+            Some(TerminalNotification::Osc777) => {
+                let _ = write!(
+                    out,
+                    "\x1b]777;notify;{};{}\x07",
+                    sanitize(&title),
+                    sanitize(&body)
+                );
+            },
+            None => {},
+        }
+        if self.bell {
+            out.push(0x07);
+        }
+
+        if !out.is_empty() {
+            // escape sequences do not move the cursor, leaving the TUI intact
+            let mut stdout = io::stdout().lock();
+            stdout
+                .write_all(&out)
+                .and_then(|_| stdout.flush())
+                .map_err(|e| GlomError::GeneralError(format!("Failed to notify: {e}").into()))?;
+        }
+
+        if let Some(command) = &self.command {
+            self.run_command(command, pipeline)?;
+        }
+
+        Ok(())
+    }
+
+    fn run_command(&self, command: &str, pipeline: &Pipeline) -> Result<()> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("GLOM_PROJECT", pipeline.project_id.to_string())
+            .env("GLOM_BRANCH", pipeline.branch.as_str())
+            .env("GLOM_WORKFLOW", pipeline.name.as_str())
+            .env("GLOM_STATUS", pipeline.status.as_api_str())
+            .env("GLOM_URL", pipeline.url.as_str())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                GlomError::GeneralError(format!("Failed to run notify_command: {e}").into())
+            })?;
+
+        // reaps the child without blocking the event loop
+        std::thread::spawn(move || match child.wait() {
+            Ok(status) if !status.success() => warn!(%status, "notify_command failed"),
+            Err(e) => warn!(error = %e, "Failed to wait for notify_command"),
+            Ok(_) => {},
+        });
+
+        Ok(())
+    }
+}
+
+/// Strips control characters, which would end the escape sequence early
+fn sanitize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control())
+        .map(|c| if c == ';' { ',' } else { c })
+        .collect()
+}
@@ -0,0 +1,219 @@
+//! Posts a message to a chat webhook, e.g. Slack or Teams, when the default branch
+//! of a project breaks or is fixed
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use compact_str::CompactString;
+use reqwest::Client;
+use tokio::runtime::Handle;
+use tracing::{debug, info, warn};
+
+use crate::{
+    domain::Pipeline,
+    glom_app::GlomConfig,
+    id::{PipelineId, ProjectId},
+    result::{GlomError, Result},
+};
+
+/// Accepted by both Slack and Teams incoming webhooks
+pub const DEFAULT_WEBHOOK_TEMPLATE: &str =
+    r#"{"text": "{{emoji}} {{project}}: {{workflow}} on {{branch}} {{summary}} {{url}}"}"#;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Posted runs remembered for deduplication; the oldest are forgotten first, as runs
+/// only complete again shortly after, e.g. when their jobs are reloaded
+const MAX_POSTED_RUNS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transition {
+    Broken,
+    Fixed,
+}
+
+/// Default branch state of a workflow
+#[derive(Debug, Default)]
+struct WorkflowState {
+    /// `None` until a run of the workflow completes
+    failing: Option<bool>,
+    /// The current failure was posted, so its fix is posted too
+    failure_posted: bool,
+    last_posted: Option<Instant>,
+}
+
+/// Posts default branch runs turning to failure, or back to success
+#[derive(Debug)]
+pub struct WebhookNotifier {
+    url: Option<CompactString>,
+    template: String,
+    /// `owner/repo` of the projects to post about; all projects if empty
+    projects: Vec<CompactString>,
+    /// Minimum time between failures posted per workflow
+    throttle: Duration,
+    client: Client,
+    handle: Handle,
+    workflows: HashMap<(ProjectId, CompactString), WorkflowState>,
+    /// Runs posted, by their id and whether they failed, oldest first
+    posted: VecDeque<(PipelineId, bool)>,
+}
+
+impl WebhookNotifier {
+    pub fn new(config: &GlomConfig, handle: Handle) -> Self {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+
+        let mut notifier = Self {
+            url: None,
+            template: String::new(),
+            projects: Vec::new(),
+            throttle: Duration::ZERO,
+            client,
+            handle,
+            workflows: HashMap::new(),
+            posted: VecDeque::new(),
+        };
+        notifier.update_config(config);
+        notifier
+    }
+
+    pub fn update_config(&mut self, config: &GlomConfig) {
+        self.url.clone_from(&config.notify_webhook_url);
+        self.template = config
+            .notify_webhook_template
+            .clone()
+            .unwrap_or_else(|| DEFAULT_WEBHOOK_TEMPLATE.into());
+        self.projects
+            .clone_from(&config.notify_webhook_projects);
+        self.throttle = Duration::from_secs(config.notify_webhook_throttle_secs);
+    }
+
+    /// Posts the completed run if it breaks or fixes the default branch of its workflow
+    pub fn run_completed(&mut self, pipeline: &Pipeline, default_branch: &str) -> Result<()> {
+        let Some(url) = self.url.clone() else {
+            return Ok(());
+        };
+
+        let project = pipeline.project_id.to_string();
+        if pipeline.branch != default_branch
+            || (!self.projects.is_empty()
+                && !self
+                    .projects
+                    .iter()
+                    .any(|p| p.as_str() == project))
+        {
+            return Ok(());
+        }
+
+        let failing = pipeline.status.is_failure();
+        let Some(transition) = self.transition(pipeline, failing) else {
+            return Ok(());
+        };
+
+        // a run is posted once, even if its jobs are reloaded or it completes again
+        if self.posted.contains(&(pipeline.id, failing)) {
+            return Ok(());
+        }
+        if self.posted.len() == MAX_POSTED_RUNS {
+            self.posted.pop_front();
+        }
+        self.posted.push_back((pipeline.id, failing));
+
+        let body = render(&self.template, pipeline, transition)?;
+        info!(project_id = %pipeline.project_id, run_id = %pipeline.id, ?transition, "Posting run to webhook");
+
+        let request = self
+            .client
+            .post(url.as_str())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+
+        self.handle.spawn(async move {
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    debug!(status = %response.status(), "Webhook notification delivered")
+                },
+                Ok(response) => {
+                    warn!(status = %response.status(), "Webhook notification rejected")
+                },
+                Err(e) => warn!(error = %e, "Failed to post webhook notification"),
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Updates the state of the run's workflow, returning the transition to post;
+    /// repeated failures, and failures within the throttle window, are not posted
+    fn transition(&mut self, pipeline: &Pipeline, failing: bool) -> Option<Transition> {
+        let throttle = self.throttle;
+        let state = self
+            .workflows
+            .entry((pipeline.project_id.clone(), pipeline.name.clone()))
+            .or_default();
+
+        let was_failing = state.failing.replace(failing);
+        let transition = match (was_failing, failing) {
+            (Some(true), true) | (None | Some(false), false) => None,
+            (None | Some(false), true) => {
+                let throttled = state
+                    .last_posted
+                    .is_some_and(|at| at.elapsed() < throttle);
+                state.failure_posted = !throttled;
+                (!throttled).then_some(Transition::Broken)
+            },
+            (Some(true), false) => {
+                std::mem::take(&mut state.failure_posted).then_some(Transition::Fixed)
+            },
+        };
+
+        if transition.is_some() {
+            state.last_posted = Some(Instant::now());
+        }
+        transition
+    }
+}
+
+/// Replaces the `{{placeholders}}` of the template with JSON escaped values
+fn render(template: &str, pipeline: &Pipeline, transition: Transition) -> Result<String> {
+    let (emoji, summary) = match transition {
+        Transition::Broken => ("🔴", "is failing"),
+        Transition::Fixed => ("🟢", "is passing again"),
+    };
+
+    let project = pipeline.project_id.to_string();
+    let body = [
+        ("project", project.as_str()),
+        ("branch", pipeline.branch.as_str()),
+        ("workflow", pipeline.name.as_str()),
+        ("status", pipeline.status.as_api_str()),
+        ("url", pipeline.url.as_str()),
+        ("sha", pipeline.head_sha.as_str()),
+        ("emoji", emoji),
+        ("summary", summary),
+    ]
+    .iter()
+    .fold(template.to_string(), |body, (name, value)| {
+        body.replace(&format!("{{{{{name}}}}}"), &json_escape(value))
+    });
+
+    serde_json::from_str::<serde_json::Value>(&body).map_err(|e| {
+        GlomError::config_validation_error(
+            "notify_webhook_template",
+            format!("not valid JSON once rendered: {e}"),
+        )
+    })?;
+
+    Ok(body)
+}
+
+/// The value as the content of a JSON string, without the quotes
+fn json_escape(value: &str) -> String {
+    // serializing a string does not fail
+    let quoted = serde_json::to_string(value).unwrap_or_else(|_| "\"\"".into());
+    quoted[1..quoted.len() - 1].to_string()
+}
@@ -0,0 +1,303 @@
+//! Prometheus exporter serving the state of the monitored projects on `/metrics`,
+//! e.g. for a Grafana dashboard
+
+use std::{
+    collections::BTreeMap,
+    convert::Infallible,
+    fmt::Write,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use compact_str::{CompactString, ToCompactString};
+use http_body_util::Full;
+use hyper::{
+    Method, Request, Response, StatusCode, body::Bytes, header::CONTENT_TYPE, server::conn::http1,
+    service::service_fn,
+};
+use hyper_util::rt::TokioIo;
+use itertools::Itertools;
+use tokio::net::TcpListener;
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    client::ApiStats,
+    domain::{PipelineStatus, Project},
+    result::{GlomError, Result},
+};
+
+/// Version 0.0.4 of the Prometheus text exposition format
+const CONTENT_TYPE_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Gauges of a project, as of its last update
+#[derive(Debug, Default)]
+struct ProjectGauges {
+    active_runs: usize,
+    /// latest run per workflow and branch
+    latest_runs: Vec<LatestRun>,
+}
+
+#[derive(Debug)]
+struct LatestRun {
+    workflow: CompactString,
+    branch: CompactString,
+    status: PipelineStatus,
+    /// `None` until the jobs of the run are loaded
+    duration_secs: Option<i64>,
+    queue_secs: Option<i64>,
+}
+
+/// Metrics of the monitored projects and the GitHub API, rendered on each scrape
+#[derive(Debug)]
+pub struct PrometheusMetrics {
+    api: Arc<ApiStats>,
+    /// by `owner/repo`, for a stable order of the series
+    projects: Mutex<BTreeMap<CompactString, ProjectGauges>>,
+}
+
+impl PrometheusMetrics {
+    pub fn new(api: Arc<ApiStats>) -> Self {
+        Self { api, projects: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Replaces the gauges of the project with its current runs
+    pub fn update(&self, project: &Project) {
+        let pipelines = project.pipelines.as_deref().unwrap_or_default();
+
+        let active_runs = pipelines
+            .iter()
+            .filter(|p| p.status.is_active() || p.has_active_jobs())
+            .count();
+
+        // newest first, as on the dashboard
+        let latest_runs = pipelines
+            .iter()
+            .filter(|p| p.source.is_interesting())
+            .unique_by(|p| (&p.name, &p.branch))
+            .map(|p| {
+                let has_jobs = p
+                    .jobs
+                    .as_ref()
+                    .is_some_and(|jobs| !jobs.is_empty());
+
+                LatestRun {
+                    workflow: p.name.clone(),
+                    branch: p.branch.clone(),
+                    status: p.status.clone(),
+                    duration_secs: has_jobs.then(|| p.duration().num_seconds()),
+                    queue_secs: p.queue_time().map(|d| d.num_seconds()),
+                }
+            })
+            .collect();
+
+        self.projects.lock().unwrap().insert(
+            project.id.to_compact_string(),
+            ProjectGauges { active_runs, latest_runs },
+        );
+    }
+
+    /// Drops the gauges of projects no longer monitored, e.g. removed or filtered out,
+    /// rather than exporting their last values forever
+    pub fn retain(&self, projects: &[Project]) {
+        self.projects.lock().unwrap().retain(|id, _| {
+            projects
+                .iter()
+                .any(|p| p.id.to_compact_string() == *id)
+        });
+    }
+
+    /// Metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let projects = self.projects.lock().unwrap();
+        let mut out = String::new();
+
+        metric_header(
+            &mut out,
+            "glom_active_runs",
+            "gauge",
+            "Workflow runs queued or in progress",
+        );
+        for (id, gauges) in projects.iter() {
+            let _ = writeln!(
+                out,
+                "glom_active_runs{{repo=\"{}\"}} {}",
+                escape(id),
+                gauges.active_runs
+            );
+        }
+
+        let runs = || {
+            projects.iter().flat_map(|(id, gauges)| {
+                gauges.latest_runs.iter().map(move |run| {
+                    let labels = format!(
+                        "repo=\"{}\",workflow=\"{}\",branch=\"{}\"",
+                        escape(id),
+                        escape(&run.workflow),
+                        escape(&run.branch),
+                    );
+                    (labels, run)
+                })
+            })
+        };
+
+        metric_header(
+            &mut out,
+            "glom_last_run_status",
+            "gauge",
+            "Status of the latest run per workflow and branch, as a 1 labelled with the status",
+        );
+        for (labels, run) in runs() {
+            let _ = writeln!(
+                out,
+                "glom_last_run_status{{{labels},status=\"{}\"}} 1",
+                run.status.as_api_str()
+            );
+        }
+
+        metric_header(
+            &mut out,
+            "glom_last_run_duration_seconds",
+            "gauge",
+            "Duration of the latest run per workflow and branch; elapsed time while active",
+        );
+        for (labels, run) in runs() {
+            if let Some(secs) = run.duration_secs {
+                let _ = writeln!(out, "glom_last_run_duration_seconds{{{labels}}} {secs}");
+            }
+        }
+
+        metric_header(
+            &mut out,
+            "glom_last_run_queue_seconds",
+            "gauge",
+            "Time the jobs of the latest run per workflow and branch waited for a runner",
+        );
+        for (labels, run) in runs() {
+            if let Some(secs) = run.queue_secs {
+                let _ = writeln!(out, "glom_last_run_queue_seconds{{{labels}}} {secs}");
+            }
+        }
+
+        metric_header(
+            &mut out,
+            "glom_api_requests_total",
+            "counter",
+            "GitHub API requests sent",
+        );
+        let _ = writeln!(out, "glom_api_requests_total {}", self.api.requests());
+
+        metric_header(
+            &mut out,
+            "glom_api_errors_total",
+            "counter",
+            "GitHub API requests failing or answered with an error status",
+        );
+        let _ = writeln!(out, "glom_api_errors_total {}", self.api.errors());
+
+        if let Some(remaining) = self.api.rate_limit_remaining() {
+            metric_header(
+                &mut out,
+                "glom_api_rate_limit_remaining",
+                "gauge",
+                "Requests remaining in the current GitHub API rate limit window",
+            );
+            let _ = writeln!(out, "glom_api_rate_limit_remaining {remaining}");
+        }
+
+        out
+    }
+}
+
+/// Serves the metrics on `/metrics`
+#[derive(Debug)]
+pub struct PrometheusExporter {
+    listen: CompactString,
+    metrics: Arc<PrometheusMetrics>,
+}
+
+impl PrometheusExporter {
+    pub fn new(listen: CompactString, api: Arc<ApiStats>) -> Self {
+        Self {
+            listen,
+            metrics: Arc::new(PrometheusMetrics::new(api)),
+        }
+    }
+
+    /// Shared handle for updating the metrics as projects change
+    pub fn metrics(&self) -> Arc<PrometheusMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Bind the listener and serve scrapes until the task is dropped
+    #[instrument(skip(self), fields(listen = %self.listen))]
+    pub async fn start(self) -> Result<()> {
+        let addr: SocketAddr = self.listen.parse().map_err(|_| {
+            GlomError::config_validation_error("metrics_listen", "not a valid socket address")
+        })?;
+
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|e| GlomError::GeneralError(format!("Failed to bind {addr}: {e}").into()))?;
+
+        info!(%addr, "Prometheus exporter listening");
+
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!(error = %e, "Failed to accept metrics connection");
+                    continue;
+                },
+            };
+
+            let metrics = self.metrics.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req| handle(metrics.clone(), req));
+                if let Err(e) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    debug!(%peer, error = %e, "Metrics connection closed with error");
+                }
+            });
+        }
+    }
+}
+
+async fn handle<B>(
+    metrics: Arc<PrometheusMetrics>,
+    req: Request<B>,
+) -> std::result::Result<Response<Full<Bytes>>, Infallible> {
+    let response = if req.method() != Method::GET {
+        reply(StatusCode::METHOD_NOT_ALLOWED, "expected GET".into())
+    } else if req.uri().path() != "/metrics" {
+        reply(StatusCode::NOT_FOUND, "not found".into())
+    } else {
+        let mut response = reply(StatusCode::OK, metrics.render());
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, CONTENT_TYPE_TEXT.parse().unwrap());
+        response
+    };
+
+    Ok(response)
+}
+
+fn reply(status: StatusCode, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response
+}
+
+fn metric_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Escapes a label value
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -13,8 +13,12 @@ use crate::{
     theme::theme,
     ui::{
         StatefulWidgets,
-        popup::{ConfigPopup, PipelineActionsPopup, ProjectDetailsPopup},
-        widget::{Notification, ProjectsTable},
+        popup::{
+            CachesPopup, ConfigPopup, EnvironmentsPopup, HistoryPopup, MetricsPopup,
+            PipelineActionsPopup, ProjectDetailsPopup, PullRequestsPopup, RunFilterPopup,
+            RunnersPopup,
+        },
+        widget::{Notification, ProjectsTable, WatchPanel},
     },
 };
 
@@ -31,7 +35,15 @@ pub fn render_main_ui(
     let layout =
         Layout::new(Direction::Horizontal, [Constraint::Percentage(100)]).split(frame_area);
 
-    render_projects_table(f, app, widget_states, layout[0]);
+    // watched runs are pinned above the projects, but not above the popups
+    let watched = app.watched_runs();
+    let watch_panel = WatchPanel::new(&watched);
+    let [watch_area, projects_area] =
+        Layout::vertical([Constraint::Length(watch_panel.height()), Constraint::Fill(1)])
+            .areas(layout[0]);
+
+    render_projects_table(f, app, widget_states, projects_area);
+    f.render_widget(watch_panel, watch_area);
     render_popups(f, widget_states, layout[0], last_tick);
     render_effects(f, effects, last_tick, frame_area);
 
@@ -56,7 +68,8 @@ fn render_projects_table(
         &filtered_projects,
         widget_states.filter_input_active,
         &widget_states.filter_input_text,
-    );
+    )
+    .with_stale(app.is_stale());
     f.render_stateful_widget(projects, area, &mut widget_states.project_table_state);
 }
 
@@ -77,6 +90,51 @@ fn render_popups(
         f.render_stateful_widget(popup, area, pipeline_actions);
     }
 
+    if let Some(runners) = widget_states.runners.as_mut() {
+        let popup_area = area.inner(Margin::new(6, 2));
+        f.render_stateful_widget(RunnersPopup::new(), popup_area, runners);
+    }
+
+    if let Some(caches) = widget_states.caches.as_mut() {
+        let popup_area = area.inner(Margin::new(6, 2));
+        f.render_stateful_widget(CachesPopup::new(), popup_area, caches);
+
+        if let Some(cursor) = caches.cursor_position {
+            f.buffer_mut()
+                .set_style(Rect::new(cursor.x, cursor.y, 1, 1), theme().input_selected);
+            f.set_cursor_position(cursor);
+        }
+    }
+
+    if let Some(pull_requests) = widget_states.pull_requests.as_mut() {
+        let popup_area = area.inner(Margin::new(6, 2));
+        f.render_stateful_widget(PullRequestsPopup::new(), popup_area, pull_requests);
+    }
+
+    if let Some(environments) = widget_states.environments.as_mut() {
+        let popup_area = area.inner(Margin::new(6, 2));
+        f.render_stateful_widget(EnvironmentsPopup::new(), popup_area, environments);
+    }
+
+    if let Some(history) = widget_states.history.as_mut() {
+        let popup_area = area.inner(Margin::new(6, 2));
+        f.render_stateful_widget(HistoryPopup::new(), popup_area, history);
+    }
+
+    if let Some(metrics) = widget_states.metrics.as_mut() {
+        let popup_area = area.inner(Margin::new(6, 2));
+        f.render_stateful_widget(MetricsPopup::new(), popup_area, metrics);
+    }
+
+    if let Some(run_filter) = widget_states.run_filter.as_mut() {
+        f.render_stateful_widget(RunFilterPopup::new(), area, run_filter);
+
+        let cursor = run_filter.cursor_position;
+        f.buffer_mut()
+            .set_style(Rect::new(cursor.x, cursor.y, 1, 1), theme().input_selected);
+        f.set_cursor_position(cursor);
+    }
+
     if let Some(config_popup) = &mut widget_states.config_popup_state {
         render_config_popup(f, config_popup, last_tick, area);
     }
@@ -33,6 +33,9 @@ pub enum GlomError {
     #[error("{0}")]
     GeneralError(CompactString),
 
+    #[error("Run history: {0}")]
+    HistoryError(CompactString),
+
     #[error("{0:?} - JSON: {1}")]
     #[allow(dead_code)]
     JsonDeserializeError(Category, CompactString),
@@ -54,6 +57,12 @@ impl From<reqwest::Error> for GlomError {
     }
 }
 
+impl From<rusqlite::Error> for GlomError {
+    fn from(e: rusqlite::Error) -> Self {
+        GlomError::HistoryError(e.to_compact_string())
+    }
+}
+
 impl From<crate::client::ClientError> for GlomError {
     fn from(e: crate::client::ClientError) -> Self {
         GlomError::GeneralError(e.to_string().into())
@@ -0,0 +1,95 @@
+//! On-disk snapshot of the project store, shown at startup until the first refresh
+
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use directories::BaseDirs;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::domain::Project;
+
+/// Bumped whenever the serialized domain types change incompatibly
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// Interval between periodic snapshots while running
+pub const SNAPSHOT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct ProjectSnapshot<'a> {
+    version: u32,
+    /// Projects of another GitHub server are not restored
+    github_url: CompactString,
+    saved_at: DateTime<Utc>,
+    projects: Cow<'a, [Project]>,
+}
+
+pub fn default_snapshot_path() -> PathBuf {
+    if let Some(dirs) = BaseDirs::new() {
+        dirs.cache_dir()
+            .join("glom")
+            .join("projects.json")
+    } else {
+        PathBuf::from("glom-projects.json")
+    }
+}
+
+/// Loads the projects saved for the GitHub server, if a compatible snapshot exists
+pub fn load_snapshot(path: &PathBuf, github_url: &str) -> Option<Vec<Project>> {
+    read_snapshot(path, github_url).map(|(_, projects)| projects)
+}
+
+/// Loads the projects saved for the GitHub server along with when they were saved, if
+/// a compatible snapshot exists
+pub fn read_snapshot(path: &PathBuf, github_url: &str) -> Option<(DateTime<Utc>, Vec<Project>)> {
+    let content = fs::read(path).ok()?;
+    let snapshot: ProjectSnapshot = match serde_json::from_slice(&content) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            warn!(path = %path.display(), error = %e, "Discarding unreadable project snapshot");
+            return None;
+        },
+    };
+
+    if snapshot.version != SNAPSHOT_VERSION || snapshot.github_url != github_url {
+        debug!(path = %path.display(), "Discarding project snapshot of another version or server");
+        return None;
+    }
+
+    info!(
+        count = snapshot.projects.len(),
+        saved_at = %snapshot.saved_at,
+        "Restored projects from snapshot"
+    );
+    Some((snapshot.saved_at, snapshot.projects.into_owned()))
+}
+
+/// Saves the projects, replacing the previous snapshot atomically
+pub fn save_snapshot(path: &Path, github_url: &str, projects: &[Project]) {
+    let snapshot = ProjectSnapshot {
+        version: SNAPSHOT_VERSION,
+        github_url: github_url.into(),
+        saved_at: Utc::now(),
+        projects: Cow::Borrowed(projects),
+    };
+
+    match write_json(path, &snapshot) {
+        Ok(()) => debug!(path = %path.display(), count = projects.len(), "Saved project snapshot"),
+        Err(e) => warn!(path = %path.display(), error = %e, "Failed to save project snapshot"),
+    }
+}
+
+/// Writes the value as JSON, replacing the file atomically through a temporary file
+/// next to it; missing parent directories are created
+pub fn write_json(path: &Path, value: &impl Serialize) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    path.parent().map_or(Ok(()), fs::create_dir_all)?;
+    let content = serde_json::to_vec(value).map_err(std::io::Error::other)?;
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
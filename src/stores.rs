@@ -1,4 +1,7 @@
-use std::{collections::HashMap, sync::mpsc::Sender};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::mpsc::Sender,
+};
 
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
@@ -6,9 +9,9 @@ use tracing::{debug, info, instrument, warn};
 
 use crate::{
     dispatcher::Dispatcher,
-    domain::{Job, Pipeline, Project},
+    domain::{Job, Pipeline, PipelineStatus, Project, ProjectDto},
     event::GlomEvent,
-    id::ProjectId,
+    id::{PipelineId, ProjectId},
 };
 
 pub struct ProjectStore {
@@ -16,6 +19,8 @@ pub struct ProjectStore {
     projects: Vec<Project>,
     project_id_lookup: HashMap<ProjectId, usize>,
     sorted: Vec<Project>, // todo: ref projects
+    /// Projects were restored from a snapshot and have not been refreshed yet
+    stale: bool,
 }
 
 impl ProjectStore {
@@ -26,9 +31,67 @@ impl ProjectStore {
             // pipelines: Vec::new(),
             project_id_lookup: HashMap::new(),
             sorted: Vec::new(),
+            stale: false,
         }
     }
 
+    /// Restores projects from a snapshot, which are considered stale until the first
+    /// projects refresh completes
+    pub fn restore(&mut self, projects: Vec<Project>) {
+        if projects.is_empty() {
+            return;
+        }
+
+        self.projects = projects;
+        self.rebuild_lookup();
+        self.sorted = self.projects_sorted_by_last_activity();
+        self.stale = true;
+
+        self.dispatch(GlomEvent::ProjectSelected(
+            self.sorted.first().unwrap().id.clone(),
+        ));
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// All projects, in no particular order
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    /// Lower bound for an incremental projects refresh: the oldest activity among
+    /// projects with active runs, else the latest activity of any project; `None`
+    /// requests all projects, as while stale
+    pub fn projects_updated_after(&self) -> Option<DateTime<Utc>> {
+        if self.stale {
+            return None;
+        }
+
+        let latest_activity = self
+            .sorted
+            .iter()
+            .max_by_key(|p| p.last_activity_at)
+            .map(|p| p.last_activity_at);
+
+        self.sorted
+            .iter()
+            .filter(|p| p.has_active_pipelines())
+            .min_by_key(|p| p.last_activity_at)
+            .map(|p| p.last_activity_at)
+            .map_or_else(|| latest_activity, Some)
+    }
+
+    /// Runs queued or in progress, or with jobs still running
+    pub fn active_pipelines(&self) -> impl Iterator<Item = &Pipeline> {
+        self.sorted
+            .iter()
+            .flat_map(|p| p.pipelines.iter())
+            .flatten()
+            .filter(|p| p.status.is_active() || p.has_active_jobs())
+    }
+
     #[instrument(skip(self, event), fields(event_type = %event.variant_name()))]
     pub fn apply(&mut self, event: &GlomEvent) {
         match event {
@@ -45,6 +108,15 @@ impl ProjectStore {
                     .filter(|p| p.jobs.is_none())
                     .for_each(|p| self.dispatch(GlomEvent::JobsFetch(project_id.clone(), p.id)));
 
+                // Fetch billable time of completed runs; it is final once a run completes
+                project
+                    .recent_pipelines()
+                    .into_iter()
+                    .filter(|p| p.billable.is_none() && !p.status.is_active())
+                    .for_each(|p| {
+                        self.dispatch(GlomEvent::RunTimingFetch(project_id.clone(), p.id))
+                    });
+
                 // Fetch repository statistics if they haven't been loaded yet
                 if project.commit_count == 0
                     && project.repo_size_kb == 0
@@ -68,6 +140,10 @@ impl ProjectStore {
                     "Processing received projects"
                 );
                 let first_projects = self.sorted.is_empty();
+                if self.stale {
+                    self.reconcile_restored(projects);
+                }
+
                 projects
                     .iter()
                     .map(|p| Project::from(p.clone()))
@@ -105,7 +181,23 @@ impl ProjectStore {
                             sender.dispatch(GlomEvent::JobsFetch(project_id.clone(), p.id))
                         });
 
+                    let running = running_runs(project);
                     project.update_pipelines(pipelines);
+                    completed_runs(project, &running).for_each(|p| {
+                        sender.dispatch(GlomEvent::RunCompleted(Box::new(p.clone())))
+                    });
+
+                    // external checks of the branch heads, merged into their status
+                    project
+                        .branch_heads_to_check()
+                        .into_iter()
+                        .for_each(|p| {
+                            sender.dispatch(GlomEvent::CommitChecksFetch(
+                                project_id.clone(),
+                                p.head_sha.clone(),
+                            ))
+                        });
+
                     sender.dispatch(GlomEvent::ProjectUpdated(Box::new(project.clone())))
                 }
 
@@ -114,14 +206,25 @@ impl ProjectStore {
 
             GlomEvent::JobsLoaded(project_id, pipeline_id, job_dtos) => {
                 debug!(project_id = %project_id, pipeline_id = %pipeline_id, job_count = job_dtos.len(), "Processing received jobs");
+                // jobs of earlier attempts are only recorded in the run history
+                let latest_attempt = job_dtos
+                    .iter()
+                    .map(|j| j.run_attempt)
+                    .max()
+                    .unwrap_or_default();
                 let jobs: Vec<Job> = job_dtos
                     .iter()
+                    .filter(|j| j.run_attempt == latest_attempt)
                     .map(|j| Job::from(j.clone()))
                     .collect();
 
                 let sender = self.sender.clone();
                 if let Some(project) = self.find_mut(project_id.clone()) {
+                    let running = running_runs(project);
                     project.update_jobs(*pipeline_id, jobs);
+                    completed_runs(project, &running).for_each(|p| {
+                        sender.dispatch(GlomEvent::RunCompleted(Box::new(p.clone())))
+                    });
                     // todo: ugly, fix
                     project.update_commit(
                         *pipeline_id,
@@ -142,14 +245,59 @@ impl ProjectStore {
                 let sender = self.sender.clone();
                 if let Some(project) = self.find_mut(project_id.clone()) {
                     project.commit_count = statistics.commit_count;
+                    project
+                        .default_branch
+                        .clone_from(&statistics.commit_branch);
                     project.repo_size_kb = statistics.repository_size / 1024; // Convert bytes to KB
                     project.artifacts_size_kb = statistics.job_artifacts_size / 1024; // Convert bytes to KB
+                    project.cache_size_kb = statistics.cache_size / 1024;
+                    project
+                        .repository
+                        .clone_from(&statistics.repository);
                     project.statistics_loading = false; // Clear loading state
 
                     sender.dispatch(GlomEvent::ProjectUpdated(Box::new(project.clone())));
                 }
             },
 
+            // keeps the cache usage in the repository statistics current
+            GlomEvent::CachesLoaded(project_id, usage, _) => {
+                let sender = self.sender.clone();
+                if let Some(project) = self.find_mut(project_id.clone()) {
+                    project.cache_size_kb = usage.active_caches_size_in_bytes / 1024;
+                    sender.dispatch(GlomEvent::ProjectUpdated(Box::new(project.clone())));
+                }
+            },
+
+            // updates the external checks of the runs of a commit
+            GlomEvent::CommitChecksLoaded(project_id, sha, checks) => {
+                let sender = self.sender.clone();
+                if let Some(project) = self.find_mut(project_id.clone()) {
+                    project.update_checks(sha, checks.clone());
+                    sender.dispatch(GlomEvent::ProjectUpdated(Box::new(project.clone())));
+                }
+            },
+
+            // updates the billable time of a workflow run
+            GlomEvent::RunTimingLoaded(project_id, pipeline_id, timing) => {
+                let sender = self.sender.clone();
+                if let Some(project) = self.find_mut(project_id.clone()) {
+                    project.update_billable(*pipeline_id, timing.clone().into());
+                    sender.dispatch(GlomEvent::ProjectUpdated(Box::new(project.clone())));
+                }
+            },
+
+            // drops pipelines loaded with the previous filter and requests them again
+            GlomEvent::RunFilterUpdated(id, _) => {
+                debug!(project_id = %id, "Run filter changed, reloading pipelines");
+                let sender = self.sender.clone();
+                if let Some(project) = self.find_mut(id.clone()) {
+                    project.pipelines = Some(Vec::new());
+                    sender.dispatch(GlomEvent::ProjectUpdated(Box::new(project.clone())));
+                    sender.dispatch(GlomEvent::PipelinesFetch(id.clone()));
+                }
+            },
+
             // requests pipelines for a project if they are not already loaded
             GlomEvent::ProjectSelected(id) => {
                 debug!(project_id = %id, "Project selected");
@@ -195,6 +343,34 @@ impl ProjectStore {
         self.project_id_lookup.get(&id).copied()
     }
 
+    /// Drops restored projects which are no longer returned by GitHub, e.g. because
+    /// they were deleted or access to them was revoked
+    fn reconcile_restored(&mut self, projects: &[ProjectDto]) {
+        let current: HashSet<ProjectId> = projects
+            .iter()
+            .map(|p| ProjectId::new(p.full_name.clone()))
+            .collect();
+
+        let restored = self.projects.len();
+        self.projects.retain(|p| current.contains(&p.id));
+        self.rebuild_lookup();
+        self.stale = false;
+
+        info!(
+            dropped = restored - self.projects.len(),
+            "Reconciled restored projects with GitHub"
+        );
+    }
+
+    fn rebuild_lookup(&mut self) {
+        self.project_id_lookup = self
+            .projects
+            .iter()
+            .enumerate()
+            .map(|(idx, p)| (p.id.clone(), idx))
+            .collect();
+    }
+
     #[instrument(skip(self, project), fields(project_id = %project.id, project_path = %project.path))]
     fn sync_project(&mut self, mut project: Project) {
         let sender = self.sender.clone();
@@ -221,6 +397,36 @@ fn is_older_than_7d(date: DateTime<Utc>) -> bool {
     Utc::now().signed_duration_since(date).num_days() > 7
 }
 
+/// Runs queued or in progress, or with jobs still running
+fn running_runs(project: &Project) -> HashSet<PipelineId> {
+    project
+        .pipelines
+        .iter()
+        .flatten()
+        .filter(|p| p.status.is_active() || p.has_active_jobs())
+        .map(|p| p.id)
+        .collect()
+}
+
+/// Runs among `running` that changed to success or failure, ignoring cancelled runs
+fn completed_runs<'a>(
+    project: &'a Project,
+    running: &'a HashSet<PipelineId>,
+) -> impl Iterator<Item = &'a Pipeline> {
+    project
+        .pipelines
+        .iter()
+        .flatten()
+        .filter(|p| running.contains(&p.id))
+        .filter(|p| !p.status.is_active() && !p.has_active_jobs())
+        .filter(|p| {
+            matches!(
+                p.status,
+                PipelineStatus::Success | PipelineStatus::Failure | PipelineStatus::TimedOut
+            )
+        })
+}
+
 #[instrument(skip(event))]
 pub fn log_event(event: &GlomEvent) {
     match event {
@@ -243,6 +449,89 @@ pub fn log_event(event: &GlomEvent) {
         GlomEvent::JobsLoaded(project_id, pipeline_id, jobs) => {
             debug!(project_id = %project_id, pipeline_id = %pipeline_id, count = jobs.len(), "Received jobs")
         },
+        GlomEvent::CachesFetch(project_id) => {
+            debug!(project_id = %project_id, "Requesting caches")
+        },
+        GlomEvent::CachesLoaded(project_id, usage, caches) => {
+            debug!(project_id = %project_id, count = caches.len(),
+                   usage = usage.active_caches_size_in_bytes, "Received caches")
+        },
+        GlomEvent::CacheDelete(project_id, deletion) => {
+            info!(project_id = %project_id, deletion = ?deletion, "Deleting caches")
+        },
+        GlomEvent::PullRequestsFetch(project_id) => {
+            debug!(project_id = %project_id, "Requesting pull requests")
+        },
+        GlomEvent::PullRequestsLoaded(project_id, pull_requests) => {
+            debug!(project_id = %project_id, count = pull_requests.len(), "Received pull requests")
+        },
+        GlomEvent::PullRequestRunsFetch(project_id, number, head_sha) => {
+            debug!(project_id = %project_id, number = number, sha = %head_sha, "Requesting pull request runs")
+        },
+        GlomEvent::PullRequestRunsLoaded(project_id, number, runs) => {
+            debug!(project_id = %project_id, number = number, count = runs.len(), "Received pull request runs")
+        },
+        GlomEvent::EnvironmentsFetch(project_id) => {
+            debug!(project_id = %project_id, "Requesting environments")
+        },
+        GlomEvent::EnvironmentsLoaded(project_id, environments) => {
+            debug!(project_id = %project_id, count = environments.len(), "Received environments")
+        },
+        GlomEvent::HistoryFetch(project_id) => {
+            debug!(project_id = %project_id, "Querying run history")
+        },
+        GlomEvent::HistoryLoaded(project_id, report) => {
+            debug!(project_id = %project_id, red_streaks = report.red_streaks.len(),
+                   job_failures = report.job_failures.len(), "Queried run history")
+        },
+        GlomEvent::MetricsFetch(project_id) => {
+            debug!(project_id = %project_id, "Computing delivery metrics")
+        },
+        GlomEvent::MetricsLoaded(project_id, report) => {
+            debug!(project_id = %project_id, branch = %report.branch, "Computed delivery metrics")
+        },
+        GlomEvent::JobFlakinessLoaded(project_id, flakiness) => {
+            debug!(project_id = %project_id, flaky_jobs = flakiness.len(), "Queried job flakiness")
+        },
+        GlomEvent::RunnersFetch(project_id) => {
+            debug!(project_id = %project_id, "Requesting runners")
+        },
+        GlomEvent::RunnersLoaded(project_id, runners) => {
+            debug!(project_id = %project_id, count = runners.len(), "Received runners")
+        },
+        GlomEvent::CommitChecksFetch(project_id, sha) => {
+            debug!(project_id = %project_id, sha = %sha, "Requesting commit checks")
+        },
+        GlomEvent::CommitChecksLoaded(project_id, sha, checks) => {
+            debug!(project_id = %project_id, sha = %sha, count = checks.checks.len(), "Received commit checks")
+        },
+        GlomEvent::WatchBranchToggle(project_id, pipeline_id) => {
+            info!(project_id = %project_id, pipeline_id = %pipeline_id, "Toggling branch watch")
+        },
+        GlomEvent::WatchRunToggle(project_id, pipeline_id) => {
+            info!(project_id = %project_id, pipeline_id = %pipeline_id, "Toggling run watch")
+        },
+        GlomEvent::WatchClear => info!("Dismissing all watches"),
+        GlomEvent::WatchedFetch => debug!("Requesting watched runs"),
+        GlomEvent::RunCompleted(pipeline) => {
+            info!(project_id = %pipeline.project_id, run_id = %pipeline.id,
+                  workflow = %pipeline.name, branch = %pipeline.branch,
+                  status = pipeline.status.as_api_str(), "Run completed")
+        },
+        GlomEvent::RunDurationRegressed(regression) => {
+            info!(project_id = %regression.project_id, run_id = %regression.run_id,
+                  workflow = %regression.workflow, duration_secs = regression.duration.num_seconds(),
+                  threshold_secs = regression.threshold.num_seconds(), "Run slower than usual")
+        },
+        GlomEvent::RunDurationsLoaded(project_id, durations) => {
+            debug!(project_id = %project_id, workflows = durations.len(), "Updated run durations")
+        },
+        GlomEvent::RunTimingFetch(project_id, pipeline_id) => {
+            debug!(project_id = %project_id, pipeline_id = %pipeline_id, "Requesting run timing")
+        },
+        GlomEvent::RunTimingLoaded(project_id, pipeline_id, _) => {
+            debug!(project_id = %project_id, pipeline_id = %pipeline_id, "Received run timing")
+        },
         GlomEvent::ProjectStatisticsFetch(project_id) => {
             debug!(project_id = %project_id, "Requesting repository statistics")
         },
@@ -276,6 +565,10 @@ pub fn log_event(event: &GlomEvent) {
                 "Job log downloaded successfully"
             )
         },
+        GlomEvent::RunFilterOpen(id) => debug!(project_id = %id, "Opening run filters"),
+        GlomEvent::RunFilterUpdated(id, filter) => {
+            info!(project_id = %id, filter = %filter.summary(), "Updated run filter")
+        },
         GlomEvent::ConfigOpen => debug!("Displaying configuration"),
         GlomEvent::ConfigApply => info!("Applying new configuration"),
         GlomEvent::ConfigUpdate(_) => debug!("Updating configuration"),
@@ -283,6 +576,10 @@ pub fn log_event(event: &GlomEvent) {
             debug!(filter = ?filter, "Applying temporary filter")
         },
         GlomEvent::FilterClear => info!("Clearing project filter"),
+        GlomEvent::DashboardExport(filter) => debug!(filter = ?filter, "Exporting dashboard"),
+        GlomEvent::DashboardExported(format, path) => {
+            info!(format = format.label(), path = ?path, "Exported dashboard")
+        },
         GlomEvent::FilterMenuClose => debug!("Closing filter input"),
         GlomEvent::AppExit => info!("Application shutting down"),
         GlomEvent::AppError(err) => {
@@ -9,6 +9,9 @@ pub struct Theme {
     pub project_description: Style,
     pub project_commits: [Style; 2], // [0] = count, [1] = "commits"
     pub project_size: [Style; 2],    // [0] = size, [1] = unit
+    pub project_badge: Style,
+    pub project_topic: Style,
+    pub project_languages: [Style; 6], // last entry groups the remaining languages
     pub commit_title: Style,
     pub pipeline_source: Style,
     pub pipeline_branch: Style,
@@ -33,6 +36,12 @@ pub struct Theme {
     pub input_description_em: Style,
     pub input_label: Style,
     pub configuration_error: Style,
+    pub runner_online: Style,
+    pub runner_offline: Style,
+    pub runner_busy: Style,
+    pub status_ok: Style,
+    pub status_failed: Style,
+    pub status_pending: Style,
     pub border: ThemeBorder,
 }
 
@@ -63,6 +72,18 @@ impl Theme {
                     .add_modifier(Modifier::BOLD),
                 Style::default().fg(Gruvbox::Blue.into()),
             ],
+            project_badge: Style::default()
+                .fg(Gruvbox::Light4.into())
+                .add_modifier(Modifier::BOLD),
+            project_topic: Style::default().fg(Gruvbox::Aqua.into()),
+            project_languages: [
+                Style::default().fg(Gruvbox::OrangeBright.into()),
+                Style::default().fg(Gruvbox::BlueBright.into()),
+                Style::default().fg(Gruvbox::GreenBright.into()),
+                Style::default().fg(Gruvbox::PurpleBright.into()),
+                Style::default().fg(Gruvbox::YellowBright.into()),
+                Style::default().fg(Gruvbox::Gray244.into()),
+            ],
             commit_title: Style::default()
                 .fg(Gruvbox::Light4.into())
                 .add_modifier(Modifier::ITALIC),
@@ -120,6 +141,16 @@ impl Theme {
             configuration_error: Style::default()
                 .fg(Gruvbox::YellowBright.into())
                 .add_modifier(Modifier::BOLD),
+            runner_online: Style::default().fg(Gruvbox::GreenBright.into()),
+            runner_offline: Style::default()
+                .fg(Gruvbox::RedBright.into())
+                .add_modifier(Modifier::BOLD),
+            runner_busy: Style::default().fg(Gruvbox::YellowBright.into()),
+            status_ok: Style::default().fg(Gruvbox::GreenBright.into()),
+            status_failed: Style::default()
+                .fg(Gruvbox::RedBright.into())
+                .add_modifier(Modifier::BOLD),
+            status_pending: Style::default().fg(Gruvbox::YellowBright.into()),
             border: ThemeBorder {
                 title: Style::default()
                     .bg(Gruvbox::Orange.into())
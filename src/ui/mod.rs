@@ -8,6 +8,16 @@ pub mod widget;
 
 pub use stateful_widgets::StatefulWidgets;
 
+/// Formats a size given in kilobytes, e.g. `1.50 MB`
+pub fn format_size(size_kb: u64) -> String {
+    let (size, unit) = match size_kb {
+        s if s < 1024 => (s as f32, "KB"),
+        s if s < 1024 * 1024 => (s as f32 / 1024.0, "MB"),
+        s => (s as f32 / (1024.0 * 1024.0), "GB"),
+    };
+    format!("{size:.2} {unit}")
+}
+
 pub fn format_duration(duration: Duration) -> CompactString {
     let total_seconds = duration.abs().num_seconds();
     let hours = total_seconds / 3600;
@@ -0,0 +1,358 @@
+use std::cmp::Reverse;
+
+use chrono::Local;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Position, Rect},
+    prelude::{Line, StatefulWidget, Text, Widget},
+    text::Span,
+    widgets::{Cell, Row, Table, TableState},
+};
+use tachyonfx::RefRect;
+use tui_input::Input;
+
+use crate::{
+    domain::{CacheDeletion, CacheDto, CacheUsageDto},
+    id::ProjectId,
+    theme::theme,
+    ui::{format_size, fx::popup_window, popup::utility::CenteredShrink},
+};
+
+/// GitHub evicts caches once a repository exceeds 10 GB
+const CACHE_SIZE_LIMIT_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Actions cache popup, opened from the project details popup
+pub struct CachesPopup {}
+
+/// state of the Actions cache popup
+pub struct CachesPopupState {
+    pub project_id: ProjectId,
+    /// caches of the repository; `None` while loading
+    caches: Option<Vec<CacheDto>>,
+    usage: CacheUsageDto,
+    sort: CacheSort,
+    pub mode: CachesMode,
+    pub table_state: TableState,
+    /// cursor of the key prefix input, if active
+    pub cursor_position: Option<Position>,
+    pub popup_area: RefRect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CacheSort {
+    Size,
+    LastAccessed,
+}
+
+pub enum CachesMode {
+    Browse,
+    KeyPrefixInput(Input),
+    Confirm(CacheDeletion),
+    Deleting,
+}
+
+impl CachesPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CachesPopupState {
+    pub fn new(project_id: ProjectId, popup_area: RefRect) -> Self {
+        Self {
+            project_id,
+            caches: None,
+            usage: CacheUsageDto::default(),
+            sort: CacheSort::Size,
+            mode: CachesMode::Browse,
+            table_state: TableState::default().with_selected(0),
+            cursor_position: None,
+            popup_area,
+        }
+    }
+
+    pub fn set_caches(&mut self, usage: CacheUsageDto, caches: Vec<CacheDto>) {
+        let len = caches.len();
+        self.usage = usage;
+        self.caches = Some(caches);
+        self.mode = CachesMode::Browse;
+        self.sort_caches();
+
+        let selected = self
+            .table_state
+            .selected()
+            .map(|idx| idx.min(len.saturating_sub(1)));
+        self.table_state.select(selected);
+    }
+
+    pub fn cache_count(&self) -> usize {
+        self.caches.as_ref().map_or(0, Vec::len)
+    }
+
+    pub fn toggle_sort(&mut self) {
+        self.sort = match self.sort {
+            CacheSort::Size => CacheSort::LastAccessed,
+            CacheSort::LastAccessed => CacheSort::Size,
+        };
+        self.sort_caches();
+    }
+
+    fn sort_caches(&mut self) {
+        if let Some(caches) = self.caches.as_mut() {
+            match self.sort {
+                CacheSort::Size => caches.sort_by_key(|c| Reverse(c.size_in_bytes)),
+                CacheSort::LastAccessed => caches.sort_by_key(|c| Reverse(c.last_accessed_at)),
+            }
+        }
+    }
+
+    fn selected_cache(&self) -> Option<&CacheDto> {
+        let idx = self.table_state.selected()?;
+        self.caches.as_ref()?.get(idx)
+    }
+
+    /// Asks for confirmation to delete the selected cache
+    pub fn delete_selected(&mut self) {
+        if let Some(id) = self.selected_cache().map(|c| c.id) {
+            self.mode = CachesMode::Confirm(CacheDeletion::Id(id));
+        }
+    }
+
+    /// Prompts for a key prefix, starting from the selected cache's key
+    pub fn delete_by_prefix(&mut self) {
+        let key = self
+            .selected_cache()
+            .map(|c| c.key.to_string())
+            .unwrap_or_default();
+        self.mode = CachesMode::KeyPrefixInput(Input::new(key));
+    }
+
+    /// Asks for confirmation to delete the caches matching the entered prefix
+    pub fn submit_prefix(&mut self) {
+        if let CachesMode::KeyPrefixInput(input) = &self.mode {
+            let prefix = input.value().trim();
+            if !prefix.is_empty() {
+                self.mode = CachesMode::Confirm(CacheDeletion::KeyPrefix(prefix.into()));
+            }
+        }
+    }
+
+    /// Confirms the pending deletion, returning it
+    pub fn confirm(&mut self) -> Option<CacheDeletion> {
+        match std::mem::replace(&mut self.mode, CachesMode::Deleting) {
+            CachesMode::Confirm(deletion) => Some(deletion),
+            mode => {
+                self.mode = mode;
+                None
+            },
+        }
+    }
+
+    pub fn cancel(&mut self) {
+        self.mode = CachesMode::Browse;
+    }
+
+    /// Key prefix input, if it is active
+    pub fn prefix_input_mut(&mut self) -> Option<&mut Input> {
+        match &mut self.mode {
+            CachesMode::KeyPrefixInput(input) => Some(input),
+            _ => None,
+        }
+    }
+
+    /// Caches affected by a deletion, according to the loaded caches
+    fn affected(&self, deletion: &CacheDeletion) -> Vec<&CacheDto> {
+        self.caches
+            .iter()
+            .flatten()
+            .filter(|c| match deletion {
+                CacheDeletion::Id(id) => c.id == *id,
+                CacheDeletion::KeyPrefix(prefix) => c.key.starts_with(prefix.as_str()),
+            })
+            .collect()
+    }
+
+    fn usage_text(&self, width: u16) -> Text<'static> {
+        let used = self.usage.active_caches_size_in_bytes;
+        let ratio = used as f64 / CACHE_SIZE_LIMIT_BYTES as f64;
+        let sort = match self.sort {
+            CacheSort::Size => "size",
+            CacheSort::LastAccessed => "last access",
+        };
+
+        let summary = Line::from(vec![
+            Span::from("usage ").style(theme().project_size[1]),
+            Span::from(format_size(used / 1024)).style(theme().project_size[0]),
+            Span::from(" of ").style(theme().project_size[1]),
+            Span::from(format_size(CACHE_SIZE_LIMIT_BYTES / 1024)).style(theme().project_size[0]),
+            Span::from(format!(" ({:.0}%)", ratio * 100.0)).style(theme().project_size[1]),
+            Span::from(format!(" · {} caches", self.usage.active_caches_count)).style(theme().date),
+            Span::from(format!(" · sorted by {sort}")).style(theme().date),
+        ]);
+
+        let bar_style = match ratio {
+            r if r >= 0.9 => theme().runner_offline,
+            r if r >= 0.7 => theme().runner_busy,
+            _ => theme().runner_online,
+        };
+        let filled = ((ratio.min(1.0) * width as f64).round() as usize).min(width as usize);
+        let bar = Line::from(vec![
+            Span::from("━".repeat(filled)).style(bar_style),
+            Span::from("━".repeat(width as usize - filled)).style(theme().table_border),
+        ]);
+
+        Text::from(vec![summary, bar])
+    }
+
+    fn rows(&self) -> Vec<Row<'static>> {
+        self.caches
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(idx, cache)| {
+                let last_accessed = cache
+                    .last_accessed_at
+                    .with_timezone(&Local)
+                    .format("%a, %d %b %H:%M")
+                    .to_string();
+
+                Row::new(vec![
+                    Cell::from(Span::from(cache.key.to_string()).style(theme().pipeline_name)),
+                    Cell::from(
+                        Span::from(cache.git_ref.to_string()).style(theme().pipeline_branch),
+                    ),
+                    Cell::from(
+                        Line::from(format_size(cache.size_in_bytes / 1024))
+                            .style(theme().project_size[0])
+                            .right_aligned(),
+                    ),
+                    Cell::from(Span::from(last_accessed).style(theme().date)),
+                ])
+                .style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    fn prompt(&self) -> Line<'static> {
+        match &self.mode {
+            CachesMode::Browse => Line::default(),
+            CachesMode::KeyPrefixInput(input) => Line::from(vec![
+                Span::from("delete caches with key prefix: ").style(theme().input_label),
+                Span::from(input.value().to_string()).style(theme().input_selected),
+            ]),
+            CachesMode::Confirm(deletion) => {
+                let affected = self.affected(deletion);
+                let size: u64 = affected.iter().map(|c| c.size_in_bytes).sum();
+                let target = match deletion {
+                    CacheDeletion::Id(_) => format!(
+                        "cache '{}'",
+                        affected
+                            .first()
+                            .map(|c| c.key.as_str())
+                            .unwrap_or_default()
+                    ),
+                    CacheDeletion::KeyPrefix(prefix) => {
+                        format!("{} caches matching '{prefix}'", affected.len())
+                    },
+                };
+
+                Line::from(vec![
+                    Span::from(format!("delete {target} ({})? ", format_size(size / 1024)))
+                        .style(theme().configuration_error),
+                    Span::from("y/n").style(theme().input_label),
+                ])
+            },
+            CachesMode::Deleting => Line::from("deleting...").style(theme().project_description),
+        }
+    }
+
+    fn update_cursor_position(&mut self, prompt_area: Rect) {
+        self.cursor_position = match &self.mode {
+            CachesMode::KeyPrefixInput(input) => Some(Position::new(
+                prompt_area.x
+                    + "delete caches with key prefix: ".len() as u16
+                    + input.cursor() as u16,
+                prompt_area.y,
+            )),
+            _ => None,
+        };
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        let rows = self.cache_count().max(1) as u16;
+        let area = screen.inner_centered(screen.width, 6 + rows);
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for CachesPopup {
+    type State = CachesPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        popup_window(
+            "Actions Caches",
+            Some(vec![
+                ("ESC", "close"),
+                ("↑ ↓", "selection"),
+                ("s", "sort"),
+                ("r", "refresh"),
+                ("d", "delete"),
+                ("D", "delete by prefix"),
+            ]),
+        )
+        .render(area, buf);
+
+        let content_area = area.inner(Margin::new(1, 1));
+        let [usage_area, table_area, prompt_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1), Constraint::Length(1)])
+                .areas(content_area);
+
+        state
+            .usage_text(usage_area.width)
+            .render(usage_area, buf);
+
+        state.prompt().render(prompt_area, buf);
+        state.update_cursor_position(prompt_area);
+
+        let message = match &state.caches {
+            None => Some("loading caches..."),
+            Some(caches) if caches.is_empty() => Some("no caches"),
+            Some(_) => None,
+        };
+
+        if let Some(message) = message {
+            Line::from(message)
+                .style(theme().project_description)
+                .render(table_area, buf);
+            return;
+        }
+
+        let header = Row::new(["key", "ref", "size", "last access"]).style(theme().input_label);
+        let max_ref = state
+            .caches
+            .iter()
+            .flatten()
+            .map(|c| c.git_ref.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(3);
+
+        let table = Table::new(
+            state.rows(),
+            [
+                Constraint::Percentage(100),
+                Constraint::Length(max_ref as u16),
+                Constraint::Length(10),
+                Constraint::Length(17),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(theme().highlight_symbol)
+        .column_spacing(1);
+
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+    }
+}
@@ -27,6 +27,8 @@ pub struct ConfigPopupState {
     input_fields: Vec<InputField>,
     pub error_message: Option<CompactString>,
     pub popup_area: RefRect,
+    /// configuration the popup was opened with; retains fields not editable here
+    config: GlomConfig,
 }
 
 impl ConfigPopup {
@@ -107,6 +109,7 @@ impl ConfigPopupState {
                 animations_field,
             ],
             popup_area,
+            config,
         }
     }
 
@@ -188,6 +191,7 @@ impl ConfigPopupState {
             search_filter,
             log_level,
             animations,
+            ..self.config.clone()
         }
     }
 
@@ -0,0 +1,223 @@
+use chrono::{Local, Utc};
+use compact_str::{CompactString, format_compact};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Margin, Rect},
+    prelude::{Line, StatefulWidget, Text, Widget},
+    text::Span,
+    widgets::{Cell, Row, Table, TableState},
+};
+use tachyonfx::RefRect;
+
+use crate::{
+    domain::{DeploymentHealth, EnvironmentDeployment, Project},
+    event::GlomEvent,
+    id::ProjectId,
+    theme::theme,
+    ui::{fx::popup_window, popup::utility::CenteredShrink},
+};
+
+/// deployment environments popup, opened from the project details popup
+pub struct EnvironmentsPopup {}
+
+/// state of the deployment environments popup
+pub struct EnvironmentsPopupState {
+    pub project_id: ProjectId,
+    /// environments with their latest deployment; `None` while loading
+    environments: Option<Vec<EnvironmentRow>>,
+    pub table_state: TableState,
+    pub popup_area: RefRect,
+}
+
+struct EnvironmentRow {
+    environment: EnvironmentDeployment,
+    health: DeploymentHealth,
+    /// workflow name of the deploying run if loaded, otherwise its id
+    run: Option<CompactString>,
+}
+
+impl EnvironmentsPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl EnvironmentsPopupState {
+    pub fn new(project_id: ProjectId, popup_area: RefRect) -> Self {
+        Self {
+            project_id,
+            environments: None,
+            table_state: TableState::default().with_selected(0),
+            popup_area,
+        }
+    }
+
+    pub fn set_environments(
+        &mut self,
+        environments: Vec<EnvironmentDeployment>,
+        project: Option<&Project>,
+    ) {
+        let now = Utc::now();
+        let rows = environments
+            .into_iter()
+            .map(|environment| {
+                let run = environment.run_id().map(|run_id| {
+                    project
+                        .and_then(|p| p.pipeline(run_id))
+                        .map(|p| p.name.clone())
+                        .unwrap_or_else(|| format_compact!("run {run_id}"))
+                });
+
+                EnvironmentRow { health: environment.health(now), environment, run }
+            })
+            .collect();
+
+        self.environments = Some(rows);
+    }
+
+    pub fn environment_count(&self) -> usize {
+        self.environments.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Event opening the workflow run behind the selected environment's latest deployment
+    pub fn selected_run_url_event(&self) -> Option<GlomEvent> {
+        let idx = self.table_state.selected()?;
+        self.environments
+            .as_ref()?
+            .get(idx)?
+            .environment
+            .run_url()
+            .map(|url| GlomEvent::UrlOpen(url.into()))
+    }
+
+    fn rows(&self) -> Vec<Row<'static>> {
+        self.environments
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(idx, row)| {
+                let env = &row.environment;
+                let (marker, marker_style) = match row.health {
+                    DeploymentHealth::Healthy => ("●", theme().status_ok),
+                    DeploymentHealth::Failed => ("✗", theme().status_failed),
+                    DeploymentHealth::Stuck => ("⚠", theme().status_failed),
+                    DeploymentHealth::Deploying => ("◐", theme().status_pending),
+                    DeploymentHealth::Unknown => ("○", theme().date),
+                };
+
+                let state = match (&env.status, row.health) {
+                    (Some(status), DeploymentHealth::Stuck) => {
+                        format!("{} (stuck)", status.state.as_str())
+                    },
+                    (Some(status), _) => status.state.as_str().to_string(),
+                    (None, _) if env.deployment.is_some() => "no status".to_string(),
+                    (None, _) => "never deployed".to_string(),
+                };
+
+                let (git_ref, sha, creator, deployed_at) = match &env.deployment {
+                    Some(d) => (
+                        d.git_ref.to_string(),
+                        d.sha.chars().take(7).collect::<String>(),
+                        d.creator
+                            .as_ref()
+                            .map(|c| c.login.to_string())
+                            .unwrap_or_default(),
+                        d.created_at
+                            .with_timezone(&Local)
+                            .format("%a, %d %b %H:%M")
+                            .to_string(),
+                    ),
+                    None => Default::default(),
+                };
+
+                Row::new(vec![
+                    Cell::from(Span::from(marker).style(marker_style)),
+                    Cell::from(
+                        Span::from(env.environment.to_string()).style(theme().pipeline_name),
+                    ),
+                    Cell::from(Span::from(state).style(marker_style)),
+                    Cell::from(Span::from(git_ref).style(theme().pipeline_branch)),
+                    Cell::from(Span::from(sha).style(theme().commit_title)),
+                    Cell::from(Span::from(creator).style(theme().pipeline_source)),
+                    Cell::from(Span::from(deployed_at).style(theme().date)),
+                    Cell::from(
+                        Span::from(row.run.as_deref().unwrap_or_default().to_string())
+                            .style(theme().pipeline_job),
+                    ),
+                ])
+                .style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        let rows = self.environment_count().max(1) as u16;
+        let area = screen.inner_centered(screen.width, 3 + rows);
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for EnvironmentsPopup {
+    type State = EnvironmentsPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        popup_window(
+            "Environments",
+            Some(vec![
+                ("ESC", "close"),
+                ("↑ ↓", "selection"),
+                ("r", "refresh"),
+                ("↵", "browse to run"),
+            ]),
+        )
+        .render(area, buf);
+
+        let content_area = area.inner(Margin::new(1, 1));
+        let message = match &state.environments {
+            None => Some("loading environments..."),
+            Some(environments) if environments.is_empty() => Some("no environments"),
+            Some(_) => None,
+        };
+
+        if let Some(message) = message {
+            Text::from(Line::from(message).style(theme().project_description))
+                .render(content_area, buf);
+            return;
+        }
+
+        let header =
+            Row::new(["", "environment", "status", "ref", "sha", "creator", "deployed", "run"])
+                .style(theme().input_label);
+
+        let max_name = state
+            .environments
+            .iter()
+            .flatten()
+            .map(|r| r.environment.environment.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(11);
+
+        let table = Table::new(
+            state.rows(),
+            [
+                Constraint::Length(1),
+                Constraint::Length(max_name as u16),
+                Constraint::Length(19),
+                Constraint::Percentage(40),
+                Constraint::Length(7),
+                Constraint::Percentage(20),
+                Constraint::Length(17),
+                Constraint::Percentage(40),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(theme().highlight_symbol)
+        .column_spacing(1);
+
+        StatefulWidget::render(table, content_area, buf, &mut state.table_state);
+    }
+}
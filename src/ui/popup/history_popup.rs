@@ -0,0 +1,271 @@
+use chrono::{Duration, Local, Utc};
+use compact_str::{CompactString, format_compact};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    prelude::{Line, StatefulWidget, Text, Widget},
+    text::Span,
+    widgets::{Cell, Row, Table, TableState},
+};
+use tachyonfx::RefRect;
+
+use crate::{
+    event::GlomEvent,
+    history::{JobFailureRate, RedStreak, RunHistoryReport},
+    id::ProjectId,
+    theme::theme,
+    ui::{fx::popup_window, popup::utility::CenteredShrink},
+};
+
+/// run history popup, opened from the project details popup
+pub struct HistoryPopup {}
+
+/// state of the run history popup
+pub struct HistoryPopupState {
+    pub project_id: ProjectId,
+    /// `None` while loading
+    report: Option<RunHistoryReport>,
+    /// selection among the red branches
+    pub table_state: TableState,
+    pub popup_area: RefRect,
+}
+
+impl HistoryPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl HistoryPopupState {
+    pub fn new(project_id: ProjectId, popup_area: RefRect) -> Self {
+        Self {
+            project_id,
+            report: None,
+            table_state: TableState::default(),
+            popup_area,
+        }
+    }
+
+    pub fn set_report(&mut self, report: RunHistoryReport) {
+        self.table_state
+            .select((!report.red_streaks.is_empty()).then_some(0));
+        self.report = Some(report);
+    }
+
+    pub fn red_streak_count(&self) -> usize {
+        self.report
+            .as_ref()
+            .map_or(0, |r| r.red_streaks.len())
+    }
+
+    fn job_failure_count(&self) -> usize {
+        self.report
+            .as_ref()
+            .map_or(0, |r| r.job_failures.len())
+    }
+
+    /// Event opening the latest failing run of the selected red branch
+    pub fn selected_run_url_event(&self) -> Option<GlomEvent> {
+        let idx = self.table_state.selected()?;
+        self.report
+            .as_ref()?
+            .red_streaks
+            .get(idx)
+            .map(|s| GlomEvent::UrlOpen(s.url.clone()))
+    }
+
+    fn red_streak_rows(streaks: &[RedStreak]) -> Vec<Row<'static>> {
+        let now = Utc::now();
+        streaks
+            .iter()
+            .enumerate()
+            .map(|(idx, streak)| {
+                Row::new(vec![
+                    Cell::from(Span::from("✗").style(theme().status_failed)),
+                    Cell::from(
+                        Span::from(streak.branch.to_string()).style(theme().pipeline_branch),
+                    ),
+                    Cell::from(
+                        Span::from(streak.workflow.to_string()).style(theme().pipeline_name),
+                    ),
+                    Cell::from(
+                        Span::from(format_age(now - streak.since).to_string())
+                            .style(theme().status_failed),
+                    ),
+                    Cell::from(
+                        Span::from(
+                            streak
+                                .since
+                                .with_timezone(&Local)
+                                .format("%a, %d %b %H:%M")
+                                .to_string(),
+                        )
+                        .style(theme().date),
+                    ),
+                    Cell::from(
+                        Span::from(format!("{} failed", streak.failed_runs))
+                            .style(theme().commit_title),
+                    ),
+                ])
+                .style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    fn job_failure_rows(failures: &[JobFailureRate]) -> Vec<Row<'static>> {
+        failures
+            .iter()
+            .enumerate()
+            .map(|(idx, job)| {
+                Row::new(vec![
+                    Cell::from(Span::from(job.name.to_string()).style(theme().pipeline_job)),
+                    Cell::from(
+                        Span::from(format!("{}/{}", job.failures, job.runs))
+                            .style(theme().status_failed),
+                    ),
+                    Cell::from(
+                        Span::from(format!("{:.0}%", job.rate())).style(theme().commit_title),
+                    ),
+                ])
+                .style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    fn section_height(rows: usize) -> u16 {
+        // title and header, or a single line when empty
+        2 + rows.max(1) as u16
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        let height = 3
+            + Self::section_height(self.red_streak_count())
+            + 1
+            + Self::section_height(self.job_failure_count());
+        let area = screen.inner_centered(screen.width, height);
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for HistoryPopup {
+    type State = HistoryPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        popup_window(
+            "Run History",
+            Some(vec![
+                ("ESC", "close"),
+                ("↑ ↓", "selection"),
+                ("r", "refresh"),
+                ("↵", "browse to run"),
+            ]),
+        )
+        .render(area, buf);
+
+        let content_area = area.inner(Margin::new(1, 1));
+        let report = match &state.report {
+            None => Some("loading run history..."),
+            Some(report) if report.recorded_since.is_none() => Some("no runs recorded yet"),
+            Some(_) => None,
+        };
+
+        if let Some(message) = report {
+            Text::from(Line::from(message).style(theme().project_description))
+                .render(content_area, buf);
+            return;
+        }
+
+        let report = state.report.as_ref().unwrap();
+        let [red_area, _, failures_area, footer_area] = Layout::vertical([
+            Constraint::Length(HistoryPopupState::section_height(report.red_streaks.len())),
+            Constraint::Length(1),
+            Constraint::Length(HistoryPopupState::section_height(report.job_failures.len())),
+            Constraint::Length(1),
+        ])
+        .areas(content_area);
+
+        // red branches
+        let [title_area, table_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(red_area);
+        Line::from("red branches")
+            .style(theme().input_label)
+            .render(title_area, buf);
+
+        if report.red_streaks.is_empty() {
+            Line::from("all branches are green")
+                .style(theme().project_description)
+                .render(table_area, buf);
+        } else {
+            let table = Table::new(
+                HistoryPopupState::red_streak_rows(&report.red_streaks),
+                [
+                    Constraint::Length(1),
+                    Constraint::Percentage(40),
+                    Constraint::Percentage(60),
+                    Constraint::Length(8),
+                    Constraint::Length(17),
+                    Constraint::Length(10),
+                ],
+            )
+            .header(
+                Row::new(["", "branch", "workflow", "red for", "since", "runs"])
+                    .style(theme().input_label),
+            )
+            .row_highlight_style(theme().highlight_symbol)
+            .column_spacing(1);
+
+            StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+        }
+
+        // job failures
+        let [title_area, table_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(failures_area);
+        Line::from("job failures this month")
+            .style(theme().input_label)
+            .render(title_area, buf);
+
+        if report.job_failures.is_empty() {
+            Line::from("no failed jobs")
+                .style(theme().project_description)
+                .render(table_area, buf);
+        } else {
+            let table = Table::new(
+                HistoryPopupState::job_failure_rows(&report.job_failures),
+                [Constraint::Percentage(100), Constraint::Length(9), Constraint::Length(4)],
+            )
+            .header(Row::new(["job", "failed", "rate"]).style(theme().input_label))
+            .column_spacing(1);
+
+            Widget::render(table, table_area, buf);
+        }
+
+        if let Some(since) = report.recorded_since {
+            Line::from(vec![
+                Span::from("recorded since ").style(theme().project_description),
+                Span::from(
+                    since
+                        .with_timezone(&Local)
+                        .format("%a, %d %b %Y")
+                        .to_string(),
+                )
+                .style(theme().date),
+            ])
+            .render(footer_area, buf);
+        }
+    }
+}
+
+/// Coarse age, e.g. `3d 4h`, `5h 12m` or `12m`
+fn format_age(age: Duration) -> CompactString {
+    let minutes = age.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+
+    match () {
+        _ if days > 0 => format_compact!("{days}d {hours}h"),
+        _ if hours > 0 => format_compact!("{hours}h {minutes}m"),
+        _ => format_compact!("{minutes}m"),
+    }
+}
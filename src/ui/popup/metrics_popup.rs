@@ -0,0 +1,179 @@
+use chrono::Duration;
+use compact_str::{CompactString, ToCompactString};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    prelude::{Line, StatefulWidget, Text, Widget},
+    text::Span,
+    widgets::{Cell, Row, Table},
+};
+use tachyonfx::RefRect;
+
+use crate::{
+    event::GlomEvent,
+    id::ProjectId,
+    metrics::{DeliveryMetrics, MetricsReport, format_percent},
+    theme::theme,
+    ui::{format_duration, fx::popup_window, popup::utility::CenteredShrink},
+};
+
+/// delivery metrics popup, opened from the project details popup
+pub struct MetricsPopup {}
+
+/// state of the delivery metrics popup
+pub struct MetricsPopupState {
+    pub project_id: ProjectId,
+    /// `None` while loading
+    report: Option<MetricsReport>,
+    pub popup_area: RefRect,
+}
+
+impl MetricsPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl MetricsPopupState {
+    pub fn new(project_id: ProjectId, popup_area: RefRect) -> Self {
+        Self { project_id, report: None, popup_area }
+    }
+
+    pub fn set_report(&mut self, report: MetricsReport) {
+        self.report = Some(report);
+    }
+
+    /// Event copying the metrics to the clipboard
+    pub fn export_event(&self) -> Option<GlomEvent> {
+        self.report
+            .as_ref()
+            .map(|r| GlomEvent::MetricsExport(r.clone()))
+    }
+
+    fn metric_rows(windows: &[DeliveryMetrics]) -> Vec<Row<'static>> {
+        let duration = |d: Option<Duration>| d.map_or_else(|| "-".into(), format_duration);
+
+        let metrics: [(&str, Vec<CompactString>); 5] = [
+            (
+                "runs",
+                windows
+                    .iter()
+                    .map(|m| m.runs.to_compact_string())
+                    .collect(),
+            ),
+            (
+                "success rate",
+                windows
+                    .iter()
+                    .map(|m| format_percent(m.success_rate))
+                    .collect(),
+            ),
+            (
+                "mean time to recovery",
+                windows
+                    .iter()
+                    .map(|m| duration(m.mttr()))
+                    .collect(),
+            ),
+            (
+                "change failure rate",
+                windows
+                    .iter()
+                    .map(|m| format_percent(m.change_failure_rate))
+                    .collect(),
+            ),
+            (
+                "median run time",
+                windows
+                    .iter()
+                    .map(|m| duration(m.median_duration()))
+                    .collect(),
+            ),
+        ];
+
+        metrics
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (name, values))| {
+                let name = Cell::from(Span::from(name).style(theme().pipeline_name));
+                let values = values.into_iter().map(|v| {
+                    Cell::from(
+                        Line::from(v.to_string())
+                            .style(theme().time)
+                            .right_aligned(),
+                    )
+                });
+
+                Row::new(std::iter::once(name).chain(values)).style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        // borders, branch, header, five metrics and the footer
+        let area = screen.inner_centered(screen.width.min(72), 11);
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for MetricsPopup {
+    type State = MetricsPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        popup_window(
+            "Delivery Metrics",
+            Some(vec![
+                ("ESC", "close"),
+                ("r", "refresh"),
+                ("y", "copy as CSV"),
+            ]),
+        )
+        .render(area, buf);
+
+        let content_area = area.inner(Margin::new(2, 1));
+        let Some(report) = state.report.as_ref() else {
+            Text::from(Line::from("computing metrics...").style(theme().project_description))
+                .render(content_area, buf);
+            return;
+        };
+
+        let [branch_area, table_area, footer_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(6), Constraint::Length(1)])
+                .areas(content_area);
+
+        Line::from(vec![
+            Span::from("default branch ").style(theme().project_description),
+            Span::from(report.branch.to_string()).style(theme().pipeline_branch),
+        ])
+        .render(branch_area, buf);
+
+        let mut widths = vec![Constraint::Fill(1)];
+        widths.extend(
+            report
+                .windows
+                .iter()
+                .map(|_| Constraint::Length(10)),
+        );
+
+        let header = std::iter::once(Line::from(""))
+            .chain(
+                report
+                    .windows
+                    .iter()
+                    .map(|m| Line::from(format!("{} days", m.window_days)).right_aligned()),
+            )
+            .collect::<Vec<_>>();
+
+        let table = Table::new(MetricsPopupState::metric_rows(&report.windows), widths)
+            .header(Row::new(header).style(theme().input_label))
+            .column_spacing(1);
+        Widget::render(table, table_area, buf);
+
+        Line::from("from the runs recorded in the run history")
+            .style(theme().date)
+            .render(footer_area, buf);
+    }
+}
@@ -1,8 +1,22 @@
+mod caches_popup;
 mod config_popup;
+mod environments_popup;
+mod history_popup;
+mod metrics_popup;
 mod pipeline_actions_popup;
 mod project_details_popup;
+mod pull_requests_popup;
+mod run_filter_popup;
+mod runners_popup;
 mod utility;
 
+pub use caches_popup::*;
 pub use config_popup::*;
+pub use environments_popup::*;
+pub use history_popup::*;
+pub use metrics_popup::*;
 pub use pipeline_actions_popup::*;
 pub use project_details_popup::*;
+pub use pull_requests_popup::*;
+pub use run_filter_popup::*;
+pub use runners_popup::*;
@@ -1,4 +1,8 @@
-use compact_str::ToCompactString;
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::Duration;
+use compact_str::{CompactString, ToCompactString};
+use itertools::Itertools;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Direction, Layout, Margin, Rect},
@@ -9,9 +13,15 @@ use ratatui::{
 use tachyonfx::RefRect;
 
 use crate::{
-    domain::{Pipeline, Project},
+    client::config::RunFilter,
+    domain::{Pipeline, Project, RepositoryInfo},
+    durations::WorkflowDurations,
+    history::JobFlakiness,
     theme::theme,
-    ui::{fx::popup_window, popup::utility::CenteredShrink, widget::PipelineTable},
+    ui::{
+        format_duration, format_size, fx::popup_window, popup::utility::CenteredShrink,
+        widget::PipelineTable,
+    },
 };
 
 /// Project details popup
@@ -20,8 +30,21 @@ pub struct ProjectDetailsPopup {}
 /// State of the project details popup
 pub struct ProjectDetailsPopupState {
     pub project: Project,
+    run_filter_summary: CompactString,
+    billing_multipliers: BTreeMap<CompactString, f64>,
+    /// queue time above which jobs are highlighted
+    queue_threshold: Duration,
     project_namespace: Text<'static>,
     project_stat_summary: Text<'static>,
+    project_repository_summary: Text<'static>,
+    billing_summary: Option<Line<'static>>,
+    queue_summary: Option<Line<'static>>,
+    /// flakiness of the project's jobs, from the run history
+    flakiness: Vec<JobFlakiness>,
+    flaky_summary: Option<Line<'static>>,
+    /// sparklines of the durations of recent successful runs per workflow
+    duration_sparklines: Text<'static>,
+    run_durations: Vec<WorkflowDurations>,
     pub pipelines: PipelineTable,
     pub pipelines_table_state: TableState,
     pub popup_area: RefRect,
@@ -35,47 +58,385 @@ impl ProjectDetailsPopup {
 
 impl ProjectDetailsPopupState {
     pub fn with_project(&self, project: Project) -> Self {
-        Self::new(project, self.popup_area.clone())
+        let mut state = Self::from_summary(
+            project,
+            self.run_filter_summary.clone(),
+            self.billing_multipliers.clone(),
+            self.queue_threshold,
+            self.popup_area.clone(),
+        );
+        state.set_flakiness(self.flakiness.clone());
+        state.set_run_durations(self.run_durations.clone());
+        state
     }
 
-    pub fn new(project: Project, popup_area: RefRect) -> ProjectDetailsPopupState {
-        let (namespace, name) = project.path_and_name();
+    pub fn new(
+        project: Project,
+        run_filter: &RunFilter,
+        billing_multipliers: BTreeMap<CompactString, f64>,
+        queue_threshold: Duration,
+        popup_area: RefRect,
+    ) -> Self {
+        Self::from_summary(
+            project,
+            run_filter.summary(),
+            billing_multipliers,
+            queue_threshold,
+            popup_area,
+        )
+    }
 
-        let description = match &project.description {
-            Some(d) => d.to_string(),
-            None => String::new(),
-        };
+    pub fn set_run_filter(&mut self, run_filter: &RunFilter) {
+        self.run_filter_summary = run_filter.summary();
+        self.project_namespace =
+            Self::create_namespace_text(&self.project, &self.run_filter_summary);
+    }
 
-        let project_namespace = Text::from(vec![
-            Line::from(name.to_string()).style(theme().project_name),
-            Line::from(namespace.trim_end_matches('/').to_string()).style(theme().project_parents),
-            Line::from(description).style(theme().project_description),
-        ]);
+    pub fn set_flakiness(&mut self, flakiness: Vec<JobFlakiness>) {
+        self.flaky_summary = Self::create_flaky_line(&flakiness);
+        self.pipelines = PipelineTable::with_likely_flaky(
+            &self.project.recent_pipelines(),
+            &Self::likely_flaky(&flakiness),
+            self.queue_threshold,
+        );
+        self.flakiness = flakiness;
+    }
+
+    pub fn set_run_durations(&mut self, run_durations: Vec<WorkflowDurations>) {
+        self.duration_sparklines = Self::create_duration_sparklines(&run_durations);
+        self.run_durations = run_durations;
+    }
+
+    fn likely_flaky(flakiness: &[JobFlakiness]) -> HashSet<&str> {
+        flakiness
+            .iter()
+            .filter(|f| f.is_likely_flaky())
+            .map(|f| f.name.as_str())
+            .collect()
+    }
+
+    fn from_summary(
+        project: Project,
+        run_filter_summary: CompactString,
+        billing_multipliers: BTreeMap<CompactString, f64>,
+        queue_threshold: Duration,
+        popup_area: RefRect,
+    ) -> ProjectDetailsPopupState {
+        let project_namespace = Self::create_namespace_text(&project, &run_filter_summary);
 
         let project_stat_summary = Self::create_stats_text(
+            &project.default_branch,
             project.commit_count,
             project.repo_size_kb,
             project.artifacts_size_kb,
+            project.cache_size_kb,
             project.statistics_loading,
         );
 
+        let project_repository_summary =
+            Self::create_repository_text(&project.repository, project.statistics_loading);
+
+        let billing_summary = Self::create_billing_line(&project, &billing_multipliers);
+        let queue_summary = Self::create_queue_line(&project, queue_threshold);
+
         let pipelines: Vec<&Pipeline> = project.recent_pipelines();
-        let pipelines = PipelineTable::new(&pipelines);
+        let pipelines = PipelineTable::new(&pipelines, queue_threshold);
 
         ProjectDetailsPopupState {
             project,
+            run_filter_summary,
+            billing_multipliers,
+            queue_threshold,
             project_namespace,
             project_stat_summary,
+            project_repository_summary,
+            billing_summary,
+            queue_summary,
+            flakiness: Vec::new(),
+            flaky_summary: None,
+            duration_sparklines: Text::default(),
+            run_durations: Vec::new(),
             pipelines,
             pipelines_table_state: TableState::default().with_selected(0),
             popup_area,
         }
     }
 
+    fn create_namespace_text(project: &Project, run_filter_summary: &str) -> Text<'static> {
+        let (namespace, name) = project.path_and_name();
+
+        let description = match &project.description {
+            Some(d) => d.to_string(),
+            None => String::new(),
+        };
+
+        let mut lines = vec![
+            Line::from(name.to_string()).style(theme().project_name),
+            Line::from(namespace.trim_end_matches('/').to_string()).style(theme().project_parents),
+            Line::from(description).style(theme().project_description),
+        ];
+
+        if !run_filter_summary.is_empty() {
+            lines.push(Line::from(vec![
+                Span::from("filter: ").style(theme().pipeline_source),
+                Span::from(run_filter_summary.to_string()).style(theme().pipeline_branch),
+            ]));
+        }
+
+        let repository = &project.repository;
+        let badges: Vec<&str> = [
+            Some(repository.visibility.as_str()).filter(|v| !v.is_empty()),
+            repository.license.as_deref(),
+            repository.archived.then_some("archived"),
+            repository.fork.then_some("fork"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if !badges.is_empty() {
+            lines.push(Line::from(badges.join(" · ")).style(theme().project_badge));
+        }
+
+        if !repository.topics.is_empty() {
+            let topics = repository
+                .topics
+                .iter()
+                .map(|t| format!("#{t}"))
+                .join(" ");
+            lines.push(Line::from(topics).style(theme().project_topic));
+        }
+
+        Text::from(lines)
+    }
+
+    fn create_repository_text(repository: &RepositoryInfo, loading: bool) -> Text<'static> {
+        let count = |value: u32| {
+            if loading && value == 0 {
+                CompactString::const_new("···")
+            } else {
+                value.to_compact_string()
+            }
+        };
+
+        let language = match &repository.language {
+            Some(language) => language.as_str(),
+            None if loading => "···",
+            None => "-",
+        };
+
+        let width = 22;
+        let style = theme().project_commits;
+
+        Text::from(vec![
+            Self::create_aligned_line("Language:", language, width, style),
+            Self::create_aligned_line("Stars:", &count(repository.stars), width, style),
+            Self::create_aligned_line("Forks:", &count(repository.forks), width, style),
+            Self::create_aligned_line("Watchers:", &count(repository.watchers), width, style),
+            Self::create_aligned_line("Issues:", &count(repository.open_issues), width, style),
+            Self::create_aligned_line(
+                "Pull requests:",
+                &count(repository.open_pull_requests),
+                width,
+                style,
+            ),
+        ])
+    }
+
+    /// Billable time of the runs with loaded timing per runner OS, with an
+    /// estimated cost if multipliers are configured
+    fn create_billing_line(
+        project: &Project,
+        billing_multipliers: &BTreeMap<CompactString, f64>,
+    ) -> Option<Line<'static>> {
+        let (runs, billable) = project.billable_summary();
+        if billable.by_os.is_empty() {
+            return None;
+        }
+
+        let mut spans = vec![
+            Span::from("Billable").style(theme().project_commits[1]),
+            Span::from(format!(" ({runs} runs)")).style(theme().date),
+        ];
+
+        for (os, ms) in &billable.by_os {
+            let duration = Duration::milliseconds(*ms as i64);
+            spans.push(Span::from(format!("  {os} ")).style(theme().project_commits[1]));
+            spans.push(Span::from(format_duration(duration).to_string()).style(theme().time));
+        }
+
+        if !billing_multipliers.is_empty() {
+            let cost: f64 = billable
+                .by_os
+                .iter()
+                .filter_map(|(os, ms)| {
+                    billing_multipliers
+                        .get(os)
+                        .map(|m| *ms as f64 / 60_000.0 * m)
+                })
+                .sum();
+            spans.push(Span::from("  est. cost ").style(theme().project_commits[1]));
+            spans.push(Span::from(format!("{cost:.2}")).style(theme().project_commits[0]));
+        }
+
+        Some(Line::from(spans))
+    }
+
+    /// Average queue time of the loaded jobs per runner label, highlighting labels
+    /// whose jobs wait longer than the threshold
+    fn create_queue_line(project: &Project, queue_threshold: Duration) -> Option<Line<'static>> {
+        let by_label = project.queue_time_by_label();
+        if by_label.is_empty() {
+            return None;
+        }
+
+        let mut spans = vec![Span::from("Queue time").style(theme().project_commits[1])];
+        for (label, queue_time) in by_label {
+            let style =
+                if queue_time > queue_threshold { theme().status_pending } else { theme().time };
+            spans.push(Span::from(format!("  {label} ")).style(theme().project_commits[1]));
+            spans.push(Span::from(format_duration(queue_time).to_string()).style(style));
+        }
+
+        Some(Line::from(spans))
+    }
+
+    /// Jobs that both failed and passed on the same commit, with the share of
+    /// their failures that were flaky
+    fn create_flaky_line(flakiness: &[JobFlakiness]) -> Option<Line<'static>> {
+        if flakiness.is_empty() {
+            return None;
+        }
+
+        let mut spans = vec![Span::from("Flaky jobs").style(theme().project_commits[1])];
+        for job in flakiness.iter().take(5) {
+            let name_style = if job.is_likely_flaky() {
+                theme().status_pending
+            } else {
+                theme().project_description
+            };
+            spans.push(Span::from(format!("  {} ", job.name)).style(name_style));
+            spans.push(Span::from(format!("{:.0}%", job.score() * 100.0)).style(theme().time));
+            spans.push(
+                Span::from(format!(" ({}/{})", job.flaky_commits, job.failed_commits))
+                    .style(theme().date),
+            );
+        }
+
+        Some(Line::from(spans))
+    }
+
+    /// Sparkline of the durations of each workflow's recent successful runs, oldest
+    /// first, followed by the latest and median duration
+    fn create_duration_sparklines(run_durations: &[WorkflowDurations]) -> Text<'static> {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        const MAX_WORKFLOWS: usize = 6;
+
+        let name_width = run_durations
+            .iter()
+            .map(|w| w.workflow.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let lines = run_durations
+            .iter()
+            .take(MAX_WORKFLOWS)
+            .map(|w| {
+                let min = w
+                    .durations
+                    .iter()
+                    .min()
+                    .copied()
+                    .unwrap_or_default();
+                let max = w
+                    .durations
+                    .iter()
+                    .max()
+                    .copied()
+                    .unwrap_or_default();
+                let range = (max - min).num_seconds().max(1) as f64;
+                let sparkline: String = w
+                    .durations
+                    .iter()
+                    .map(|d| {
+                        let level = (d.num_seconds() - min.num_seconds()) as f64 / range;
+                        BARS[(level * (BARS.len() - 1) as f64).round() as usize]
+                    })
+                    .collect();
+
+                let mut sorted = w.durations.clone();
+                sorted.sort();
+                let median = sorted
+                    .get(sorted.len() / 2)
+                    .copied()
+                    .unwrap_or_default();
+                let latest = w.durations.last().copied().unwrap_or_default();
+
+                Line::from(vec![
+                    Span::from(format!("{:name_width$}  ", w.workflow))
+                        .style(theme().pipeline_name),
+                    Span::from(sparkline).style(theme().time),
+                    Span::from("  last ").style(theme().project_commits[1]),
+                    Span::from(format_duration(latest).to_string()).style(theme().time),
+                    Span::from("  median ").style(theme().project_commits[1]),
+                    Span::from(format_duration(median).to_string()).style(theme().time),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        Text::from(lines)
+    }
+
+    /// Proportional bar of the repository's languages, followed by a legend
+    fn create_languages_text(repository: &RepositoryInfo, width: u16) -> Text<'static> {
+        let shares = repository.language_shares();
+        let palette = &theme().project_languages;
+
+        let mut bar = Vec::new();
+        let mut legend = Vec::new();
+        let mut filled = 0;
+        let mut cumulative = 0.0;
+        for (idx, (name, share)) in shares.iter().enumerate() {
+            // the last palette color groups all remaining languages
+            let color_idx = idx.min(palette.len() - 1);
+            cumulative += share;
+            let end = ((cumulative / 100.0) * width as f64).round() as usize;
+            if end > filled {
+                bar.push(Span::from("━".repeat(end - filled)).style(palette[color_idx]));
+                filled = end;
+            }
+
+            if idx < palette.len() - 1 {
+                legend.push(Span::from("● ").style(palette[color_idx]));
+                legend.push(
+                    Span::from(format!("{name} {share:.1}%  ")).style(theme().project_description),
+                );
+            }
+        }
+
+        if shares.len() >= palette.len() {
+            legend.push(Span::from("● ").style(palette[palette.len() - 1]));
+            legend.push(Span::from("other").style(theme().project_description));
+        }
+
+        Text::from(vec![Line::from(bar), Line::from(legend)])
+    }
+
+    fn details_height(&self) -> u16 {
+        let languages_h = if self.project.repository.languages.is_empty() { 0 } else { 2 };
+        let billing_h = self.billing_summary.iter().len() as u16;
+        let queue_h = self.queue_summary.iter().len() as u16;
+        let flaky_h = self.flaky_summary.iter().len() as u16;
+        let durations_h = self.duration_sparklines.height() as u16;
+        6 + languages_h + billing_h + queue_h + flaky_h + durations_h
+    }
+
     fn create_stats_text(
+        branch: &str,
         commit_count: u32,
         repo_size_kb: u64,
         artifacts_size_kb: u64,
+        cache_size_kb: u64,
         loading: bool,
     ) -> Text<'static> {
         let commits_value = if loading && commit_count == 0 {
@@ -84,21 +445,25 @@ impl ProjectDetailsPopupState {
             &commit_count.to_compact_string()
         };
 
-        let repo_size_value = if loading && repo_size_kb == 0 {
+        let repo_size_value =
+            if loading && repo_size_kb == 0 { "···" } else { &format_size(repo_size_kb) };
+
+        let artifacts_size_value = if loading && artifacts_size_kb == 0 {
             "···"
         } else {
-            &Self::format_size(repo_size_kb)
+            &format_size(artifacts_size_kb)
         };
 
-        let artifacts_size_value = if loading && artifacts_size_kb == 0 {
+        let cache_size_value = if loading && cache_size_kb == 0 {
             "···"
         } else {
-            &Self::format_size(artifacts_size_kb)
+            &format_size(cache_size_kb)
         };
 
         let width = 22;
 
         Text::from(vec![
+            Self::create_aligned_line("Branch:", branch, width, theme().project_commits),
             Self::create_aligned_line("Commits:", commits_value, width, theme().project_commits),
             Self::create_aligned_line("Repository:", repo_size_value, width, theme().project_size),
             Self::create_aligned_line(
@@ -107,6 +472,7 @@ impl ProjectDetailsPopupState {
                 width,
                 theme().project_size,
             ),
+            Self::create_aligned_line("Caches:", cache_size_value, width, theme().project_size),
         ])
     }
 
@@ -138,18 +504,9 @@ impl ProjectDetailsPopupState {
         }
     }
 
-    fn format_size(size_kb: u64) -> String {
-        let (size, unit) = match size_kb {
-            s if s < 1024 => (s as f32, "KB"),
-            s if s < 1024 * 1024 => (s as f32 / 1024.0, "MB"),
-            s => (s as f32 / (1024.0 * 1024.0), "GB"),
-        };
-        format!("{size:.2} {unit}")
-    }
-
     pub fn update_popup_area(&self, screen: Rect) -> Rect {
         let pipeline_table_h = 2 * self.pipelines.rows.len() as u16;
-        let project_details_h = 4;
+        let project_details_h = self.details_height();
         let total_height = 2 + project_details_h + pipeline_table_h;
 
         let a = screen.inner_centered(screen.width, total_height);
@@ -163,7 +520,7 @@ impl StatefulWidget for ProjectDetailsPopup {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let pipeline_table_h = 2 * state.pipelines.rows.len() as u16;
-        let project_details_h = 4;
+        let project_details_h = state.details_height();
 
         let area = state.update_popup_area(area);
 
@@ -172,6 +529,14 @@ impl StatefulWidget for ProjectDetailsPopup {
             Some(vec![
                 ("ESC", "close"),
                 ("↑ ↓", "selection"),
+                ("f", "filter runs"),
+                ("c", "caches"),
+                ("e", "environments"),
+                ("p", "pull requests"),
+                ("h", "history"),
+                ("m", "metrics"),
+                ("w", "watch run"),
+                ("W", "watch branch"),
                 ("↵", "actions..."),
             ]),
         )
@@ -186,10 +551,26 @@ impl StatefulWidget for ProjectDetailsPopup {
             ])
             .split(content_area);
 
+        let [summary_area, billing_area, queue_area, flaky_area, durations_area, languages_area] =
+            Layout::vertical([
+                Constraint::Length(6),
+                Constraint::Length(state.billing_summary.iter().len() as u16),
+                Constraint::Length(state.queue_summary.iter().len() as u16),
+                Constraint::Length(state.flaky_summary.iter().len() as u16),
+                Constraint::Length(state.duration_sparklines.height() as u16),
+                Constraint::Fill(1),
+            ])
+            .areas(outer_layout[0]);
+
         let project_details_layout = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(100), Constraint::Length(22)])
-            .split(outer_layout[0]);
+            .constraints([
+                Constraint::Percentage(100),
+                Constraint::Length(22),
+                Constraint::Length(2),
+                Constraint::Length(22),
+            ])
+            .split(summary_area);
 
         state
             .project_namespace
@@ -197,14 +578,45 @@ impl StatefulWidget for ProjectDetailsPopup {
             .render(project_details_layout[0], buf);
 
         state
-            .project_stat_summary
+            .project_repository_summary
             .clone()
             .render(project_details_layout[1], buf);
 
-        PipelineTable::new(&state.project.recent_pipelines()).render(
-            outer_layout[1],
-            buf,
-            &mut state.pipelines_table_state,
-        );
+        state
+            .project_stat_summary
+            .clone()
+            .render(project_details_layout[3], buf);
+
+        if let Some(billing_summary) = &state.billing_summary {
+            billing_summary.clone().render(billing_area, buf);
+        }
+
+        if let Some(queue_summary) = &state.queue_summary {
+            queue_summary.clone().render(queue_area, buf);
+        }
+
+        if let Some(flaky_summary) = &state.flaky_summary {
+            flaky_summary.clone().render(flaky_area, buf);
+        }
+
+        state
+            .duration_sparklines
+            .clone()
+            .render(durations_area, buf);
+
+        if !state.project.repository.languages.is_empty() {
+            ProjectDetailsPopupState::create_languages_text(
+                &state.project.repository,
+                languages_area.width,
+            )
+            .render(languages_area, buf);
+        }
+
+        PipelineTable::with_likely_flaky(
+            &state.project.recent_pipelines(),
+            &ProjectDetailsPopupState::likely_flaky(&state.flakiness),
+            state.queue_threshold,
+        )
+        .render(outer_layout[1], buf, &mut state.pipelines_table_state);
     }
 }
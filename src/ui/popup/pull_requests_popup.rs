@@ -0,0 +1,348 @@
+use chrono::{Duration, Local};
+use compact_str::{CompactString, format_compact};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Margin, Rect},
+    prelude::{Line, StatefulWidget, Text, Widget},
+    style::Style,
+    text::Span,
+    widgets::{Cell, Row, Table, TableState},
+};
+use tachyonfx::RefRect;
+
+use crate::{
+    domain::{IconRepresentable, Pipeline, PipelineStatus, PullRequest, ReviewDecision},
+    event::GlomEvent,
+    id::ProjectId,
+    theme::theme,
+    ui::{fx::popup_window, popup::utility::CenteredShrink, widget::PipelineTable},
+};
+
+/// open pull requests popup
+pub struct PullRequestsPopup {}
+
+/// state of the open pull requests popup
+pub struct PullRequestsPopupState {
+    pub project_id: ProjectId,
+    /// open pull requests; `None` while loading
+    pull_requests: Option<Vec<PullRequest>>,
+    /// workflow runs of the pull request shown below the list
+    runs: Option<PullRequestRuns>,
+    pub focus: PullRequestsFocus,
+    pub table_state: TableState,
+    pub runs_table_state: TableState,
+    /// queue time above which jobs are highlighted
+    queue_threshold: Duration,
+    pub popup_area: RefRect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PullRequestsFocus {
+    PullRequests,
+    Runs,
+}
+
+struct PullRequestRuns {
+    number: u64,
+    head_ref: CompactString,
+    /// `None` while loading
+    pipelines: Option<Vec<Pipeline>>,
+}
+
+impl PullRequestsPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PullRequestsPopupState {
+    pub fn new(project_id: ProjectId, queue_threshold: Duration, popup_area: RefRect) -> Self {
+        Self {
+            project_id,
+            pull_requests: None,
+            runs: None,
+            focus: PullRequestsFocus::PullRequests,
+            table_state: TableState::default().with_selected(0),
+            runs_table_state: TableState::default(),
+            queue_threshold,
+            popup_area,
+        }
+    }
+
+    pub fn set_pull_requests(&mut self, pull_requests: Vec<PullRequest>) {
+        let selected = self
+            .table_state
+            .selected()
+            .map(|idx| idx.min(pull_requests.len().saturating_sub(1)));
+
+        self.table_state.select(selected);
+        self.pull_requests = Some(pull_requests);
+    }
+
+    pub fn set_runs(&mut self, number: u64, pipelines: Vec<Pipeline>) {
+        if let Some(runs) = self.runs.as_mut().filter(|r| r.number == number) {
+            self.runs_table_state
+                .select((!pipelines.is_empty()).then_some(0));
+            runs.pipelines = Some(pipelines);
+        }
+    }
+
+    pub fn pull_request_count(&self) -> usize {
+        self.pull_requests.as_ref().map_or(0, Vec::len)
+    }
+
+    pub fn run_count(&self) -> usize {
+        self.runs
+            .as_ref()
+            .and_then(|r| r.pipelines.as_ref())
+            .map_or(0, Vec::len)
+    }
+
+    fn selected_pull_request(&self) -> Option<&PullRequest> {
+        let idx = self.table_state.selected()?;
+        self.pull_requests.as_ref()?.get(idx)
+    }
+
+    /// Shows the runs of the selected pull request, returning the event requesting them
+    pub fn show_selected_runs(&mut self) -> Option<GlomEvent> {
+        let pr = self.selected_pull_request()?;
+        let event = GlomEvent::PullRequestRunsFetch(
+            self.project_id.clone(),
+            pr.number,
+            pr.head_sha.clone(),
+        );
+
+        self.runs = Some(PullRequestRuns {
+            number: pr.number,
+            head_ref: pr.head_ref.clone(),
+            pipelines: None,
+        });
+        self.runs_table_state.select(None);
+        self.focus = PullRequestsFocus::Runs;
+
+        Some(event)
+    }
+
+    pub fn focus_pull_requests(&mut self) {
+        self.focus = PullRequestsFocus::PullRequests;
+    }
+
+    /// Event opening the selected pull request in the browser
+    pub fn selected_pull_request_url_event(&self) -> Option<GlomEvent> {
+        self.selected_pull_request()
+            .map(|pr| GlomEvent::UrlOpen(pr.url.clone()))
+    }
+
+    /// Event opening the selected run of the pull request in the browser
+    pub fn selected_run_url_event(&self) -> Option<GlomEvent> {
+        let idx = self.runs_table_state.selected()?;
+        self.runs
+            .as_ref()?
+            .pipelines
+            .as_ref()?
+            .get(idx)
+            .map(|p| GlomEvent::UrlOpen(p.url.clone()))
+    }
+
+    fn rows(&self) -> Vec<Row<'static>> {
+        self.pull_requests
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(idx, pr)| {
+                let checks_status = pr.checks.status();
+                let checks_icon = checks_status
+                    .as_ref()
+                    .map_or(CompactString::const_new(" "), PipelineStatus::icon);
+
+                let (review_style, mergeable_style) = (
+                    Self::review_style(pr.review),
+                    Self::mergeable_style(&pr.mergeable_state),
+                );
+
+                let mut title = vec![Span::from(format!("#{} ", pr.number)).style(theme().date)];
+                if pr.draft {
+                    title.push(Span::from("draft ").style(theme().project_badge));
+                }
+                title.push(Span::from(pr.title.to_string()).style(theme().pipeline_name));
+
+                Row::new(vec![
+                    Cell::from(checks_icon.to_string()),
+                    Cell::from(Line::from(title)),
+                    Cell::from(Span::from(pr.author.to_string()).style(theme().pipeline_source)),
+                    Cell::from(Span::from(pr.review.as_str()).style(review_style)),
+                    Cell::from(Span::from(pr.mergeable_state.to_string()).style(mergeable_style)),
+                    Cell::from(Span::from(Self::checks_summary(pr)).style(theme().commit_title)),
+                    Cell::from(
+                        Span::from(
+                            pr.updated_at
+                                .with_timezone(&Local)
+                                .format("%a, %d %b %H:%M")
+                                .to_string(),
+                        )
+                        .style(theme().date),
+                    ),
+                ])
+                .style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    /// Count of passed, failed and pending checks, e.g. `4✓ 1✗ 2…`
+    fn checks_summary(pr: &PullRequest) -> CompactString {
+        let checks = &pr.checks.checks;
+        if checks.is_empty() {
+            return "no checks".into();
+        }
+
+        let failed = checks
+            .iter()
+            .filter(|c| c.status.is_failure())
+            .count();
+        let pending = checks
+            .iter()
+            .filter(|c| c.status.is_active())
+            .count();
+        let passed = checks.len() - failed - pending;
+
+        format_compact!("{passed}✓ {failed}✗ {pending}…")
+    }
+
+    fn review_style(review: ReviewDecision) -> Style {
+        match review {
+            ReviewDecision::Approved => theme().status_ok,
+            ReviewDecision::ChangesRequested => theme().status_failed,
+            ReviewDecision::ReviewRequired => theme().status_pending,
+            ReviewDecision::Unknown => theme().date,
+        }
+    }
+
+    fn mergeable_style(state: &str) -> Style {
+        match state {
+            "clean" | "has_hooks" => theme().status_ok,
+            "dirty" | "blocked" => theme().status_failed,
+            "behind" | "unstable" => theme().status_pending,
+            _ => theme().date,
+        }
+    }
+
+    fn runs_height(&self) -> u16 {
+        match &self.runs {
+            Some(runs) => {
+                2 + 2 * runs
+                    .pipelines
+                    .as_ref()
+                    .map_or(1, |p| p.len().max(1)) as u16
+            },
+            None => 0,
+        }
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        let rows = self.pull_request_count().max(1) as u16;
+        let area = screen.inner_centered(screen.width, 3 + rows + self.runs_height());
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for PullRequestsPopup {
+    type State = PullRequestsPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        let shortcuts = match state.focus {
+            PullRequestsFocus::PullRequests => vec![
+                ("ESC", "close"),
+                ("↑ ↓", "selection"),
+                ("↵", "runs"),
+                ("w", "browse to PR"),
+                ("r", "refresh"),
+            ],
+            PullRequestsFocus::Runs => {
+                vec![("ESC", "back"), ("↑ ↓", "selection"), ("↵", "browse to run")]
+            },
+        };
+        popup_window("Pull Requests", Some(shortcuts)).render(area, buf);
+
+        let content_area = area.inner(Margin::new(1, 1));
+        let message = match &state.pull_requests {
+            None => Some("loading pull requests..."),
+            Some(pull_requests) if pull_requests.is_empty() => Some("no open pull requests"),
+            Some(_) => None,
+        };
+
+        if let Some(message) = message {
+            Text::from(Line::from(message).style(theme().project_description))
+                .render(content_area, buf);
+            return;
+        }
+
+        let [pull_requests_area, runs_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(state.runs_height())])
+                .areas(content_area);
+
+        let header =
+            Row::new(["", "pull request", "author", "review", "mergeable", "checks", "updated"])
+                .style(theme().input_label);
+
+        let max_author = state
+            .pull_requests
+            .iter()
+            .flatten()
+            .map(|pr| pr.author.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(6);
+
+        let table = Table::new(
+            state.rows(),
+            [
+                Constraint::Length(2),
+                Constraint::Percentage(100),
+                Constraint::Length(max_author as u16),
+                Constraint::Length(17),
+                Constraint::Length(9),
+                Constraint::Length(12),
+                Constraint::Length(17),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(theme().highlight_symbol)
+        .column_spacing(1);
+
+        StatefulWidget::render(table, pull_requests_area, buf, &mut state.table_state);
+
+        let Some(runs) = &state.runs else {
+            return;
+        };
+
+        let [_, title_area, table_area] =
+            Layout::vertical([Constraint::Length(1), Constraint::Length(1), Constraint::Fill(1)])
+                .areas(runs_area);
+
+        Line::from(vec![
+            Span::from(format!("runs of #{} ", runs.number)).style(theme().input_label),
+            Span::from(runs.head_ref.to_string()).style(theme().pipeline_branch),
+        ])
+        .render(title_area, buf);
+
+        match &runs.pipelines {
+            None => Line::from("loading runs...")
+                .style(theme().project_description)
+                .render(table_area, buf),
+            Some(pipelines) if pipelines.is_empty() => Line::from("no workflow runs")
+                .style(theme().project_description)
+                .render(table_area, buf),
+            Some(pipelines) => {
+                let pipelines: Vec<&Pipeline> = pipelines.iter().collect();
+                PipelineTable::new(&pipelines, state.queue_threshold).render(
+                    table_area,
+                    buf,
+                    &mut state.runs_table_state,
+                );
+            },
+        }
+    }
+}
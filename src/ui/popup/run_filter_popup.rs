@@ -0,0 +1,305 @@
+use compact_str::{CompactString, ToCompactString};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Margin, Position, Rect},
+    prelude::{Line, StatefulWidget, Style, Text, Widget},
+    text::Span,
+};
+use tachyonfx::RefRect;
+use tui_input::Input;
+
+use crate::{
+    client::config::{CreatedRange, RunFilter, RunStatusFilter},
+    id::ProjectId,
+    theme::theme,
+    ui::{fx::popup_window, popup::utility::CenteredShrink, widget::InputField},
+};
+
+const ANY: &str = "any";
+
+const EVENT_OPTIONS: [&str; 7] =
+    [ANY, "push", "pull_request", "schedule", "workflow_dispatch", "release", "merge_group"];
+
+/// workflow run filter popup, opened from the project details popup
+pub struct RunFilterPopup {}
+
+pub struct RunFilterPopupState {
+    pub project_id: ProjectId,
+    active_input_idx: u16,
+    pub cursor_position: Position,
+    input_fields: Vec<InputField>,
+    pub error_message: Option<CompactString>,
+    pub popup_area: RefRect,
+}
+
+impl RunFilterPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl RunFilterPopupState {
+    pub fn new(project_id: ProjectId, filter: RunFilter, popup_area: RefRect) -> Self {
+        let status_options: Vec<&'static str> = std::iter::once(ANY)
+            .chain(RunStatusFilter::ALL.iter().map(|s| s.as_str()))
+            .collect();
+
+        let input_fields = vec![
+            text_field("branch", branch_description(), filter.branch.as_deref()),
+            dropdown_field(
+                "event",
+                event_description(),
+                EVENT_OPTIONS.to_vec(),
+                filter.event.as_deref().unwrap_or(ANY),
+            ),
+            text_field("actor", actor_description(), filter.actor.as_deref()),
+            dropdown_field(
+                "status",
+                status_description(),
+                status_options,
+                filter.status.map(|s| s.as_str()).unwrap_or(ANY),
+            ),
+            text_field(
+                "head sha",
+                head_sha_description(),
+                filter.head_sha.as_deref(),
+            ),
+            dropdown_field(
+                "exclude pull requests",
+                exclude_pull_requests_description(),
+                vec!["false", "true"],
+                if filter.exclude_pull_requests { "true" } else { "false" },
+            ),
+            text_field(
+                "created",
+                created_description(),
+                filter
+                    .created
+                    .as_ref()
+                    .map(|c| c.to_string())
+                    .as_deref(),
+            ),
+        ];
+
+        Self {
+            project_id,
+            active_input_idx: 0,
+            cursor_position: Position::default(),
+            input_fields,
+            error_message: None,
+            popup_area,
+        }
+    }
+
+    pub fn select_next_input(&mut self) {
+        self.active_input_idx = (self.active_input_idx + 1) % self.input_fields.len() as u16;
+    }
+
+    pub fn select_previous_input(&mut self) {
+        self.active_input_idx = if self.active_input_idx == 0 {
+            self.input_fields.len() as u16 - 1
+        } else {
+            self.active_input_idx - 1
+        };
+    }
+
+    pub fn cycle_dropdown_next(&mut self) {
+        self.input_fields[self.active_input_idx as usize].cycle_dropdown_next();
+    }
+
+    pub fn cycle_dropdown_prev(&mut self) {
+        self.input_fields[self.active_input_idx as usize].cycle_dropdown_prev();
+    }
+
+    pub fn is_current_field_dropdown(&self) -> bool {
+        self.input_fields[self.active_input_idx as usize].is_dropdown()
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input_fields[self.active_input_idx as usize].input
+    }
+
+    pub fn input_mut(&mut self) -> &mut Input {
+        &mut self.input_fields[self.active_input_idx as usize].input
+    }
+
+    /// Builds the filter from the input fields, failing on malformed values
+    pub fn to_filter(&self) -> Result<RunFilter, CompactString> {
+        let value = |idx: usize| {
+            let v = self.input_fields[idx].input.value().trim();
+            (!v.is_empty() && v != ANY).then(|| v.to_compact_string())
+        };
+
+        let status = match value(3) {
+            Some(s) => Some(
+                RunStatusFilter::parse(&s)
+                    .ok_or_else(|| CompactString::from(format!("unknown status: {s}")))?,
+            ),
+            None => None,
+        };
+
+        let created = match value(6) {
+            Some(c) => Some(CreatedRange::parse(&c).ok_or_else(|| {
+                CompactString::from("created must be e.g. >=2025-01-01 or 2025-01-01..2025-01-31")
+            })?),
+            None => None,
+        };
+
+        Ok(RunFilter {
+            branch: value(0),
+            event: value(1),
+            actor: value(2),
+            status,
+            head_sha: value(4),
+            exclude_pull_requests: value(5).as_deref() == Some("true"),
+            created,
+        })
+    }
+
+    /// returns the style for the input, considering the selected input field.
+    fn input_style(&self, idx: u16) -> Style {
+        if idx == self.active_input_idx {
+            theme().input_selected
+        } else {
+            theme().input
+        }
+    }
+
+    fn update_cursor_position(&mut self, area: &Rect) {
+        let input = self.input();
+        self.cursor_position = Position::new(
+            area.x + 1 + input.cursor() as u16,
+            area.y + 3 + self.active_input_idx * 3, // 3 elements per input field
+        );
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        let area = screen.inner_centered(80, 3 + 3 * self.input_fields.len() as u16);
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for RunFilterPopup {
+    type State = RunFilterPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        popup_window(
+            "Run Filters",
+            Some(vec![
+                ("ESC", "close"),
+                ("↑ ↓", "navigate"),
+                ("← →", "change"),
+                ("↵", "apply"),
+            ]),
+        )
+        .render(area, buf);
+
+        let content_area = area.inner(Margin::new(1, 1));
+        let mut text: Vec<Line> = state
+            .input_fields
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, input_field)| {
+                [
+                    Line::from(input_field.label).style(theme().input_label),
+                    input_field
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| Line::from("")),
+                    Line::from(input_field.sanitized_input_display().to_string())
+                        .style(state.input_style(idx as u16)),
+                ]
+            })
+            .collect();
+
+        if let Some(error_message) = &state.error_message {
+            text.push(Line::from(error_message.to_string()).style(theme().configuration_error));
+        }
+
+        Widget::render(Text::from(text), content_area, buf);
+
+        state.update_cursor_position(&area);
+    }
+}
+
+fn text_field(label: &'static str, description: Line<'static>, value: Option<&str>) -> InputField {
+    InputField::builder()
+        .label(label)
+        .description(Some(description))
+        .input(Input::new(value.unwrap_or_default().to_string()))
+        .into()
+}
+
+fn dropdown_field(
+    label: &'static str,
+    description: Line<'static>,
+    options: Vec<&'static str>,
+    value: &str,
+) -> InputField {
+    let mut field: InputField = InputField::builder()
+        .label(label)
+        .description(Some(description))
+        .input(Input::new(options[0].to_string()))
+        .dropdown_options(Some(options))
+        .into();
+
+    field.set_dropdown_value(value);
+    field
+}
+
+fn branch_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("only runs for this ").style(theme().input_description),
+        Span::from("branch").style(theme().input_description_em),
+        Span::from(", e.g. ").style(theme().input_description),
+        Span::from("main").style(theme().input_description_em),
+    ])
+}
+
+fn event_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("only runs triggered by this ").style(theme().input_description),
+        Span::from("event").style(theme().input_description_em),
+    ])
+}
+
+fn actor_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("only runs triggered by this ").style(theme().input_description),
+        Span::from("github user").style(theme().input_description_em),
+    ])
+}
+
+fn status_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("only runs with this ").style(theme().input_description),
+        Span::from("status or conclusion").style(theme().input_description_em),
+    ])
+}
+
+fn head_sha_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("only runs for this ").style(theme().input_description),
+        Span::from("commit sha").style(theme().input_description_em),
+    ])
+}
+
+fn exclude_pull_requests_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("omit ").style(theme().input_description),
+        Span::from("pull request").style(theme().input_description_em),
+        Span::from(" details from the response").style(theme().input_description),
+    ])
+}
+
+fn created_description() -> Line<'static> {
+    Line::from(vec![
+        Span::from("created within, e.g. ").style(theme().input_description),
+        Span::from(">=2025-01-01").style(theme().input_description_em),
+        Span::from(" or ").style(theme().input_description),
+        Span::from("2025-01-01..2025-01-31").style(theme().input_description_em),
+    ])
+}
@@ -0,0 +1,225 @@
+use compact_str::{CompactString, format_compact};
+use itertools::Itertools;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Margin, Rect},
+    prelude::{Line, StatefulWidget, Text, Widget},
+    text::Span,
+    widgets::{Cell, Row, Table, TableState},
+};
+use tachyonfx::RefRect;
+
+use crate::{
+    domain::{Project, RunnerDto},
+    event::GlomEvent,
+    id::{JobId, PipelineId, ProjectId},
+    theme::theme,
+    ui::{fx::popup_window, popup::utility::CenteredShrink},
+};
+
+/// self-hosted runners popup
+pub struct RunnersPopup {}
+
+/// state of the self-hosted runners popup
+pub struct RunnersPopupState {
+    pub project_id: ProjectId,
+    /// runners of the project and its organization; `None` while loading
+    runners: Option<Vec<RunnerRow>>,
+    pub table_state: TableState,
+    pub popup_area: RefRect,
+}
+
+struct RunnerRow {
+    runner: RunnerDto,
+    job: Option<RunnerJob>,
+}
+
+/// job a busy runner is currently running
+struct RunnerJob {
+    project_id: ProjectId,
+    pipeline_id: PipelineId,
+    job_id: JobId,
+    description: CompactString,
+}
+
+impl RunnersPopup {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl RunnersPopupState {
+    pub fn new(project_id: ProjectId, popup_area: RefRect) -> Self {
+        Self {
+            project_id,
+            runners: None,
+            table_state: TableState::default().with_selected(0),
+            popup_area,
+        }
+    }
+
+    /// Replaces the runners, listing offline runners first as they are the
+    /// usual cause of queued jobs
+    pub fn set_runners(&mut self, runners: Vec<RunnerDto>, projects: &[Project]) {
+        let rows = runners
+            .into_iter()
+            .sorted_by(|a, b| {
+                (a.is_online(), !a.busy, &a.name).cmp(&(b.is_online(), !b.busy, &b.name))
+            })
+            .map(|runner| RunnerRow { runner, job: None })
+            .collect();
+
+        self.runners = Some(rows);
+        self.refresh_jobs(projects);
+    }
+
+    /// Resolves the jobs of busy runners from the loaded projects
+    pub fn refresh_jobs(&mut self, projects: &[Project]) {
+        for row in self.runners.iter_mut().flatten() {
+            row.job = row
+                .runner
+                .busy
+                .then(|| {
+                    projects.iter().find_map(|project| {
+                        project
+                            .job_on_runner(&row.runner.name)
+                            .map(|(pipeline, job)| RunnerJob {
+                                project_id: project.id.clone(),
+                                pipeline_id: pipeline.id,
+                                job_id: job.id,
+                                description: format_compact!(
+                                    "{} › {} › {}",
+                                    project.path,
+                                    pipeline.name,
+                                    job.name
+                                ),
+                            })
+                    })
+                })
+                .flatten();
+        }
+    }
+
+    pub fn runner_count(&self) -> usize {
+        self.runners.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Event opening the job of the selected runner in the browser, if it is busy
+    pub fn selected_job_url_event(&self) -> Option<GlomEvent> {
+        let idx = self.table_state.selected()?;
+        self.runners
+            .as_ref()?
+            .get(idx)?
+            .job
+            .as_ref()
+            .map(|job| GlomEvent::JobOpenUrl(job.project_id.clone(), job.pipeline_id, job.job_id))
+    }
+
+    fn rows(&self) -> Vec<Row<'static>> {
+        self.runners
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(idx, row)| {
+                let runner = &row.runner;
+                let (status, status_style) = if runner.is_online() {
+                    ("● online", theme().runner_online)
+                } else {
+                    ("○ offline", theme().runner_offline)
+                };
+
+                let activity = match (&row.job, runner.busy) {
+                    (Some(job), _) => {
+                        Span::from(job.description.to_string()).style(theme().pipeline_job)
+                    },
+                    (None, true) => Span::from("busy").style(theme().runner_busy),
+                    (None, false) => Span::from("idle").style(theme().date),
+                };
+
+                let labels = runner
+                    .labels
+                    .iter()
+                    .map(|l| l.name.as_str())
+                    .join(", ");
+
+                Row::new(vec![
+                    Cell::from(Span::from(status).style(status_style)),
+                    Cell::from(Span::from(runner.name.to_string()).style(theme().pipeline_name)),
+                    Cell::from(Span::from(runner.scope.as_str()).style(theme().pipeline_source)),
+                    Cell::from(Span::from(runner.os.to_string()).style(theme().pipeline_branch)),
+                    Cell::from(Span::from(labels).style(theme().commit_title)),
+                    Cell::from(activity),
+                ])
+                .style(theme().table_row(idx))
+            })
+            .collect()
+    }
+
+    pub fn update_popup_area(&self, screen: Rect) -> Rect {
+        let rows = self.runner_count().max(1) as u16;
+        let area = screen.inner_centered(screen.width, 3 + rows);
+        self.popup_area.set(area);
+        area
+    }
+}
+
+impl StatefulWidget for RunnersPopup {
+    type State = RunnersPopupState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let area = state.update_popup_area(area);
+
+        popup_window(
+            "Runners",
+            Some(vec![
+                ("ESC", "close"),
+                ("↑ ↓", "selection"),
+                ("r", "refresh"),
+                ("↵", "browse to job"),
+            ]),
+        )
+        .render(area, buf);
+
+        let content_area = area.inner(Margin::new(1, 1));
+        let message = match &state.runners {
+            None => Some("loading runners..."),
+            Some(runners) if runners.is_empty() => Some("no self-hosted runners"),
+            Some(_) => None,
+        };
+
+        if let Some(message) = message {
+            Text::from(Line::from(message).style(theme().project_description))
+                .render(content_area, buf);
+            return;
+        }
+
+        let header =
+            Row::new(["status", "name", "scope", "os", "labels", "job"]).style(theme().input_label);
+
+        let max_name = state
+            .runners
+            .iter()
+            .flatten()
+            .map(|r| r.runner.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(4);
+
+        let table = Table::new(
+            state.rows(),
+            [
+                Constraint::Length(9),
+                Constraint::Length(max_name as u16),
+                Constraint::Length(5),
+                Constraint::Length(7),
+                Constraint::Percentage(40),
+                Constraint::Percentage(60),
+            ],
+        )
+        .header(header)
+        .row_highlight_style(theme().highlight_symbol)
+        .column_spacing(1);
+
+        StatefulWidget::render(table, content_area, buf, &mut state.table_state);
+    }
+}
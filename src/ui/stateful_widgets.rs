@@ -1,18 +1,23 @@
-use std::sync::mpsc::Sender;
+use std::{collections::BTreeMap, sync::mpsc::Sender};
 
 use compact_str::CompactString;
 use ratatui::widgets::TableState;
 use tachyonfx::{Duration, RefRect};
 
 use crate::{
+    client::config::RunFilter,
     dispatcher::Dispatcher,
-    domain::Project,
+    domain::{Pipeline, Project},
     effect_registry::EffectRegistry,
     event::GlomEvent,
     glom_app::{GlomApp, GlomConfig, Modulo},
     id::PipelineId,
     ui::{
-        popup::{ConfigPopupState, PipelineActionsPopupState, ProjectDetailsPopupState},
+        popup::{
+            CachesPopupState, ConfigPopupState, EnvironmentsPopupState, HistoryPopupState,
+            MetricsPopupState, PipelineActionsPopupState, ProjectDetailsPopupState,
+            PullRequestsPopupState, RunFilterPopupState, RunnersPopupState,
+        },
         widget::NotificationState,
     },
 };
@@ -24,6 +29,13 @@ pub struct StatefulWidgets {
     pub config_popup_state: Option<ConfigPopupState>,
     pub project_details: Option<ProjectDetailsPopupState>,
     pub pipeline_actions: Option<PipelineActionsPopupState>,
+    pub run_filter: Option<RunFilterPopupState>,
+    pub runners: Option<RunnersPopupState>,
+    pub caches: Option<CachesPopupState>,
+    pub environments: Option<EnvironmentsPopupState>,
+    pub history: Option<HistoryPopupState>,
+    pub metrics: Option<MetricsPopupState>,
+    pub pull_requests: Option<PullRequestsPopupState>,
     pub notice: Option<NotificationState>,
     pub filter_input_active: bool,
     pub filter_input_text: CompactString,
@@ -41,6 +53,13 @@ impl StatefulWidgets {
             config_popup_state: None,
             project_details: None,
             pipeline_actions: None,
+            run_filter: None,
+            runners: None,
+            caches: None,
+            environments: None,
+            history: None,
+            metrics: None,
+            pull_requests: None,
             notice: None,
             filter_input_active: false,
             filter_input_text: CompactString::default(),
@@ -61,10 +80,41 @@ impl StatefulWidgets {
             GlomEvent::ProjectDetailsOpen(id) => {
                 let popup_area = RefRect::default();
                 effects.register_project_details(popup_area.clone());
-                self.open_project_details(app.project(id.clone()).clone(), popup_area, app.sender())
+                let run_filter = app.run_filter(id);
+                self.open_project_details(
+                    app.project(id.clone()).clone(),
+                    run_filter,
+                    app.billing_multipliers().clone(),
+                    app.queue_time_threshold(),
+                    popup_area,
+                    app.sender(),
+                )
             },
             GlomEvent::ProjectDetailsClose => self.project_details = None,
-            GlomEvent::ProjectUpdated(p) => self.refresh_project_details(p),
+            GlomEvent::JobFlakinessLoaded(id, flakiness) => {
+                if let Some(state) = self
+                    .project_details
+                    .as_mut()
+                    .filter(|pd| &pd.project.id == id)
+                {
+                    state.set_flakiness(flakiness.clone());
+                }
+            },
+            GlomEvent::RunDurationsLoaded(id, run_durations) => {
+                if let Some(state) = self
+                    .project_details
+                    .as_mut()
+                    .filter(|pd| &pd.project.id == id)
+                {
+                    state.set_run_durations(run_durations.clone());
+                }
+            },
+            GlomEvent::ProjectUpdated(p) => {
+                self.refresh_project_details(p);
+                if let Some(runners) = self.runners.as_mut() {
+                    runners.refresh_jobs(app.projects());
+                }
+            },
 
             GlomEvent::PipelineActionsClose => self.close_pipeline_actions(),
             GlomEvent::PipelineActionsOpen(project_id, pipeline_id) => {
@@ -81,6 +131,140 @@ impl StatefulWidgets {
             },
             GlomEvent::ConfigClose => self.config_popup_state = None,
 
+            GlomEvent::RunFilterOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_run_filter_popup(popup_area.clone());
+                self.run_filter = Some(RunFilterPopupState::new(
+                    id.clone(),
+                    app.run_filter(id),
+                    popup_area,
+                ));
+            },
+            GlomEvent::RunFilterClose => self.run_filter = None,
+            GlomEvent::RunFilterUpdated(id, filter) => {
+                if let Some(pd) = self
+                    .project_details
+                    .as_mut()
+                    .filter(|pd| &pd.project.id == id)
+                {
+                    pd.set_run_filter(filter);
+                }
+            },
+
+            GlomEvent::RunnersOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_runners_popup(popup_area.clone());
+                self.runners = Some(RunnersPopupState::new(id.clone(), popup_area));
+            },
+            GlomEvent::RunnersClose => self.runners = None,
+            GlomEvent::RunnersLoaded(id, runners) => {
+                if let Some(state) = self
+                    .runners
+                    .as_mut()
+                    .filter(|r| &r.project_id == id)
+                {
+                    state.set_runners(runners.clone(), app.projects());
+                }
+            },
+
+            GlomEvent::CachesOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_caches_popup(popup_area.clone());
+                self.caches = Some(CachesPopupState::new(id.clone(), popup_area));
+            },
+            GlomEvent::CachesClose => self.caches = None,
+            GlomEvent::CachesLoaded(id, usage, caches) => {
+                if let Some(state) = self
+                    .caches
+                    .as_mut()
+                    .filter(|c| &c.project_id == id)
+                {
+                    state.set_caches(usage.clone(), caches.clone());
+                }
+            },
+
+            GlomEvent::EnvironmentsOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_environments_popup(popup_area.clone());
+                self.environments = Some(EnvironmentsPopupState::new(id.clone(), popup_area));
+            },
+            GlomEvent::EnvironmentsClose => self.environments = None,
+            GlomEvent::EnvironmentsLoaded(id, environments) => {
+                if let Some(state) = self
+                    .environments
+                    .as_mut()
+                    .filter(|e| &e.project_id == id)
+                {
+                    let project = app.projects().iter().find(|p| &p.id == id);
+                    state.set_environments(environments.clone(), project);
+                }
+            },
+
+            GlomEvent::HistoryOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_history_popup(popup_area.clone());
+                self.history = Some(HistoryPopupState::new(id.clone(), popup_area));
+            },
+            GlomEvent::HistoryClose => self.history = None,
+            GlomEvent::HistoryLoaded(id, report) => {
+                if let Some(state) = self
+                    .history
+                    .as_mut()
+                    .filter(|h| &h.project_id == id)
+                {
+                    state.set_report(report.clone());
+                }
+            },
+
+            GlomEvent::MetricsOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_metrics_popup(popup_area.clone());
+                self.metrics = Some(MetricsPopupState::new(id.clone(), popup_area));
+            },
+            GlomEvent::MetricsClose => self.metrics = None,
+            GlomEvent::MetricsLoaded(id, report) => {
+                if let Some(state) = self
+                    .metrics
+                    .as_mut()
+                    .filter(|m| &m.project_id == id)
+                {
+                    state.set_report(report.clone());
+                }
+            },
+
+            GlomEvent::PullRequestsOpen(id) => {
+                let popup_area = RefRect::default();
+                effects.register_pull_requests_popup(popup_area.clone());
+                self.pull_requests = Some(PullRequestsPopupState::new(
+                    id.clone(),
+                    app.queue_time_threshold(),
+                    popup_area,
+                ));
+            },
+            GlomEvent::PullRequestsClose => self.pull_requests = None,
+            GlomEvent::PullRequestsLoaded(id, pull_requests) => {
+                if let Some(state) = self
+                    .pull_requests
+                    .as_mut()
+                    .filter(|p| &p.project_id == id)
+                {
+                    state.set_pull_requests(pull_requests.clone());
+                }
+            },
+            GlomEvent::PullRequestRunsLoaded(id, number, runs) => {
+                if let Some(state) = self
+                    .pull_requests
+                    .as_mut()
+                    .filter(|p| &p.project_id == id)
+                {
+                    let runs = runs
+                        .iter()
+                        .map(|p| Pipeline::from(p.clone()))
+                        .collect();
+                    state.set_runs(*number, runs);
+                }
+            },
+
             GlomEvent::FilterMenuShow => self.show_filter_input(),
             GlomEvent::FilterMenuClose => self.close_filter_input(),
             GlomEvent::FilterInputChar(c) => self.add_filter_char(c),
@@ -93,7 +277,21 @@ impl StatefulWidgets {
     }
 
     fn current_popup_area(&self) -> Option<RefRect> {
-        if let Some(pd) = &self.project_details {
+        if let Some(pr) = &self.pull_requests {
+            Some(pr.popup_area.clone())
+        } else if let Some(m) = &self.metrics {
+            Some(m.popup_area.clone())
+        } else if let Some(h) = &self.history {
+            Some(h.popup_area.clone())
+        } else if let Some(e) = &self.environments {
+            Some(e.popup_area.clone())
+        } else if let Some(c) = &self.caches {
+            Some(c.popup_area.clone())
+        } else if let Some(r) = &self.runners {
+            Some(r.popup_area.clone())
+        } else if let Some(rf) = &self.run_filter {
+            Some(rf.popup_area.clone())
+        } else if let Some(pd) = &self.project_details {
             Some(pd.popup_area.clone())
         } else if let Some(cp) = &self.config_popup_state {
             Some(cp.popup_area.clone())
@@ -119,6 +317,9 @@ impl StatefulWidgets {
     fn open_project_details(
         &mut self,
         project: Project,
+        run_filter: RunFilter,
+        billing_multipliers: BTreeMap<CompactString, f64>,
+        queue_threshold: chrono::Duration,
         area_tracker: RefRect,
         sender: Sender<GlomEvent>,
     ) {
@@ -128,7 +329,13 @@ impl StatefulWidgets {
             .map(|p| sender.dispatch(GlomEvent::PipelineSelected(p.id)))
             .unwrap_or(());
 
-        self.project_details = Some(ProjectDetailsPopupState::new(project, area_tracker));
+        self.project_details = Some(ProjectDetailsPopupState::new(
+            project,
+            &run_filter,
+            billing_multipliers,
+            queue_threshold,
+            area_tracker,
+        ));
     }
 
     fn open_config(&mut self, config: GlomConfig, popup_area: RefRect) {
@@ -239,6 +446,114 @@ impl StatefulWidgets {
         }
     }
 
+    pub fn handle_runner_selection(&mut self, direction: i32) {
+        let Some(runners) = self.runners.as_mut() else {
+            return;
+        };
+
+        let count = runners.runner_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = runners.table_state.selected() {
+            let new_index = (current as i32 + direction).modulo(count as i32);
+            runners
+                .table_state
+                .select(Some(new_index as usize));
+        }
+    }
+
+    pub fn handle_pull_request_selection(&mut self, direction: i32) {
+        let Some(pull_requests) = self.pull_requests.as_mut() else {
+            return;
+        };
+
+        let count = pull_requests.pull_request_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = pull_requests.table_state.selected() {
+            let new_index = (current as i32 + direction).modulo(count as i32);
+            pull_requests
+                .table_state
+                .select(Some(new_index as usize));
+        }
+    }
+
+    pub fn handle_pull_request_run_selection(&mut self, direction: i32) {
+        let Some(pull_requests) = self.pull_requests.as_mut() else {
+            return;
+        };
+
+        let count = pull_requests.run_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = pull_requests.runs_table_state.selected() {
+            let new_index = (current as i32 + direction).modulo(count as i32);
+            pull_requests
+                .runs_table_state
+                .select(Some(new_index as usize));
+        }
+    }
+
+    pub fn handle_history_selection(&mut self, direction: i32) {
+        let Some(history) = self.history.as_mut() else {
+            return;
+        };
+
+        let count = history.red_streak_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = history.table_state.selected() {
+            let new_index = (current as i32 + direction).modulo(count as i32);
+            history
+                .table_state
+                .select(Some(new_index as usize));
+        }
+    }
+
+    pub fn handle_environment_selection(&mut self, direction: i32) {
+        let Some(environments) = self.environments.as_mut() else {
+            return;
+        };
+
+        let count = environments.environment_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = environments.table_state.selected() {
+            let new_index = (current as i32 + direction).modulo(count as i32);
+            environments
+                .table_state
+                .select(Some(new_index as usize));
+        }
+    }
+
+    pub fn handle_cache_selection(&mut self, direction: i32) {
+        let Some(caches) = self.caches.as_mut() else {
+            return;
+        };
+
+        let count = caches.cache_count();
+        if count == 0 {
+            return;
+        }
+
+        if let Some(current) = caches.table_state.selected() {
+            let new_index = (current as i32 + direction).modulo(count as i32);
+            caches
+                .table_state
+                .select(Some(new_index as usize));
+        }
+    }
+
     fn show_filter_input(&mut self) {
         self.filter_input_active = true;
         // Start with the current temporary filter or empty string
@@ -12,6 +12,7 @@ use crate::{
     notice_service::{Notice, NoticeMessage},
     stores::ProjectStore,
     theme::theme,
+    ui::format_duration,
 };
 
 #[derive(Clone)]
@@ -40,6 +41,9 @@ impl NotificationState {
             | NoticeMessage::GithubGetPipelinesError(ref id, _, _) => {
                 project_lookup.find(id.clone()).map(|p| p.title())
             },
+            NoticeMessage::RunDurationRegressed(ref r) => project_lookup
+                .find(r.project_id.clone())
+                .map(|p| p.title()),
         };
 
         Self { notice, project_name, content_area }
@@ -104,6 +108,17 @@ impl StatefulWidget for Notification {
                 Span::from("Log level changed to "),
                 Span::from(format!("{level:?}")).style(theme().notification_project),
             ]),
+            NoticeMessage::RunDurationRegressed(r) => Line::from(vec![
+                Span::from(r.workflow.as_str()).style(theme().notification_project),
+                Span::from(" in "),
+                Span::from(project).style(theme().notification_project),
+                Span::from(format!(
+                    " took {}, above its p{:.0} of {}",
+                    format_duration(r.duration),
+                    r.percentile,
+                    format_duration(r.threshold)
+                )),
+            ]),
         };
 
         let text_len = (text.width() as u16).min(area.width - 2);
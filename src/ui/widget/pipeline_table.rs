@@ -1,5 +1,7 @@
-use chrono::Local;
-use compact_str::ToCompactString;
+use std::collections::HashSet;
+
+use chrono::{Duration, Local};
+use compact_str::{CompactString, ToCompactString, format_compact};
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Rect},
@@ -17,18 +19,39 @@ use crate::{
 ///
 /// Each pipeline is represented as a row in the table, with the following format:
 /// ```text
-/// #BRANCH| WORKFLOW NAME | PIPELNE/JOB | TIME   | COMMENT
-/// main   | CI/CD         | 🔵🔵🔵🔵🔵 | 14m24s | Merge branch 'renovate/all-minor-dependencies'
-///        |               | deploy-prod |  3m23s |  into 'main'
+/// #BRANCH| WORKFLOW NAME | PIPELNE/JOB | TIME   | QUEUE        | COMMENT
+/// main   | CI/CD         | 🔵🔵🔵🔵🔵 | 14m24s |        0:42  | Merge branch 'renovate/all-minor-dependencies'
+///        |               | deploy-prod |  3m23s | deploy 6:10  |  into 'main'
 /// ```
+///
+/// The queue column shows the total time the run's jobs waited for runners, and the
+/// longest waiting job if it waited longer than the queue time threshold.
 #[derive(Clone)]
 pub struct PipelineTable {
-    pub constraints: [Constraint; 6],
+    pub constraints: [Constraint; 7],
     pub rows: Vec<Row<'static>>,
 }
 
 impl PipelineTable {
-    pub fn new(pipelines: &[&Pipeline]) -> Self {
+    pub fn new(pipelines: &[&Pipeline], queue_threshold: Duration) -> Self {
+        Self::with_likely_flaky(pipelines, &HashSet::new(), queue_threshold)
+    }
+
+    /// Marks failures of the given jobs as likely flaky
+    pub fn with_likely_flaky(
+        pipelines: &[&Pipeline],
+        likely_flaky: &HashSet<&str>,
+        queue_threshold: Duration,
+    ) -> Self {
+        let max_queue = pipelines
+            .iter()
+            .map(|p| {
+                Self::starved_job_text(p, queue_threshold)
+                    .chars()
+                    .count()
+            })
+            .fold(5, usize::max);
+
         let (max_branch, max_workflow_name, max_job_name, max_failed_job_name, max_duration) =
             pipelines
                 .iter()
@@ -36,14 +59,20 @@ impl PipelineTable {
                     (
                         b.max(p.branch.chars().count()),
                         w.max(p.name.chars().count()),
-                        j.max(p.active_job_name().chars().count())
-                            .max(p.jobs.clone().map(|j| j.len() * 2).unwrap_or(0)),
+                        j.max(p.active_job_name().chars().count()).max(
+                            p.jobs.as_ref().map_or(2, |j| j.len() * 2)
+                                + p.checks
+                                    .as_ref()
+                                    .filter(|c| !c.checks.is_empty())
+                                    .map_or(0, |c| 1 + c.checks.len() * 2),
+                        ),
                         f.max(
-                            p.failing_job_name()
+                            Self::failing_job_text(p, likely_flaky)
                                 .map(|j| j.chars().count())
                                 .unwrap_or(0),
                         ),
-                        d.max(format_duration(p.duration()).chars().count()),
+                        d.max(format_duration(p.duration()).chars().count())
+                            .max(Self::billable_text(p).chars().count()),
                         // pe.max("NA%".chars().count()),
                     )
                 });
@@ -55,18 +84,23 @@ impl PipelineTable {
                 Constraint::Length(max_workflow_name as u16),
                 Constraint::Length(max_job_name.max(max_failed_job_name) as u16),
                 Constraint::Length(max_duration as u16),
+                Constraint::Length(max_queue as u16),
                 Constraint::Percentage(100),
             ],
             rows: pipelines
                 .iter()
-                .map(|p| Self::parse_row(p))
+                .map(|p| Self::parse_row(p, likely_flaky, queue_threshold))
                 .enumerate()
                 .map(|(idx, r)| r.style(theme().table_row(idx)))
                 .collect(),
         }
     }
 
-    fn parse_row(p: &Pipeline) -> Row<'static> {
+    fn parse_row(
+        p: &Pipeline,
+        likely_flaky: &HashSet<&str>,
+        queue_threshold: Duration,
+    ) -> Row<'static> {
         let branch = p.branch.clone();
 
         let comment = if let Some(commit) = &p.commit {
@@ -86,23 +120,32 @@ impl PipelineTable {
             Cell::from(text_from(p.created_at.with_timezone(&Local))),
             branch_cell,
             Cell::from(Span::from(p.name.to_string()).style(theme().pipeline_name)),
-            Self::pipeline_jobs_cell(p),
+            Self::pipeline_jobs_cell(p, likely_flaky),
             Self::pipeline_duration_cell(p),
+            Self::pipeline_queue_cell(p, queue_threshold),
             // Self::pipeline_percentages_cell(p),
             Cell::from(Span::from(comment).style(theme().commit_title)),
         ])
         .height(2)
     }
 
-    fn pipeline_jobs_cell(p: &Pipeline) -> Cell<'static> {
-        // let branch_name = if Some(p.failing_job_name()) {
-        //
-        // }
+    /// Name of the failing job, noting when its failures are likely flaky
+    fn failing_job_text(p: &Pipeline, likely_flaky: &HashSet<&str>) -> Option<CompactString> {
+        p.failing_job_name()
+            .map(|name| match likely_flaky.contains(name.as_str()) {
+                true => format_compact!("{name} (likely flaky)"),
+                false => name,
+            })
+    }
 
-        let branch_name = if let Some(name) = p.failing_job_name() {
-            Line::from(name.to_string()).style(theme().pipeline_job_failed)
-        } else {
-            Line::from(p.active_job_name().to_string()).style(theme().pipeline_job)
+    fn pipeline_jobs_cell(p: &Pipeline, likely_flaky: &HashSet<&str>) -> Cell<'static> {
+        let branch_name = match p.failing_job_name() {
+            Some(name) if likely_flaky.contains(name.as_str()) => Line::from(vec![
+                Span::from(name.to_string()).style(theme().pipeline_job_failed),
+                Span::from(" (likely flaky)").style(theme().status_pending),
+            ]),
+            Some(name) => Line::from(name.to_string()).style(theme().pipeline_job_failed),
+            None => Line::from(p.active_job_name().to_string()).style(theme().pipeline_job),
         };
 
         let content = Text::from(vec![Line::from(p.icon().to_string()), branch_name]);
@@ -111,24 +154,62 @@ impl PipelineTable {
     }
 
     fn pipeline_duration_cell(p: &Pipeline) -> Cell<'static> {
-        let active_job_duration = p
-            .active_job()
-            .map(|j| j.duration())
-            .map(format_duration)
-            .unwrap_or("".to_compact_string());
+        // completed runs show their billable time in place of the active job's duration
+        let second_line = match p.active_job() {
+            Some(job) => {
+                Line::from(format_duration(job.duration()).to_string()).style(theme().time)
+            },
+            None => Line::from(Self::billable_text(p).to_string()).style(theme().date),
+        };
 
         let duration = p.duration();
         let content = Text::from(vec![
             Line::from(format_duration(duration).to_string())
                 .style(theme().time)
                 .alignment(Alignment::Right),
-            Line::from(active_job_duration.to_string())
-                .style(theme().time)
+            second_line.alignment(Alignment::Right),
+        ]);
+
+        Cell::from(content)
+    }
+}
+
+impl PipelineTable {
+    /// Longest waiting job and its queue time, if above the threshold
+    fn starved_job_text(p: &Pipeline, queue_threshold: Duration) -> CompactString {
+        p.longest_queued_job()
+            .filter(|(_, queue_time)| *queue_time > queue_threshold)
+            .map(|(job, queue_time)| {
+                format_compact!("{} {}", job.name, format_duration(queue_time))
+            })
+            .unwrap_or_default()
+    }
+
+    fn pipeline_queue_cell(p: &Pipeline, queue_threshold: Duration) -> Cell<'static> {
+        let queue_time = p
+            .queue_time()
+            .map(|t| format_duration(t).to_string())
+            .unwrap_or_default();
+
+        let content = Text::from(vec![
+            Line::from(queue_time)
+                .style(theme().date)
+                .alignment(Alignment::Right),
+            Line::from(Self::starved_job_text(p, queue_threshold).to_string())
+                .style(theme().status_pending)
                 .alignment(Alignment::Right),
         ]);
 
         Cell::from(content)
     }
+
+    fn billable_text(p: &Pipeline) -> CompactString {
+        p.billable
+            .as_ref()
+            .filter(|b| !b.by_os.is_empty())
+            .map(|b| format_compact!("{} billed", format_duration(b.total())))
+            .unwrap_or_default()
+    }
 }
 
 impl StatefulWidget for PipelineTable {
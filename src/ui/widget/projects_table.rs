@@ -17,6 +17,8 @@ pub struct ProjectsTable<'a> {
     rows: Vec<Row<'a>>,
     filter_active: bool,
     filter_text: &'a str,
+    /// Projects are restored from the previous session and not yet refreshed
+    stale: bool,
 }
 
 impl<'a> ProjectsTable<'a> {
@@ -30,8 +32,14 @@ impl<'a> ProjectsTable<'a> {
                 .collect(),
             filter_active,
             filter_text,
+            stale: false,
         }
     }
+
+    pub fn with_stale(mut self, stale: bool) -> Self {
+        self.stale = stale;
+        self
+    }
 }
 
 impl StatefulWidget for ProjectsTable<'_> {
@@ -51,6 +59,7 @@ impl StatefulWidget for ProjectsTable<'_> {
                 ("f/", "filter"),
                 ("r", "refresh"),
                 ("p", "pipeline refresh"),
+                ("x", "export"),
                 ("↑↓", "selection"),
                 ("↵", "details"),
             ])
@@ -70,10 +79,19 @@ impl StatefulWidget for ProjectsTable<'_> {
             (area, None)
         };
 
+        let stale_title = if self.stale {
+            Line::from(" cached, refreshing… ")
+                .style(theme().date)
+                .right_aligned()
+        } else {
+            Line::default()
+        };
+
         // Render main table block
         Block::new()
             .title(" github pipelines ")
             .title_style(theme().border_title)
+            .title(stale_title)
             .title_bottom(shortcuts.as_line())
             .borders(if self.filter_active {
                 Borders::TOP | Borders::LEFT | Borders::RIGHT
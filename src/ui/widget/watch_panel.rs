@@ -0,0 +1,128 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Margin, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Row, Table, Widget},
+};
+
+use crate::{
+    domain::{IconRepresentable, Pipeline},
+    id::ProjectId,
+    theme::theme,
+    ui::{format_duration, widget::Shortcuts},
+    watch::{WatchItem, WatchedRuns},
+};
+
+/// Rows shown before the panel stops growing
+const MAX_ROWS: usize = 8;
+
+/// watched runs and branches, pinned above the projects table.
+///
+/// Each run of a watched item is a row:
+/// ```text
+/// 🔵🔵🟢🕒 | owner/repo | main | CI | build | 4m12s
+/// ```
+pub struct WatchPanel<'a> {
+    rows: Vec<Row<'a>>,
+}
+
+impl<'a> WatchPanel<'a> {
+    pub fn new(watched: &[WatchedRuns<'a>]) -> Self {
+        let rows = watched
+            .iter()
+            .flat_map(|w| match w.runs.as_slice() {
+                [] => vec![Self::waiting_row(w.item)],
+                runs => runs.iter().map(|p| Self::run_row(p)).collect(),
+            })
+            .take(MAX_ROWS)
+            .enumerate()
+            .map(|(idx, r)| r.style(theme().table_row(idx)))
+            .collect();
+
+        Self { rows }
+    }
+
+    /// Height of the panel with its borders; zero without watched items
+    pub fn height(&self) -> u16 {
+        match self.rows.len() {
+            0 => 0,
+            n => n as u16 + 2,
+        }
+    }
+
+    fn run_row(p: &'a Pipeline) -> Row<'a> {
+        let has_jobs = p
+            .jobs
+            .as_ref()
+            .is_some_and(|jobs| !jobs.is_empty());
+        let duration = match p.status.is_active() || has_jobs {
+            true => format_duration(p.duration()),
+            false => Default::default(),
+        };
+
+        Row::new(vec![
+            Cell::from(p.icon().to_string()),
+            Self::project_cell(&p.project_id),
+            Cell::from(Span::from(p.branch.as_str()).style(theme().pipeline_branch)),
+            Cell::from(Span::from(p.name.as_str()).style(theme().pipeline_name)),
+            Cell::from(Span::from(p.active_job_name().to_string()).style(theme().pipeline_job)),
+            Cell::from(
+                Line::from(duration.to_string())
+                    .style(theme().time)
+                    .alignment(Alignment::Right),
+            ),
+        ])
+    }
+
+    fn waiting_row(item: &'a WatchItem) -> Row<'a> {
+        let branch = match item {
+            WatchItem::Branch { branch, .. } => branch.as_str(),
+            WatchItem::Run { .. } => "",
+        };
+
+        Row::new(vec![
+            Cell::from(""),
+            Self::project_cell(item.project_id()),
+            Cell::from(Span::from(branch).style(theme().pipeline_branch)),
+            Cell::from(Span::from("waiting for runs…").style(theme().date)),
+        ])
+    }
+
+    fn project_cell(id: &ProjectId) -> Cell<'a> {
+        Cell::from(Span::from(id.to_string()).style(theme().project_name))
+    }
+}
+
+impl Widget for WatchPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let shortcuts = Shortcuts::from(vec![("u", "unwatch all")]);
+
+        Block::new()
+            .title(" watching ")
+            .title_style(theme().border_title)
+            .title_bottom(shortcuts.as_line())
+            .borders(Borders::ALL)
+            .border_style(theme().table_border)
+            .style(theme().background)
+            .border_type(BorderType::Plain)
+            .render(area, buf);
+
+        let content_area = area.inner(Margin::new(2, 1));
+        Widget::render(
+            Table::new(self.rows, WATCH_COLUMN_CONSTRAINTS).column_spacing(1),
+            content_area,
+            buf,
+        );
+    }
+}
+
+const WATCH_COLUMN_CONSTRAINTS: [Constraint; 6] = [
+    Constraint::Length(16),      // job icons
+    Constraint::Min(30),         // project name
+    Constraint::Length(20),      // branch
+    Constraint::Length(20),      // workflow
+    Constraint::Percentage(100), // active job
+    Constraint::Length(8),       // duration
+];
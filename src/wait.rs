@@ -0,0 +1,302 @@
+//! Waits for the workflow runs of a commit to complete, showing their jobs inline
+
+use std::{
+    collections::HashSet,
+    io::{IsTerminal, Write, stdout},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use crossterm::{
+    cursor::MoveToPreviousLine,
+    queue,
+    style::{Color, Stylize},
+    terminal::{Clear, ClearType},
+};
+use tokio::runtime::Runtime;
+
+use crate::{
+    client::{
+        ClientConfig, ClientError, GithubApi,
+        config::{PipelineQuery, RunFilter},
+    },
+    domain::{JobDto, PipelineDto, PipelineStatus},
+    id::ProjectId,
+    ui::format_duration,
+};
+
+pub struct WaitOptions {
+    pub project_id: ProjectId,
+    pub head_sha: CompactString,
+    /// Only wait for these workflows, by name; for all runs of the commit if empty
+    pub workflows: Vec<String>,
+    pub timeout: Duration,
+    pub interval: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitOutcome {
+    Succeeded,
+    Failed,
+    TimedOut,
+}
+
+struct RunProgress {
+    run: PipelineDto,
+    jobs: Vec<JobDto>,
+}
+
+/// Polls the commit's runs and their jobs until all of them completed or the timeout
+/// expired. The commit's runs may not exist yet right after a push, so it also waits
+/// for the first run, and for a run of each named workflow.
+pub fn wait_for_runs(
+    rt: &Runtime,
+    api: &GithubApi,
+    config: &ClientConfig,
+    options: &WaitOptions,
+) -> Result<WaitOutcome, ClientError> {
+    let query = config
+        .default_pipeline_query()
+        .with_filter(RunFilter {
+            head_sha: Some(options.head_sha.clone()),
+            ..RunFilter::default()
+        });
+
+    let deadline = Instant::now() + options.timeout;
+    let mut view = ProgressView::new();
+    loop {
+        match poll(rt, api, &query, options) {
+            Ok(progress) => {
+                let missing: Vec<&str> = options
+                    .workflows
+                    .iter()
+                    .filter(|w| !progress.iter().any(|p| p.run.name == w.as_str()))
+                    .map(String::as_str)
+                    .collect();
+
+                view.render(options, &progress, &missing);
+
+                let completed = !progress.is_empty()
+                    && missing.is_empty()
+                    && progress
+                        .iter()
+                        .all(|p| !run_status(&p.run).is_active());
+                if completed {
+                    let succeeded = progress
+                        .iter()
+                        .all(|p| is_success(&run_status(&p.run)));
+                    return Ok(if succeeded {
+                        WaitOutcome::Succeeded
+                    } else {
+                        WaitOutcome::Failed
+                    });
+                }
+            },
+            // keep waiting through timeouts and dropped connections
+            Err(e) if e.is_retryable() => {},
+            Err(e) => return Err(e),
+        }
+
+        match deadline.checked_duration_since(Instant::now()) {
+            Some(remaining) if !remaining.is_zero() => {
+                thread::sleep(options.interval.min(remaining))
+            },
+            _ => return Ok(WaitOutcome::TimedOut),
+        }
+    }
+}
+
+/// Runs of the commit, limited to the named workflows, with their jobs
+fn poll(
+    rt: &Runtime,
+    api: &GithubApi,
+    query: &PipelineQuery,
+    options: &WaitOptions,
+) -> Result<Vec<RunProgress>, ClientError> {
+    let runs = rt
+        .block_on(api.get_pipelines(options.project_id.clone(), query))?
+        .into_iter()
+        .filter(|run| {
+            options.workflows.is_empty()
+                || options
+                    .workflows
+                    .iter()
+                    .any(|w| w == run.name.as_str())
+        });
+
+    let mut progress = Vec::new();
+    for run in runs {
+        let jobs = rt.block_on(api.get_jobs(options.project_id.clone(), run.id))?;
+        progress.push(RunProgress { run, jobs });
+    }
+
+    Ok(progress)
+}
+
+fn run_status(run: &PipelineDto) -> PipelineStatus {
+    PipelineStatus::resolve(run.status.clone(), run.conclusion.clone())
+}
+
+/// Skipped and neutral runs or jobs don't fail a commit
+fn is_success(status: &PipelineStatus) -> bool {
+    matches!(
+        status,
+        PipelineStatus::Success | PipelineStatus::Skipped | PipelineStatus::Neutral
+    )
+}
+
+/// Line of the progress view
+struct ProgressLine {
+    symbol: &'static str,
+    color: Color,
+    text: String,
+    /// jobs are indented below their run on a terminal
+    indented: bool,
+}
+
+impl ProgressLine {
+    fn new(status: &PipelineStatus, text: String) -> Self {
+        let (symbol, color) = match status {
+            s if is_success(s) => ("✓", Color::Green),
+            s if s.is_failure() => ("✗", Color::Red),
+            s if s.is_active() => ("●", Color::Yellow),
+            _ => ("○", Color::DarkGrey),
+        };
+
+        Self { symbol, color, text, indented: false }
+    }
+}
+
+/// Redraws the runs in place on a terminal; otherwise only prints lines as they change,
+/// leaving out the elapsed time of active runs and jobs
+struct ProgressView {
+    is_terminal: bool,
+    lines_drawn: u16,
+    printed: HashSet<String>,
+}
+
+impl ProgressView {
+    fn new() -> Self {
+        Self {
+            is_terminal: stdout().is_terminal(),
+            lines_drawn: 0,
+            printed: HashSet::new(),
+        }
+    }
+
+    fn render(&mut self, options: &WaitOptions, progress: &[RunProgress], missing: &[&str]) {
+        let now = Utc::now();
+        let sha = options
+            .head_sha
+            .get(..7)
+            .unwrap_or(&options.head_sha);
+
+        let mut lines = vec![ProgressLine {
+            symbol: "»",
+            color: Color::Blue,
+            text: format!("{}@{sha}", options.project_id),
+            indented: false,
+        }];
+        if progress.is_empty() && missing.is_empty() {
+            lines.push(ProgressLine::new(
+                &PipelineStatus::Waiting,
+                "waiting for runs to start".into(),
+            ));
+        }
+
+        for p in progress {
+            let status = run_status(&p.run);
+            let started = p.run.run_started_at.unwrap_or(p.run.created_at);
+            let finished = (!status.is_active()).then_some(p.run.updated_at);
+            lines.push(ProgressLine::new(
+                &status,
+                format!(
+                    "{} ({}) {}{}",
+                    p.run.name,
+                    p.run.event.to_string(),
+                    status.as_api_str(),
+                    self.elapsed(started, finished, now),
+                ),
+            ));
+
+            for job in &p.jobs {
+                let status = PipelineStatus::resolve(job.status.clone(), job.conclusion.clone());
+                let elapsed = job
+                    .started_at
+                    .map(|started| self.elapsed(started, job.completed_at, now))
+                    .unwrap_or_default();
+                let name = if self.is_terminal {
+                    job.name.to_string()
+                } else {
+                    format!("{} / {}", p.run.name, job.name)
+                };
+
+                lines.push(ProgressLine {
+                    indented: self.is_terminal,
+                    ..ProgressLine::new(&status, format!("{name} {}{elapsed}", status.as_api_str()))
+                });
+            }
+        }
+
+        lines.extend(missing.iter().map(|workflow| {
+            ProgressLine::new(&PipelineStatus::Waiting, format!("{workflow} not started"))
+        }));
+
+        if self.is_terminal {
+            self.redraw(&lines);
+        } else {
+            for line in lines {
+                let line = format!("{} {}", line.symbol, line.text);
+                if self.printed.insert(line.clone()) {
+                    println!("{line}");
+                }
+            }
+        }
+    }
+
+    fn redraw(&mut self, lines: &[ProgressLine]) {
+        let width = crossterm::terminal::size()
+            .map(|(w, _)| w as usize)
+            .unwrap_or(80);
+
+        let mut out = stdout().lock();
+        if self.lines_drawn > 0 {
+            let _ = queue!(
+                out,
+                MoveToPreviousLine(self.lines_drawn),
+                Clear(ClearType::FromCursorDown)
+            );
+        }
+
+        for line in lines {
+            let indent = if line.indented { "  " } else { "" };
+            // wrapped lines would throw off the redraw
+            let text: String = line
+                .text
+                .chars()
+                .take(width.saturating_sub(indent.len() + 3))
+                .collect();
+            let _ = writeln!(out, "{indent}{} {text}", line.symbol.with(line.color));
+        }
+        let _ = out.flush();
+
+        self.lines_drawn = lines.len() as u16;
+    }
+
+    /// Duration of a run or job; of active ones only on a terminal, as it changes on
+    /// every poll
+    fn elapsed(
+        &self,
+        started: DateTime<Utc>,
+        finished: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> String {
+        match finished {
+            Some(finished) => format!(" {}", format_duration(finished - started)),
+            None if self.is_terminal => format!(" {}", format_duration(now - started)),
+            None => String::new(),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! Runs and branches pinned to the top of the main screen until they finish, kept
+//! in a small state file across restarts
+
+use std::{fs, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use compact_str::CompactString;
+use directories::BaseDirs;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::{
+    domain::{Pipeline, Project},
+    id::{PipelineId, ProjectId},
+    snapshot::write_json,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum WatchItem {
+    /// A single run, until it completes
+    Run { project_id: ProjectId, run_id: PipelineId },
+    /// The runs of a branch, until none are left queued or in progress
+    Branch { project_id: ProjectId, branch: CompactString },
+}
+
+/// A watch item along with when it was added, telling the runs completed since
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WatchEntry {
+    #[serde(flatten)]
+    item: WatchItem,
+    /// Items saved before this was recorded count from the time they are loaded
+    #[serde(default = "Utc::now")]
+    added_at: DateTime<Utc>,
+}
+
+/// A watch item with the runs it currently refers to
+#[derive(Debug, Clone)]
+pub struct WatchedRuns<'a> {
+    pub item: &'a WatchItem,
+    /// Empty until the watched run, or a run of the watched branch, is loaded
+    pub runs: Vec<&'a Pipeline>,
+}
+
+#[derive(Debug)]
+pub struct WatchList {
+    path: PathBuf,
+    items: Vec<WatchEntry>,
+}
+
+pub fn default_watch_path() -> PathBuf {
+    if let Some(dirs) = BaseDirs::new() {
+        dirs.data_local_dir()
+            .join("glom")
+            .join("watches.json")
+    } else {
+        PathBuf::from("glom-watches.json")
+    }
+}
+
+impl WatchItem {
+    pub fn project_id(&self) -> &ProjectId {
+        match self {
+            WatchItem::Run { project_id, .. } | WatchItem::Branch { project_id, .. } => project_id,
+        }
+    }
+
+    pub fn matches(&self, pipeline: &Pipeline) -> bool {
+        match self {
+            WatchItem::Run { project_id, run_id } => {
+                &pipeline.project_id == project_id && &pipeline.id == run_id
+            },
+            WatchItem::Branch { project_id, branch } => {
+                &pipeline.project_id == project_id && pipeline.branch == *branch
+            },
+        }
+    }
+}
+
+impl WatchList {
+    /// Loads the watch list, starting empty if the file is missing or unreadable
+    pub fn open(path: PathBuf) -> Self {
+        let items = match fs::read(&path) {
+            Ok(content) => serde_json::from_slice(&content)
+                .inspect_err(|e| warn!(path = %path.display(), error = %e, "Discarding unreadable watch list"))
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+
+        Self { path, items }
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &WatchItem> {
+        self.items.iter().map(|entry| &entry.item)
+    }
+
+    pub fn is_watched(&self, pipeline: &Pipeline) -> bool {
+        self.items().any(|item| item.matches(pipeline))
+    }
+
+    /// Watches the item, or dismisses it if already watched
+    pub fn toggle(&mut self, item: WatchItem) {
+        match self
+            .items
+            .iter()
+            .position(|entry| entry.item == item)
+        {
+            Some(idx) => _ = self.items.remove(idx),
+            None => self
+                .items
+                .push(WatchEntry { item, added_at: Utc::now() }),
+        }
+
+        self.save();
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.save();
+    }
+
+    /// Drops the items finished by the completed run: the run itself, and its branch
+    /// unless other runs of the branch are still active
+    pub fn complete(&mut self, pipeline: &Pipeline, project: Option<&Project>) {
+        let branch_active = project
+            .and_then(|p| p.pipelines.as_ref())
+            .is_some_and(|runs| {
+                runs.iter().any(|p| {
+                    p.branch == pipeline.branch
+                        && p.id != pipeline.id
+                        && (p.status.is_active() || p.has_active_jobs())
+                })
+            });
+
+        let count = self.items.len();
+        self.items
+            .retain(|WatchEntry { item, .. }| match item {
+                WatchItem::Run { .. } => !item.matches(pipeline),
+                WatchItem::Branch { .. } => branch_active || !item.matches(pipeline),
+            });
+
+        if self.items.len() != count {
+            self.save();
+        }
+    }
+
+    /// Drops the items of the project whose runs completed since they were added without
+    /// a `RunCompleted`, e.g. while glom was closed: runs loaded and no longer active, and
+    /// branches without active runs. Runs already finished when they were watched, and
+    /// idle branches, stay until dismissed or until a later run of the branch completes
+    pub fn prune(&mut self, project: &Project) {
+        // the runs are cleared while they are reloaded, e.g. with another run filter
+        let Some(runs) = project
+            .pipelines
+            .as_ref()
+            .filter(|runs| !runs.is_empty())
+        else {
+            return;
+        };
+        let is_active = |p: &Pipeline| p.status.is_active() || p.has_active_jobs();
+
+        let count = self.items.len();
+        self.items
+            .retain(|WatchEntry { item, added_at }| {
+                if item.project_id() != &project.id {
+                    return true;
+                }
+
+                let completed_since = |p: &Pipeline| !is_active(p) && p.updated_at > *added_at;
+                let mut item_runs = runs.iter().filter(|p| item.matches(p));
+                match item {
+                    WatchItem::Run { .. } => !item_runs.any(completed_since),
+                    WatchItem::Branch { .. } => {
+                        let item_runs: Vec<&Pipeline> = item_runs.collect();
+                        item_runs.iter().any(|p| is_active(p))
+                            || !item_runs.iter().any(|p| completed_since(p))
+                    },
+                }
+            });
+
+        if self.items.len() != count {
+            self.save();
+        }
+    }
+
+    /// Runs of each watch item, as currently known; a branch shows its active runs,
+    /// or else its latest run
+    pub fn resolve<'a>(&'a self, projects: &'a [Project]) -> Vec<WatchedRuns<'a>> {
+        self.items()
+            .map(|item| {
+                let runs: Vec<&Pipeline> = projects
+                    .iter()
+                    .find(|p| &p.id == item.project_id())
+                    .and_then(|p| p.pipelines.as_ref())
+                    .map(|runs| runs.iter().filter(|p| item.matches(p)).collect())
+                    .unwrap_or_default();
+
+                let active: Vec<&Pipeline> = runs
+                    .iter()
+                    .copied()
+                    .filter(|p| p.status.is_active() || p.has_active_jobs())
+                    .collect();
+
+                // runs are sorted by their last update, newest first
+                let runs = match item {
+                    WatchItem::Branch { .. } if active.is_empty() => {
+                        runs.into_iter().take(1).collect()
+                    },
+                    WatchItem::Branch { .. } => active,
+                    WatchItem::Run { .. } => runs,
+                };
+
+                WatchedRuns { item, runs }
+            })
+            .collect()
+    }
+
+    /// Replaces the state file atomically
+    fn save(&self) {
+        match write_json(&self.path, &self.items) {
+            Ok(()) => {
+                debug!(path = %self.path.display(), count = self.items.len(), "Saved watch list")
+            },
+            Err(e) => warn!(path = %self.path.display(), error = %e, "Failed to save watch list"),
+        }
+    }
+}